@@ -0,0 +1,56 @@
+use std::collections::hash_map::RandomState;
+use std::collections::HashSet;
+use std::hash::BuildHasher;
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use lei::LEI;
+
+/// Distinct, valid LEIs sharing a handful of LOU ID prefixes, as in GLEIF data.
+fn leis(count: usize) -> Vec<LEI> {
+    const LOU_IDS: [&str; 4] = ["5493", "2138", "9598", "3157"];
+    (0..count)
+        .map(|i| {
+            let payload = format!("{}{:014}", LOU_IDS[i % LOU_IDS.len()], i);
+            lei::build_from_payload(&payload).unwrap()
+        })
+        .collect()
+}
+
+fn hash(c: &mut Criterion) {
+    let leis = leis(100_000);
+    let packed: Vec<u128> = leis.iter().map(LEI::to_u128).collect();
+    let state = RandomState::new();
+
+    let mut group = c.benchmark_group("hash");
+
+    group.bench_function("lei", |b| {
+        b.iter(|| {
+            for lei in &leis {
+                black_box(state.hash_one(lei));
+            }
+        })
+    });
+
+    // The u128 form hashes as a single integer, but LEI cannot use it because LEI must hash the
+    // same as its str for Borrow<str> lookups.
+    group.bench_function("u128", |b| {
+        b.iter(|| {
+            for n in &packed {
+                black_box(state.hash_one(n));
+            }
+        })
+    });
+
+    group.finish();
+
+    c.bench_function("hash_set_insert", |b| {
+        b.iter_batched(
+            || leis.clone(),
+            |leis| black_box(leis.into_iter().collect::<HashSet<LEI>>()),
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+criterion_group!(benches, hash);
+criterion_main!(benches);
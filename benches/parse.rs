@@ -0,0 +1,52 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+const VALUES: [&str; 5] = [
+    "YZ83GD8L7GG84979J516",
+    "549300IYKILIU506KA05",
+    "95980020140005346817",
+    "5493002F3N6V3Z14SP04",
+    "AJ6VL0Z1WDC42KKJZO20",
+];
+
+fn parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+
+    group.bench_function("parse", |b| {
+        b.iter(|| {
+            for value in VALUES {
+                black_box(lei::parse(black_box(value)).unwrap());
+            }
+        })
+    });
+
+    // What parse() used to do: copy the input into a String before validating.
+    group.bench_function("copy_then_parse", |b| {
+        b.iter(|| {
+            for value in VALUES {
+                let copy: String = black_box(value).into();
+                black_box(lei::parse(&copy).unwrap());
+            }
+        })
+    });
+
+    group.bench_function("parse_loose", |b| {
+        b.iter(|| {
+            for value in VALUES {
+                black_box(lei::parse_loose(black_box(value)).unwrap());
+            }
+        })
+    });
+
+    group.bench_function("validate", |b| {
+        b.iter(|| {
+            for value in VALUES {
+                black_box(lei::validate(black_box(value)));
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
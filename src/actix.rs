@@ -0,0 +1,353 @@
+#![warn(missing_docs)]
+//! # lei::actix
+//!
+//! [actix-web](https://crates.io/crates/actix-web) extractors for LEIs in requests, mirroring
+//! `lei::axum`. `LeiPath` takes the route's single path parameter with actix-web's
+//! `Path<String>`, and `LeiQuery` and `LeiForm` take the `lei` parameter with its `Query` and
+//! `Form`. All three parse the value and reject requests carrying an invalid LEI with
+//! `422 Unprocessable Entity` and [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457) problem
+//! details:
+//!
+//! ```json
+//! {"type":"about:blank","title":"Unprocessable Entity","status":422,
+//!  "detail":"incorrect check digits \"17\" when expecting \"16\"","code":"LEI-E009",
+//!  "value":"YZ83GD8L7GG84979J517"}
+//! ```
+//!
+//! Values are parsed strictly unless the app registers `LeiConfig::new().loose()` as app data, in
+//! which case they are parsed with `parse_loose()`. Other failures are described the same way,
+//! with the status actix-web's own extractors would use. For other parameters, extract them with
+//! actix-web and call `LeiConfig::parse()`, whose error is the same rejection.
+//!
+//! ```
+//! use actix_web::{web, App};
+//! use lei::actix::{LeiConfig, LeiPath};
+//!
+//! async fn entity(LeiPath(lei): LeiPath) -> String {
+//!     format!("LOU {}", lei.lou_id())
+//! }
+//!
+//! let app = App::new()
+//!     .app_data(LeiConfig::new().loose())
+//!     .route("/entities/{lei}", web::get().to(entity));
+//! ```
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::future::Future;
+use std::ops::Deref;
+use std::pin::Pin;
+
+use ::actix_web::dev::Payload;
+use ::actix_web::http::StatusCode;
+use ::actix_web::web::{Form, Path, Query};
+use ::actix_web::{FromRequest, HttpRequest, HttpResponse, ResponseError};
+
+use crate::{LEIError, LEI};
+
+/// The query parameter or form field `LeiQuery` and `LeiForm` read.
+const PARAMETER: &str = "lei";
+
+/// How the extractors of this module parse `LEI`s. Register it with `App::app_data()`; without
+/// it, parsing is strict.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LeiConfig {
+    loose: bool,
+}
+
+impl LeiConfig {
+    /// Parse with the strict `parse()`.
+    pub fn new() -> LeiConfig {
+        LeiConfig::default()
+    }
+
+    /// Parse with `parse_loose()` instead, allowing surrounding whitespace and lowercase letters.
+    pub fn loose(mut self) -> LeiConfig {
+        self.loose = true;
+        self
+    }
+
+    /// The configuration registered for the app handling `req`, or the default.
+    pub fn of(req: &HttpRequest) -> LeiConfig {
+        req.app_data::<LeiConfig>().copied().unwrap_or_default()
+    }
+
+    /// Parse `value` in the configured mode, rejecting an invalid LEI with
+    /// `LeiRejection::Invalid`, for handlers that extract their parameters with actix-web's own
+    /// extractors.
+    pub fn parse(&self, value: &str) -> Result<LEI, LeiRejection> {
+        let result = if self.loose {
+            crate::parse_loose(value)
+        } else {
+            crate::parse(value)
+        };
+        result.map_err(|error| LeiRejection::Invalid {
+            value: value.to_owned(),
+            error,
+        })
+    }
+
+    /// Parse the `lei` entry of `params`, rejecting a missing one with
+    /// `LeiRejection::MissingParameter`.
+    fn parse_param(&self, params: &HashMap<String, String>) -> Result<LEI, LeiRejection> {
+        let value = params
+            .get(PARAMETER)
+            .ok_or(LeiRejection::MissingParameter)?;
+        self.parse(value)
+    }
+}
+
+/// Extracts the LEI in the route's single path parameter, rejecting an invalid LEI with
+/// `LeiRejection::Invalid`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LeiPath(pub LEI);
+
+/// Extracts the LEI in the `lei` query parameter, rejecting an invalid LEI with
+/// `LeiRejection::Invalid`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LeiQuery(pub LEI);
+
+/// Extracts the LEI in the `lei` field of a URL-encoded form body, rejecting an invalid LEI with
+/// `LeiRejection::Invalid`. The body is read by `Form`, so `FormConfig` applies.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LeiForm(pub LEI);
+
+impl Deref for LeiPath {
+    type Target = LEI;
+
+    fn deref(&self) -> &LEI {
+        &self.0
+    }
+}
+
+impl Deref for LeiQuery {
+    type Target = LEI;
+
+    fn deref(&self) -> &LEI {
+        &self.0
+    }
+}
+
+impl Deref for LeiForm {
+    type Target = LEI;
+
+    fn deref(&self) -> &LEI {
+        &self.0
+    }
+}
+
+/// Why `LeiPath`, `LeiQuery` or `LeiForm` rejected a request.
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum LeiRejection {
+    /// A value is not a valid LEI. Responds with `422 Unprocessable Entity`.
+    Invalid {
+        /// The value as found in the request.
+        value: String,
+        /// What is wrong with it.
+        error: LEIError,
+    },
+    /// actix-web's own `Path`, `Query` or `Form` extractor rejected the request.
+    Extract(::actix_web::Error),
+    /// The `lei` query parameter or form field is missing. Responds with `400 Bad Request`.
+    MissingParameter,
+}
+
+impl Display for LeiRejection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            LeiRejection::Invalid { value, error } => write!(f, "invalid LEI {value:?}: {error}"),
+            LeiRejection::Extract(err) => write!(f, "{err}"),
+            LeiRejection::MissingParameter => write!(f, "missing parameter `{PARAMETER}`"),
+        }
+    }
+}
+
+impl Error for LeiRejection {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LeiRejection::Invalid { error, .. } => Some(error),
+            LeiRejection::Extract(_) | LeiRejection::MissingParameter => None,
+        }
+    }
+}
+
+impl ResponseError for LeiRejection {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            LeiRejection::Invalid { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+            LeiRejection::Extract(err) => err.as_response_error().status_code(),
+            LeiRejection::MissingParameter => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        let status = self.status_code();
+        let mut body = String::from("{\"type\":\"about:blank\",\"title\":");
+        crate::json::push_string(&mut body, status.canonical_reason().unwrap_or(""));
+        body.push_str(&format!(",\"status\":{},\"detail\":", status.as_u16()));
+        match self {
+            LeiRejection::Invalid { value, error } => {
+                crate::json::push_string(&mut body, &error.to_string());
+                body.push_str(",\"code\":");
+                crate::json::push_string(&mut body, error.code());
+                body.push_str(",\"value\":");
+                crate::json::push_string(&mut body, value);
+            }
+            other => crate::json::push_string(&mut body, &other.to_string()),
+        }
+        body.push('}');
+
+        HttpResponse::build(status)
+            .content_type("application/problem+json")
+            .body(body)
+    }
+}
+
+/// The future of an extractor of this module.
+type Extract<T> = Pin<Box<dyn Future<Output = Result<T, LeiRejection>>>>;
+
+impl FromRequest for LeiPath {
+    type Error = LeiRejection;
+    type Future = Extract<LeiPath>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let config = LeiConfig::of(req);
+        let path = Path::<String>::from_request(req, payload);
+        Box::pin(async move {
+            let value = path.await.map_err(LeiRejection::Extract)?;
+            config.parse(&value).map(LeiPath)
+        })
+    }
+}
+
+impl FromRequest for LeiQuery {
+    type Error = LeiRejection;
+    type Future = Extract<LeiQuery>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let config = LeiConfig::of(req);
+        let query = Query::<HashMap<String, String>>::from_request(req, payload);
+        Box::pin(async move {
+            let params = query.await.map_err(LeiRejection::Extract)?;
+            config.parse_param(&params).map(LeiQuery)
+        })
+    }
+}
+
+impl FromRequest for LeiForm {
+    type Error = LeiRejection;
+    type Future = Extract<LeiForm>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let config = LeiConfig::of(req);
+        let form = Form::<HashMap<String, String>>::from_request(req, payload);
+        Box::pin(async move {
+            let params = form.await.map_err(LeiRejection::Extract)?;
+            config.parse_param(&params).map(LeiForm)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::actix_web::test::{call_service, init_service, read_body, TestRequest};
+    use ::actix_web::{web, App};
+
+    async fn call(config: LeiConfig, request: TestRequest) -> (StatusCode, String) {
+        let app = init_service(
+            App::new()
+                .app_data(config)
+                .route(
+                    "/entities/{lei}",
+                    web::get().to(|LeiPath(lei): LeiPath| async move { lei.to_string() }),
+                )
+                .route(
+                    "/children",
+                    web::get().to(|LeiQuery(lei): LeiQuery| async move { lei.to_string() }),
+                )
+                .route(
+                    "/children",
+                    web::post().to(|LeiForm(lei): LeiForm| async move { lei.to_string() }),
+                ),
+        )
+        .await;
+        let response = call_service(&app, request.to_request()).await;
+        let status = response.status();
+        let body = read_body(response).await;
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[test]
+    fn strict_by_default() {
+        ::actix_web::rt::System::new().block_on(async {
+            let uri = "/entities/549300IYKILIU506KA05";
+            assert_eq!(
+                call(LeiConfig::new(), TestRequest::get().uri(uri)).await,
+                (StatusCode::OK, "549300IYKILIU506KA05".to_owned())
+            );
+
+            let uri = "/entities/549300IYKILIU506KA06";
+            assert_eq!(
+                call(LeiConfig::new(), TestRequest::get().uri(uri)).await,
+                (
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    r#"{"type":"about:blank","title":"Unprocessable Entity","status":422,"detail":"incorrect check digits \"06\" when expecting \"05\"","code":"LEI-E009","value":"549300IYKILIU506KA06"}"#
+                        .to_owned()
+                )
+            );
+
+            let uri = "/children?lei=%20549300iykiliu506ka05";
+            let (status, body) = call(LeiConfig::new(), TestRequest::get().uri(uri)).await;
+            assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+            assert!(body.contains(r#""value":" 549300iykiliu506ka05""#), "{body}");
+
+            let uri = "/entities/549300IYKILIU506KA0%35";
+            assert_eq!(
+                call(LeiConfig::new(), TestRequest::get().uri(uri)).await,
+                (StatusCode::OK, "549300IYKILIU506KA05".to_owned())
+            );
+
+            let (status, body) = call(LeiConfig::new(), TestRequest::get().uri("/children")).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert!(body.contains(r#""detail":"missing parameter `lei`""#), "{body}");
+        });
+    }
+
+    #[test]
+    fn loose_config() {
+        ::actix_web::rt::System::new().block_on(async {
+            let uri = "/children?lei=%20549300iykiliu506ka05&page=2";
+            assert_eq!(
+                call(LeiConfig::new().loose(), TestRequest::get().uri(uri)).await,
+                (StatusCode::OK, "549300IYKILIU506KA05".to_owned())
+            );
+
+            let request = TestRequest::post()
+                .uri("/children")
+                .set_form([("lei", "549300iykiliu506ka05")]);
+            assert_eq!(
+                call(LeiConfig::new().loose(), request).await,
+                (StatusCode::OK, "549300IYKILIU506KA05".to_owned())
+            );
+
+            let request = TestRequest::post()
+                .uri("/children")
+                .set_form([("lei", "bogus")]);
+            let (status, body) = call(LeiConfig::new().loose(), request).await;
+            assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+            assert!(body.contains(r#""code":"LEI-E001""#), "{body}");
+
+            let request = TestRequest::post()
+                .uri("/children")
+                .insert_header(("content-type", "text/plain"))
+                .set_payload("lei=549300IYKILIU506KA05");
+            let (status, body) = call(LeiConfig::new(), request).await;
+            assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+            assert!(body.contains(r#""status":415"#), "{body}");
+        });
+    }
+}
@@ -0,0 +1,372 @@
+#![warn(missing_docs)]
+//! # lei::arrow
+//!
+//! [Apache Arrow](https://crates.io/crates/arrow) support: LEI columns stored as
+//! `FixedSizeBinary(20)` arrays tagged with an extension type, and bulk validation of string and
+//! binary columns, so LEIs keep their type through Arrow pipelines.
+//!
+//! A `LeiArray` wraps a `FixedSizeBinaryArray` whose non-null values are all known to be valid
+//! LEIs. Build one with `LeiArrayBuilder`, or validate an existing column with
+//! `LeiArray::try_from_array()`, which accepts string, binary and fixed-size binary arrays.
+//!
+//! ```
+//! use arrow_array::{Array, StringArray};
+//! use lei::arrow::{LeiArray, LeiArrayError};
+//!
+//! let column = StringArray::from(vec![Some("YZ83GD8L7GG84979J516"), None]);
+//! let leis = LeiArray::try_from_array(&column).unwrap();
+//! assert_eq!(leis.get(0).unwrap().lou_id(), "YZ83");
+//! assert_eq!(leis.get(1), None);
+//!
+//! let column = StringArray::from(vec!["YZ83GD8L7GG84979J516", "549300IYKILIU506KA06"]);
+//! let valid = lei::arrow::validate(&column).unwrap();
+//! assert_eq!(valid.value(1), false);
+//! assert!(matches!(
+//!     LeiArray::try_from_array(&column),
+//!     Err(LeiArrayError::Invalid { index: 1, .. })
+//! ));
+//! ```
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use arrow_array::builder::FixedSizeBinaryBuilder;
+use arrow_array::cast::AsArray;
+use arrow_array::{Array, BooleanArray, FixedSizeBinaryArray};
+use arrow_schema::{DataType, Field};
+
+use crate::{LEIError, LEI};
+
+/// The name of the Arrow extension type for LEI columns, stored in field metadata under
+/// `EXTENSION_NAME_KEY`.
+pub const EXTENSION_NAME: &str = "lei.lei";
+
+/// The field metadata key Arrow uses for extension type names.
+pub const EXTENSION_NAME_KEY: &str = "ARROW:extension:name";
+
+/// The storage type of an LEI column.
+pub const DATA_TYPE: DataType = DataType::FixedSizeBinary(20);
+
+/// A field for an LEI column: `FixedSizeBinary(20)`, tagged with the `EXTENSION_NAME` extension
+/// type.
+pub fn field(name: impl Into<String>, nullable: bool) -> Field {
+    Field::new(name, DATA_TYPE, nullable).with_metadata(HashMap::from([(
+        EXTENSION_NAME_KEY.to_owned(),
+        EXTENSION_NAME.to_owned(),
+    )]))
+}
+
+/// Return true if `field` is tagged with the LEI extension type.
+pub fn is_lei_field(field: &Field) -> bool {
+    field.metadata().get(EXTENSION_NAME_KEY).map(String::as_str) == Some(EXTENSION_NAME)
+}
+
+/// All the ways reading an Arrow array as LEIs could fail.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq)]
+pub enum LeiArrayError {
+    /// The array is not a string, binary or fixed-size binary array.
+    UnsupportedType(DataType),
+    /// The value at `index` is not a valid LEI.
+    Invalid {
+        /// The index of the value within the array.
+        index: usize,
+        /// What is wrong with the value.
+        error: LEIError,
+    },
+}
+
+impl Display for LeiArrayError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            LeiArrayError::UnsupportedType(data_type) => {
+                write!(f, "cannot read LEIs from an array of type {data_type}")
+            }
+            LeiArrayError::Invalid { index, error } => {
+                write!(f, "invalid LEI at index {index}: {error}")
+            }
+        }
+    }
+}
+
+impl Error for LeiArrayError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LeiArrayError::UnsupportedType(_) => None,
+            LeiArrayError::Invalid { error, .. } => Some(error),
+        }
+    }
+}
+
+/// Iterate over the values of a string, binary or fixed-size binary array as bytes.
+fn values(
+    array: &dyn Array,
+) -> Result<Box<dyn Iterator<Item = Option<&[u8]>> + '_>, LeiArrayError> {
+    Ok(match array.data_type() {
+        DataType::Utf8 => Box::new(
+            array
+                .as_string::<i32>()
+                .iter()
+                .map(|v| v.map(str::as_bytes)),
+        ),
+        DataType::LargeUtf8 => Box::new(
+            array
+                .as_string::<i64>()
+                .iter()
+                .map(|v| v.map(str::as_bytes)),
+        ),
+        DataType::Utf8View => Box::new(array.as_string_view().iter().map(|v| v.map(str::as_bytes))),
+        DataType::Binary => Box::new(array.as_binary::<i32>().iter()),
+        DataType::LargeBinary => Box::new(array.as_binary::<i64>().iter()),
+        DataType::BinaryView => Box::new(array.as_binary_view().iter()),
+        DataType::FixedSizeBinary(_) => Box::new(array.as_fixed_size_binary().iter()),
+        other => return Err(LeiArrayError::UnsupportedType(other.clone())),
+    })
+}
+
+/// Check every value of a string, binary or fixed-size binary array with the same rules as
+/// `parse()`. The result has the same length and nulls as `array`.
+pub fn validate(array: &dyn Array) -> Result<BooleanArray, LeiArrayError> {
+    Ok(values(array)?
+        .map(|v| v.map(crate::validate_bytes))
+        .collect())
+}
+
+/// Find every invalid value of a string, binary or fixed-size binary array, with its index and
+/// what is wrong with it. Nulls are not reported.
+pub fn invalid_values(array: &dyn Array) -> Result<Vec<(usize, LEIError)>, LeiArrayError> {
+    Ok(values(array)?
+        .enumerate()
+        .filter_map(|(i, v)| {
+            let err = crate::parse_bytes_unrecorded(v?).err()?;
+            Some((i, err))
+        })
+        .collect())
+}
+
+/// A `FixedSizeBinaryArray` of valid LEIs (and nulls).
+#[derive(Clone, Debug, PartialEq)]
+pub struct LeiArray(FixedSizeBinaryArray);
+
+impl LeiArray {
+    /// Validate a `FixedSizeBinaryArray` as LEIs, failing at the first invalid value.
+    pub fn try_new(array: FixedSizeBinaryArray) -> Result<LeiArray, LeiArrayError> {
+        if array.value_length() != 20 {
+            return Err(LeiArrayError::UnsupportedType(array.data_type().clone()));
+        }
+        check(&array)?;
+        Ok(LeiArray(array))
+    }
+
+    /// Read a string, binary or fixed-size binary array as LEIs, failing at the first invalid
+    /// value. A `FixedSizeBinary(20)` array is validated in place; others are copied.
+    pub fn try_from_array(array: &dyn Array) -> Result<LeiArray, LeiArrayError> {
+        if array.data_type() == &DATA_TYPE {
+            return LeiArray::try_new(array.as_fixed_size_binary().clone());
+        }
+        check(array)?;
+        let mut builder = LeiArrayBuilder::with_capacity(array.len());
+        for v in values(array)? {
+            match v {
+                Some(b) => builder.append(LEI::from_validated_bytes(b)),
+                None => builder.append_null(),
+            }
+        }
+        Ok(builder.finish())
+    }
+
+    /// The number of values, including nulls.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Return true if there are no values.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Return the LEI at index `i`, or `None` if it is null. Panics if `i` is out of bounds.
+    pub fn get(&self, i: usize) -> Option<LEI> {
+        if self.0.is_null(i) {
+            None
+        } else {
+            Some(LEI::from_validated_bytes(self.0.value(i)))
+        }
+    }
+
+    /// Iterate over the values, with `None` for nulls.
+    pub fn iter(&self) -> impl Iterator<Item = Option<LEI>> + '_ {
+        self.0.iter().map(|v| v.map(LEI::from_validated_bytes))
+    }
+
+    /// Return the underlying `FixedSizeBinaryArray`.
+    pub fn as_fixed_size_binary(&self) -> &FixedSizeBinaryArray {
+        &self.0
+    }
+
+    /// Return the underlying `FixedSizeBinaryArray`, consuming the `LeiArray`.
+    pub fn into_inner(self) -> FixedSizeBinaryArray {
+        self.0
+    }
+}
+
+/// Fail with the first invalid value of `array`.
+fn check(array: &dyn Array) -> Result<(), LeiArrayError> {
+    for (index, v) in values(array)?.enumerate() {
+        if let Some(b) = v {
+            if let Err(error) = crate::parse_bytes_unrecorded(b) {
+                return Err(LeiArrayError::Invalid { index, error });
+            }
+        }
+    }
+    Ok(())
+}
+
+impl From<LeiArray> for FixedSizeBinaryArray {
+    fn from(array: LeiArray) -> Self {
+        array.0
+    }
+}
+
+impl FromIterator<LEI> for LeiArray {
+    fn from_iter<I: IntoIterator<Item = LEI>>(iter: I) -> Self {
+        iter.into_iter().map(Some).collect()
+    }
+}
+
+impl FromIterator<Option<LEI>> for LeiArray {
+    fn from_iter<I: IntoIterator<Item = Option<LEI>>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut builder = LeiArrayBuilder::with_capacity(iter.size_hint().0);
+        for lei in iter {
+            builder.append_option(lei);
+        }
+        builder.finish()
+    }
+}
+
+/// A builder for `LeiArray`.
+#[derive(Debug)]
+pub struct LeiArrayBuilder(FixedSizeBinaryBuilder);
+
+impl Default for LeiArrayBuilder {
+    fn default() -> Self {
+        LeiArrayBuilder::new()
+    }
+}
+
+impl LeiArrayBuilder {
+    /// Create an empty builder.
+    pub fn new() -> LeiArrayBuilder {
+        LeiArrayBuilder(FixedSizeBinaryBuilder::new(20))
+    }
+
+    /// Create an empty builder with room for `capacity` values.
+    pub fn with_capacity(capacity: usize) -> LeiArrayBuilder {
+        LeiArrayBuilder(FixedSizeBinaryBuilder::with_capacity(capacity, 20))
+    }
+
+    /// Append an LEI.
+    pub fn append(&mut self, lei: LEI) {
+        self.0
+            .append_value(lei.as_bytes())
+            .expect("an LEI is 20 bytes");
+    }
+
+    /// Append a null.
+    pub fn append_null(&mut self) {
+        self.0.append_null();
+    }
+
+    /// Append an LEI, or a null for `None`.
+    pub fn append_option(&mut self, lei: Option<LEI>) {
+        match lei {
+            Some(lei) => self.append(lei),
+            None => self.append_null(),
+        }
+    }
+
+    /// Build the `LeiArray`, leaving the builder empty.
+    pub fn finish(&mut self) -> LeiArray {
+        LeiArray(self.0.finish())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{BinaryArray, LargeStringArray, StringViewArray};
+
+    fn leis() -> Vec<LEI> {
+        ["YZ83GD8L7GG84979J516", "549300IYKILIU506KA05"]
+            .into_iter()
+            .map(|s| crate::parse(s).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn builder_and_reader() {
+        let mut builder = LeiArrayBuilder::new();
+        builder.append(leis()[0]);
+        builder.append_null();
+        builder.append_option(Some(leis()[1]));
+        let array = builder.finish();
+
+        assert_eq!(array.len(), 3);
+        assert_eq!(array.as_fixed_size_binary().data_type(), &DATA_TYPE);
+        assert_eq!(
+            array.iter().collect::<Vec<_>>(),
+            vec![Some(leis()[0]), None, Some(leis()[1])]
+        );
+
+        let collected: LeiArray = leis().into_iter().collect();
+        assert_eq!(collected.get(1), Some(leis()[1]));
+    }
+
+    #[test]
+    fn validates_existing_arrays() {
+        let fixed =
+            FixedSizeBinaryArray::from(vec![b"YZ83GD8L7GG84979J516", b"YZ83GD8L7GG84979J517"]);
+        assert_eq!(
+            LeiArray::try_new(fixed.clone()),
+            Err(LeiArrayError::Invalid {
+                index: 1,
+                error: crate::parse("YZ83GD8L7GG84979J517").unwrap_err(),
+            })
+        );
+        assert_eq!(
+            validate(&fixed).unwrap(),
+            BooleanArray::from(vec![true, false])
+        );
+
+        let large = LargeStringArray::from(vec![Some(" YZ83GD8L7GG84979J516"), None]);
+        let invalid = invalid_values(&large).unwrap();
+        assert_eq!(invalid.len(), 1);
+        assert_eq!(invalid[0].0, 0);
+
+        let view = StringViewArray::from(vec!["549300IYKILIU506KA05"]);
+        assert_eq!(
+            LeiArray::try_from_array(&view).unwrap().get(0),
+            Some(leis()[1])
+        );
+
+        let binary = BinaryArray::from(vec![&b"549300IYKILIU506KA05"[..]]);
+        assert!(LeiArray::try_from_array(&binary).is_ok());
+
+        let ints = arrow_array::Int32Array::from(vec![1]);
+        assert_eq!(
+            validate(&ints),
+            Err(LeiArrayError::UnsupportedType(DataType::Int32))
+        );
+    }
+
+    #[test]
+    fn fields() {
+        let f = field("lei", true);
+        assert_eq!(f.data_type(), &DATA_TYPE);
+        assert!(is_lei_field(&f));
+        assert!(!is_lei_field(&Field::new("lei", DataType::Utf8, true)));
+    }
+}
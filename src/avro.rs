@@ -0,0 +1,148 @@
+#![warn(missing_docs)]
+//! # lei::avro
+//!
+//! [Apache Avro](https://crates.io/crates/apache-avro) schemas and value conversions for `LEI`,
+//! so Avro-encoded pipelines can carry validated LEIs end to end.
+//!
+//! An LEI can be written either as an Avro `string` annotated with the logical type `lei`, which
+//! readers that don't know the logical type see as a plain string, or as a named `fixed` of 20
+//! bytes, which is a byte shorter per value. Conversions from Avro values accept both and
+//! validate with the strict `parse()`.
+//!
+//! ```
+//! use apache_avro::types::Value;
+//! use lei::LEI;
+//!
+//! let lei = lei::parse("YZ83GD8L7GG84979J516").unwrap();
+//! assert_eq!(Value::from(lei), Value::String("YZ83GD8L7GG84979J516".to_owned()));
+//! assert_eq!(LEI::try_from(lei::avro::to_fixed_value(&lei)).unwrap(), lei);
+//!
+//! let schema = lei::avro::string_schema();
+//! assert!(Value::from(lei).validate(&schema));
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use ::apache_avro::types::Value;
+use ::apache_avro::Schema;
+
+use crate::{LEIError, LEI};
+
+/// The Avro schema of an LEI written as a `string` with logical type `lei`.
+pub const STRING_SCHEMA: &str = r#"{"type": "string", "logicalType": "lei"}"#;
+
+/// The Avro schema of an LEI written as a `fixed` of 20 bytes, named `org.iso.lei.LEI`.
+pub const FIXED_SCHEMA: &str =
+    r#"{"type": "fixed", "name": "LEI", "namespace": "org.iso.lei", "size": 20}"#;
+
+/// The parsed `STRING_SCHEMA`.
+pub fn string_schema() -> Schema {
+    Schema::parse_str(STRING_SCHEMA).expect("STRING_SCHEMA is a valid Avro schema")
+}
+
+/// The parsed `FIXED_SCHEMA`.
+pub fn fixed_schema() -> Schema {
+    Schema::parse_str(FIXED_SCHEMA).expect("FIXED_SCHEMA is a valid Avro schema")
+}
+
+/// Convert an LEI to an Avro `fixed` value for use with `FIXED_SCHEMA`. For `STRING_SCHEMA`,
+/// use `Value::from()`.
+pub fn to_fixed_value(lei: &LEI) -> Value {
+    Value::Fixed(LEI::LENGTH, lei.as_bytes().to_vec())
+}
+
+/// Writes the LEI as an Avro `string`, for use with `STRING_SCHEMA`.
+impl From<LEI> for Value {
+    fn from(lei: LEI) -> Value {
+        Value::String(lei.to_string())
+    }
+}
+
+/// All the ways converting an Avro value to an `LEI` could fail.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq)]
+pub enum AvroError {
+    /// The value is not a `string`, `bytes` or `fixed`.
+    UnexpectedType(Value),
+    /// The value is not a valid LEI.
+    Invalid(LEIError),
+}
+
+impl Display for AvroError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            AvroError::UnexpectedType(value) => {
+                write!(
+                    f,
+                    "expected an Avro string, bytes or fixed but got {value:?}"
+                )
+            }
+            AvroError::Invalid(err) => write!(f, "invalid LEI: {err}"),
+        }
+    }
+}
+
+impl Error for AvroError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AvroError::UnexpectedType(_) => None,
+            AvroError::Invalid(err) => Some(err),
+        }
+    }
+}
+
+impl From<LEIError> for AvroError {
+    fn from(err: LEIError) -> Self {
+        AvroError::Invalid(err)
+    }
+}
+
+/// Reads an LEI from an Avro `string`, `bytes` or `fixed`, validating with `parse()`.
+impl TryFrom<Value> for LEI {
+    type Error = AvroError;
+
+    fn try_from(value: Value) -> Result<LEI, AvroError> {
+        match value {
+            Value::String(s) => Ok(crate::parse(&s)?),
+            Value::Bytes(b) | Value::Fixed(_, b) => Ok(crate::parse_bytes(&b)?),
+            other => Err(AvroError::UnexpectedType(other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schemas_parse_and_validate_values() {
+        let lei = crate::parse("549300IYKILIU506KA05").unwrap();
+        assert!(Value::from(lei).validate(&string_schema()));
+        assert!(to_fixed_value(&lei).validate(&fixed_schema()));
+    }
+
+    #[test]
+    fn conversions() {
+        let lei = crate::parse("549300IYKILIU506KA05").unwrap();
+        assert_eq!(LEI::try_from(Value::from(lei)), Ok(lei));
+        assert_eq!(LEI::try_from(to_fixed_value(&lei)), Ok(lei));
+        assert_eq!(
+            LEI::try_from(Value::Bytes(lei.as_bytes().to_vec())),
+            Ok(lei)
+        );
+
+        assert_eq!(
+            LEI::try_from(Value::String("549300IYKILIU506KA06".to_owned())),
+            Err(AvroError::Invalid(LEIError::IncorrectCheckDigits {
+                was: crate::CheckDigits(*b"06"),
+                expected: crate::CheckDigits(*b"05"),
+            }))
+        );
+        assert_eq!(
+            LEI::try_from(Value::Long(1)),
+            Err(AvroError::UnexpectedType(Value::Long(1)))
+        );
+    }
+}
@@ -0,0 +1,246 @@
+#![warn(missing_docs)]
+//! # lei::axum
+//!
+//! [axum](https://crates.io/crates/axum) extractors for LEIs in routes. `LeiPath` takes the
+//! route's single path parameter with axum's `Path<String>`, and `LeiQuery` takes the `lei`
+//! query parameter with axum's `Query`. Both parse the value with `parse()` and reject requests
+//! carrying an invalid LEI with `422 Unprocessable Entity` and a JSON body describing the error:
+//!
+//! ```json
+//! {"code":"LEI-E009","message":"incorrect check digits \"17\" when expecting \"16\"",
+//!  "value":"YZ83GD8L7GG84979J517"}
+//! ```
+//!
+//! Other failures, such as a missing parameter, are rejected the way axum's own extractors
+//! reject them. For other parameters, extract them with axum and call `lei::axum::parse()`, whose
+//! error is the same rejection.
+//!
+//! ```
+//! use axum::routing::get;
+//! use axum::Router;
+//! use lei::axum::LeiPath;
+//!
+//! async fn entity(LeiPath(lei): LeiPath) -> String {
+//!     format!("LOU {}", lei.lou_id())
+//! }
+//!
+//! let app: Router = Router::new().route("/entities/{lei}", get(entity));
+//! ```
+
+use std::collections::HashMap;
+use std::ops::Deref;
+
+use ::axum::extract::rejection::{PathRejection, QueryRejection};
+use ::axum::extract::{FromRequestParts, Path, Query};
+use ::axum::http::header::CONTENT_TYPE;
+use ::axum::http::request::Parts;
+use ::axum::http::{HeaderValue, StatusCode};
+use ::axum::response::{IntoResponse, Response};
+
+use crate::{LEIError, LEI};
+
+/// The query parameter `LeiQuery` reads.
+const QUERY_PARAMETER: &str = "lei";
+
+/// Extracts the LEI in the route's single path parameter, rejecting an invalid LEI with
+/// `LeiRejection::Invalid`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LeiPath(pub LEI);
+
+/// Extracts the LEI in the `lei` query parameter, rejecting an invalid LEI with
+/// `LeiRejection::Invalid`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LeiQuery(pub LEI);
+
+impl Deref for LeiPath {
+    type Target = LEI;
+
+    fn deref(&self) -> &LEI {
+        &self.0
+    }
+}
+
+impl Deref for LeiQuery {
+    type Target = LEI;
+
+    fn deref(&self) -> &LEI {
+        &self.0
+    }
+}
+
+/// Why `LeiPath` or `LeiQuery` rejected a request.
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum LeiRejection {
+    /// A value is not a valid LEI. Responds with `422 Unprocessable Entity`.
+    Invalid {
+        /// The value as found in the request.
+        value: String,
+        /// What is wrong with it.
+        error: LEIError,
+    },
+    /// The path parameter could not be extracted, as for `Path<String>`.
+    Path(PathRejection),
+    /// The query string could not be extracted, as for `Query`.
+    Query(QueryRejection),
+    /// The `lei` query parameter is missing. Responds with `400 Bad Request`.
+    MissingQueryParameter,
+}
+
+impl IntoResponse for LeiRejection {
+    fn into_response(self) -> Response {
+        let (value, error) = match self {
+            LeiRejection::Invalid { value, error } => (value, error),
+            LeiRejection::Path(rejection) => return rejection.into_response(),
+            LeiRejection::Query(rejection) => return rejection.into_response(),
+            LeiRejection::MissingQueryParameter => {
+                let body = format!("Missing query parameter `{QUERY_PARAMETER}`");
+                return (StatusCode::BAD_REQUEST, body).into_response();
+            }
+        };
+        let mut body = String::from("{\"code\":");
+        crate::json::push_string(&mut body, error.code());
+        body.push_str(",\"message\":");
+        crate::json::push_string(&mut body, &error.to_string());
+        body.push_str(",\"value\":");
+        crate::json::push_string(&mut body, &value);
+        body.push('}');
+
+        let mut response = (StatusCode::UNPROCESSABLE_ENTITY, body).into_response();
+        response
+            .headers_mut()
+            .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        response
+    }
+}
+
+/// Parse `value` with `parse()`, rejecting an invalid LEI with `LeiRejection::Invalid`, for
+/// handlers that extract their parameters with axum's own extractors.
+pub fn parse(value: &str) -> Result<LEI, LeiRejection> {
+    crate::parse(value).map_err(|error| LeiRejection::Invalid {
+        value: value.to_owned(),
+        error,
+    })
+}
+
+impl<S> FromRequestParts<S> for LeiPath
+where
+    S: Send + Sync,
+{
+    type Rejection = LeiRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, LeiRejection> {
+        let Path(value) = Path::<String>::from_request_parts(parts, state)
+            .await
+            .map_err(LeiRejection::Path)?;
+        parse(&value).map(LeiPath)
+    }
+}
+
+impl<S> FromRequestParts<S> for LeiQuery
+where
+    S: Send + Sync,
+{
+    type Rejection = LeiRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, LeiRejection> {
+        let Query(params) = Query::<HashMap<String, String>>::from_request_parts(parts, state)
+            .await
+            .map_err(LeiRejection::Query)?;
+        let value = params
+            .get(QUERY_PARAMETER)
+            .ok_or(LeiRejection::MissingQueryParameter)?;
+        parse(value).map(LeiQuery)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::axum::body::{to_bytes, Body};
+    use ::axum::http::Request;
+    use ::axum::routing::get;
+    use ::axum::Router;
+    use ::tower::ServiceExt;
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/entities/{lei}",
+                get(|LeiPath(lei): LeiPath| async move { lei.to_string() }),
+            )
+            .route(
+                "/children",
+                get(|LeiQuery(lei): LeiQuery| async move { lei.to_string() }),
+            )
+            .route(
+                "/lous/{lou}/children",
+                get(
+                    |Path(lou): Path<String>, Query(params): Query<HashMap<String, String>>| async move {
+                        let parent = parse(params.get("parent").map_or("", String::as_str))?;
+                        Ok::<_, LeiRejection>(format!("{lou} {parent}"))
+                    },
+                ),
+            )
+    }
+
+    async fn get_uri(uri: &str) -> (StatusCode, String) {
+        let request = Request::get(uri).body(Body::empty()).unwrap();
+        let response = app().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn path() {
+        assert_eq!(
+            get_uri("/entities/549300IYKILIU506KA05").await,
+            (StatusCode::OK, "549300IYKILIU506KA05".to_owned())
+        );
+        assert_eq!(
+            get_uri("/entities/549300IYKILIU506KA0%35").await,
+            (StatusCode::OK, "549300IYKILIU506KA05".to_owned())
+        );
+        assert_eq!(
+            get_uri("/entities/549300IYKILIU506KA06").await,
+            (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                r#"{"code":"LEI-E009","message":"incorrect check digits \"06\" when expecting \"05\"","value":"549300IYKILIU506KA06"}"#
+                    .to_owned()
+            )
+        );
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn query() {
+        assert_eq!(
+            get_uri("/children?lei=549300IYKILIU506KA05&page=2").await,
+            (StatusCode::OK, "549300IYKILIU506KA05".to_owned())
+        );
+
+        let (status, body) = get_uri("/children?lei=%20549300IYKILIU506KA05").await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(body.starts_with(r#"{"code":"LEI-E001","#), "{body}");
+
+        assert_eq!(
+            get_uri("/children?page=2").await,
+            (
+                StatusCode::BAD_REQUEST,
+                "Missing query parameter `lei`".to_owned()
+            )
+        );
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn parse_in_handlers() {
+        assert_eq!(
+            get_uri("/lous/5493/children?parent=549300IYKILIU506KA05").await,
+            (StatusCode::OK, "5493 549300IYKILIU506KA05".to_owned())
+        );
+
+        let (status, body) = get_uri("/lous/5493/children?parent=bogus").await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(body.ends_with(r#""value":"bogus"}"#), "{body}");
+    }
+}
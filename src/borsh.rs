@@ -0,0 +1,65 @@
+#![warn(missing_docs)]
+//! # lei::borsh
+//!
+//! [borsh](https://crates.io/crates/borsh) `BorshSerialize` and `BorshDeserialize`
+//! implementations for `LEI`.
+//!
+//! An LEI is written as its 20 ASCII bytes, with no length prefix. Deserialization reads exactly
+//! 20 bytes and validates them with the strict `parse()`, failing with an
+//! `io::ErrorKind::InvalidData` error that wraps the `LEIError`.
+//!
+//! ```
+//! let lei = lei::parse("YZ83GD8L7GG84979J516").unwrap();
+//! let bytes = borsh::to_vec(&lei).unwrap();
+//! assert_eq!(bytes, b"YZ83GD8L7GG84979J516");
+//! assert_eq!(borsh::from_slice::<lei::LEI>(&bytes).unwrap(), lei);
+//! ```
+
+use std::io;
+
+use ::borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::LEI;
+
+impl BorshSerialize for LEI {
+    fn serialize<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(self.as_bytes())
+    }
+}
+
+impl BorshDeserialize for LEI {
+    fn deserialize_reader<R: io::Read>(reader: &mut R) -> io::Result<LEI> {
+        let mut bb = [0u8; 20];
+        reader.read_exact(&mut bb)?;
+        crate::parse_bytes(&bb).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LEIError;
+
+    #[test]
+    fn round_trip_in_a_struct() {
+        let leis = vec![
+            crate::parse("YZ83GD8L7GG84979J516").unwrap(),
+            crate::parse("549300IYKILIU506KA05").unwrap(),
+        ];
+        let bytes = ::borsh::to_vec(&leis).unwrap();
+        assert_eq!(bytes.len(), 4 + 40);
+        assert_eq!(::borsh::from_slice::<Vec<LEI>>(&bytes).unwrap(), leis);
+    }
+
+    #[test]
+    fn rejects_invalid() {
+        let err = ::borsh::from_slice::<LEI>(b"YZ83GD8L7GG84979J517").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(matches!(
+            err.get_ref().unwrap().downcast_ref::<LEIError>(),
+            Some(LEIError::IncorrectCheckDigits { .. })
+        ));
+
+        assert!(::borsh::from_slice::<LEI>(b"YZ83GD8L7GG84979J5").is_err());
+    }
+}
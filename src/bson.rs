@@ -0,0 +1,127 @@
+#![warn(missing_docs)]
+//! # lei::bson
+//!
+//! [BSON](https://crates.io/crates/bson) conversions for `LEI`, for use with the `mongodb`
+//! driver. An LEI is stored as a BSON string. This feature enables `serde`, so structs with
+//! `LEI` fields can be used as MongoDB documents directly, validating on the way in.
+//!
+//! ```
+//! use bson::{doc, Bson};
+//! use lei::LEI;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Entity {
+//!     name: String,
+//!     lei: LEI,
+//! }
+//!
+//! let lei = lei::parse("YZ83GD8L7GG84979J516").unwrap();
+//! assert_eq!(Bson::from(lei), Bson::String("YZ83GD8L7GG84979J516".to_owned()));
+//!
+//! let entity = Entity { name: "Acme".to_owned(), lei };
+//! let document = bson::to_document(&entity).unwrap();
+//! assert_eq!(document, doc! { "name": "Acme", "lei": "YZ83GD8L7GG84979J516" });
+//! assert_eq!(bson::from_document::<Entity>(document).unwrap(), entity);
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use ::bson::Bson;
+
+use crate::{LEIError, LEI};
+
+/// Writes the LEI as a BSON string.
+impl From<LEI> for Bson {
+    fn from(lei: LEI) -> Bson {
+        Bson::String(lei.to_string())
+    }
+}
+
+/// All the ways converting a BSON value to an `LEI` could fail.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq)]
+pub enum BsonError {
+    /// The value is not a string.
+    UnexpectedType(Bson),
+    /// The value is not a valid LEI.
+    Invalid(LEIError),
+}
+
+impl Display for BsonError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            BsonError::UnexpectedType(value) => {
+                write!(f, "expected a BSON string but got {value}")
+            }
+            BsonError::Invalid(err) => write!(f, "invalid LEI: {err}"),
+        }
+    }
+}
+
+impl Error for BsonError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BsonError::UnexpectedType(_) => None,
+            BsonError::Invalid(err) => Some(err),
+        }
+    }
+}
+
+impl From<LEIError> for BsonError {
+    fn from(err: LEIError) -> Self {
+        BsonError::Invalid(err)
+    }
+}
+
+/// Reads an LEI from a BSON string, validating with `parse()`.
+impl TryFrom<Bson> for LEI {
+    type Error = BsonError;
+
+    fn try_from(value: Bson) -> Result<LEI, BsonError> {
+        match value {
+            Bson::String(s) => Ok(crate::parse(&s)?),
+            other => Err(BsonError::UnexpectedType(other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::bson::doc;
+    use ::serde::Deserialize;
+
+    #[test]
+    fn conversions() {
+        let lei = crate::parse("549300IYKILIU506KA05").unwrap();
+        assert_eq!(LEI::try_from(Bson::from(lei)), Ok(lei));
+        assert_eq!(
+            LEI::try_from(Bson::String("549300IYKILIU506KA06".to_owned())),
+            Err(BsonError::Invalid(LEIError::IncorrectCheckDigits {
+                was: crate::CheckDigits(*b"06"),
+                expected: crate::CheckDigits(*b"05"),
+            }))
+        );
+        assert_eq!(
+            LEI::try_from(Bson::Int32(1)),
+            Err(BsonError::UnexpectedType(Bson::Int32(1)))
+        );
+    }
+
+    #[test]
+    fn documents_with_invalid_leis_fail_to_deserialize() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Entity {
+            lei: LEI,
+        }
+
+        let err = ::bson::from_document::<Entity>(doc! { "lei": "549300IYKILIU506KA06" })
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("549300IYKILIU506KA06"), "{err}");
+    }
+}
@@ -0,0 +1,134 @@
+#![warn(missing_docs)]
+//! # lei::builder
+//!
+//! A step-by-step way to construct an LEI from its parts, for tooling that mints candidate LEIs.
+//!
+//! Each part is validated as it is supplied, either from a string or as an already-validated
+//! `LouId` or `EntityId`, and the _Check Digits_ are computed by `build()`.
+//!
+//! ```
+//! use lei::builder::LEIBuilder;
+//!
+//! let lei = LEIBuilder::new()
+//!     .lou_id("5493")?
+//!     .entity_id("00IYKILIU506KA")?
+//!     .build()?;
+//! assert_eq!(lei.to_string(), "549300IYKILIU506KA05");
+//! # Ok::<(), lei::LEIError>(())
+//! ```
+
+use crate::{EntityId, LEIError, LouId, LEI};
+
+/// A builder for an `LEI`. See the module documentation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LEIBuilder {
+    lou_id: Option<LouId>,
+    entity_id: Option<EntityId>,
+    loose: bool,
+}
+
+impl LEIBuilder {
+    /// Create a builder with no parts, accepting only strictly formatted strings.
+    pub fn new() -> LEIBuilder {
+        LEIBuilder::default()
+    }
+
+    /// Accept string parts with leading or trailing whitespace and/or lowercase letters, as
+    /// `parse_loose()` does. This applies to parts supplied after it is set.
+    pub fn loose(mut self, loose: bool) -> LEIBuilder {
+        self.loose = loose;
+        self
+    }
+
+    /// Set the _LOU ID_ from a string, validating its format.
+    pub fn lou_id(mut self, lou_id: &str) -> Result<LEIBuilder, LEIError> {
+        self.lou_id = Some(if self.loose {
+            LouId::parse(&lou_id.trim().to_ascii_uppercase())?
+        } else {
+            LouId::parse(lou_id)?
+        });
+        Ok(self)
+    }
+
+    /// Set an already-validated _LOU ID_.
+    pub fn lou_id_typed(mut self, lou_id: LouId) -> LEIBuilder {
+        self.lou_id = Some(lou_id);
+        self
+    }
+
+    /// Set the _Entity ID_ from a string, validating its format.
+    pub fn entity_id(mut self, entity_id: &str) -> Result<LEIBuilder, LEIError> {
+        self.entity_id = Some(if self.loose {
+            EntityId::parse(&entity_id.trim().to_ascii_uppercase())?
+        } else {
+            EntityId::parse(entity_id)?
+        });
+        Ok(self)
+    }
+
+    /// Set an already-validated _Entity ID_.
+    pub fn entity_id_typed(mut self, entity_id: EntityId) -> LEIBuilder {
+        self.entity_id = Some(entity_id);
+        self
+    }
+
+    /// Build the LEI, computing its _Check Digits_. A part that was never set is reported as
+    /// having length zero: `InvalidLouIdLength { was: 0 }` or `InvalidEntityIdLength { was: 0 }`.
+    pub fn build(&self) -> Result<LEI, LEIError> {
+        let lou_id = self.lou_id.ok_or(LEIError::InvalidLouIdLength { was: 0 })?;
+        let entity_id = self
+            .entity_id
+            .ok_or(LEIError::InvalidEntityIdLength { was: 0 })?;
+        Ok(LEI::from_typed_parts(lou_id, entity_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn string_and_typed_parts_agree() {
+        let from_strings = LEIBuilder::new()
+            .lou_id("YZ83")
+            .unwrap()
+            .entity_id("GD8L7GG84979J5")
+            .unwrap()
+            .build();
+        let from_typed = LEIBuilder::new()
+            .lou_id_typed("YZ83".parse().unwrap())
+            .entity_id_typed("GD8L7GG84979J5".parse().unwrap())
+            .build();
+        assert_eq!(from_strings, from_typed);
+        assert_eq!(from_strings, crate::parse("YZ83GD8L7GG84979J516"));
+    }
+
+    #[test]
+    fn strictness() {
+        assert_eq!(
+            LEIBuilder::new().lou_id("yz83"),
+            Err(LEIError::InvalidLouId { was: *b"yz83" })
+        );
+        let lei = LEIBuilder::new()
+            .loose(true)
+            .lou_id(" yz83")
+            .unwrap()
+            .entity_id("gd8l7gg84979j5 ")
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(lei.to_string(), "YZ83GD8L7GG84979J516");
+    }
+
+    #[test]
+    fn missing_parts() {
+        assert_eq!(
+            LEIBuilder::new().build(),
+            Err(LEIError::InvalidLouIdLength { was: 0 })
+        );
+        assert_eq!(
+            LEIBuilder::new().lou_id("5493").unwrap().build(),
+            Err(LEIError::InvalidEntityIdLength { was: 0 })
+        );
+    }
+}
@@ -0,0 +1,322 @@
+#![warn(missing_docs)]
+//! # lei::capi
+//!
+//! A stable C ABI, so C and C++ systems can link this implementation instead of porting it. The
+//! functions take input as a pointer and a length (it need not be NUL-terminated), write results
+//! into caller-provided fixed-size buffers, and return an `int` status: `LEI_OK`, one of the
+//! `LEI_E_*` codes (whose numbers match the `LEIError` codes, so `LEI_E_INCORRECT_CHECK_DIGITS`
+//! is 9 for `LEI-E009`), or `LEI_ERR_NULL_POINTER`. Nothing is allocated, so there is nothing to
+//! free.
+//!
+//! Build the crate as a library for C with, for example,
+//! `cargo rustc --release --features capi --crate-type staticlib`, and generate a header with
+//! `cbindgen --lang c --output lei.h`:
+//!
+//! ```c
+//! char lei[LEI_BUFFER_LEN];
+//! int status = lei_parse(input, strlen(input), lei);
+//! if (status > LEI_OK) {
+//!     fprintf(stderr, "invalid LEI: %s (%s)\n", lei_error_name(status), lei_error_code(status));
+//! }
+//! ```
+//!
+//! The same functions are callable from Rust:
+//!
+//! ```
+//! use lei::capi::*;
+//!
+//! let input = "YZ83GD8L7GG84979J516";
+//! let mut out = [0; LEI_BUFFER_LEN];
+//! let status = unsafe { lei_parse(input.as_ptr().cast(), input.len(), out.as_mut_ptr()) };
+//! assert_eq!(status, LEI_OK);
+//! ```
+
+use std::ffi::{c_char, c_int};
+use std::ptr;
+use std::slice;
+
+use crate::{LEIError, LEIErrorKind, LEI};
+
+/// The size of an LEI buffer: 20 characters and a terminating NUL.
+pub const LEI_BUFFER_LEN: usize = 21;
+
+/// The size of a _Check Digits_ buffer: 2 characters and a terminating NUL.
+pub const LEI_CHECK_DIGITS_BUFFER_LEN: usize = 3;
+
+/// Success.
+pub const LEI_OK: c_int = 0;
+/// A required pointer argument was `NULL`.
+pub const LEI_ERR_NULL_POINTER: c_int = -1;
+/// `LEI-E001`: the input is not 20 characters long.
+pub const LEI_E_INVALID_LENGTH: c_int = 1;
+/// `LEI-E002`: the _Payload_ is not 18 characters long.
+pub const LEI_E_INVALID_PAYLOAD_LENGTH: c_int = 2;
+/// `LEI-E003`: the _LOU ID_ is not 4 characters long.
+pub const LEI_E_INVALID_LOU_ID_LENGTH: c_int = 3;
+/// `LEI-E004`: the _Entity ID_ is not 14 characters long.
+pub const LEI_E_INVALID_ENTITY_ID_LENGTH: c_int = 4;
+/// `LEI-E005`: the _Check Digits_ are not 2 characters long.
+pub const LEI_E_INVALID_CHECK_DIGITS_LENGTH: c_int = 5;
+/// `LEI-E006`: the _LOU ID_ is not 4 uppercase ASCII alphanumeric characters.
+pub const LEI_E_INVALID_LOU_ID: c_int = 6;
+/// `LEI-E007`: the _Entity ID_ is not 14 uppercase ASCII alphanumeric characters.
+pub const LEI_E_INVALID_ENTITY_ID: c_int = 7;
+/// `LEI-E008`: the _Check Digits_ are not two ASCII decimal digits.
+pub const LEI_E_INVALID_CHECK_DIGITS: c_int = 8;
+/// `LEI-E009`: the _Check Digits_ are well-formed but incorrect.
+pub const LEI_E_INCORRECT_CHECK_DIGITS: c_int = 9;
+/// `LEI-E010`: the _Check Digits_ are one of the reserved values 00, 01 or 99.
+pub const LEI_E_RESERVED_CHECK_DIGITS: c_int = 10;
+
+/// The status for an error.
+fn status(err: &LEIError) -> c_int {
+    match err.kind() {
+        LEIErrorKind::InvalidLength => LEI_E_INVALID_LENGTH,
+        LEIErrorKind::InvalidPayloadLength => LEI_E_INVALID_PAYLOAD_LENGTH,
+        LEIErrorKind::InvalidLouIdLength => LEI_E_INVALID_LOU_ID_LENGTH,
+        LEIErrorKind::InvalidEntityIdLength => LEI_E_INVALID_ENTITY_ID_LENGTH,
+        LEIErrorKind::InvalidCheckDigitsLength => LEI_E_INVALID_CHECK_DIGITS_LENGTH,
+        LEIErrorKind::InvalidLouId => LEI_E_INVALID_LOU_ID,
+        LEIErrorKind::InvalidEntityId => LEI_E_INVALID_ENTITY_ID,
+        LEIErrorKind::InvalidCheckDigits => LEI_E_INVALID_CHECK_DIGITS,
+        LEIErrorKind::IncorrectCheckDigits => LEI_E_INCORRECT_CHECK_DIGITS,
+        LEIErrorKind::ReservedCheckDigits => LEI_E_RESERVED_CHECK_DIGITS,
+    }
+}
+
+/// The input bytes, or `None` if `input` is `NULL` and `len` is not 0.
+///
+/// # Safety
+///
+/// Unless `NULL`, `input` must point to `len` readable bytes.
+unsafe fn input<'a>(input: *const c_char, len: usize) -> Option<&'a [u8]> {
+    if input.is_null() {
+        return (len == 0).then_some(&[]);
+    }
+    Some(unsafe { slice::from_raw_parts(input.cast(), len) }) // This is safe because the caller guarantees input points to len bytes
+}
+
+/// Write `bytes` and a terminating NUL to `out`, unless it is `NULL`.
+///
+/// # Safety
+///
+/// Unless `NULL`, `out` must point to `bytes.len() + 1` writable bytes.
+unsafe fn write(bytes: &[u8], out: *mut c_char) {
+    if out.is_null() {
+        return;
+    }
+    // This is safe because the caller guarantees out has room for the bytes and the NUL
+    unsafe {
+        ptr::copy_nonoverlapping(bytes.as_ptr(), out.cast(), bytes.len());
+        *out.add(bytes.len()) = 0;
+    }
+}
+
+unsafe fn parse_into(
+    value: *const c_char,
+    len: usize,
+    out: *mut c_char,
+    parse: fn(&[u8]) -> Result<LEI, LEIError>,
+) -> c_int {
+    let Some(value) = (unsafe { input(value, len) }) else {
+        return LEI_ERR_NULL_POINTER;
+    };
+    match parse(value) {
+        Ok(lei) => {
+            unsafe { write(lei.as_bytes(), out) };
+            LEI_OK
+        }
+        Err(err) => status(&err),
+    }
+}
+
+/// Parse `len` bytes at `value` strictly, like `lei::parse()`, and on success write the LEI as a
+/// NUL-terminated string to `out`, unless it is `NULL`. Returns `LEI_OK` or an error status.
+///
+/// # Safety
+///
+/// `value` must point to `len` readable bytes (or be `NULL` with `len` 0), and `out` must be
+/// `NULL` or point to `LEI_BUFFER_LEN` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn lei_parse(value: *const c_char, len: usize, out: *mut c_char) -> c_int {
+    unsafe { parse_into(value, len, out, crate::parse_bytes) }
+}
+
+/// Parse `len` bytes at `value` like `lei::parse_loose()`, allowing surrounding whitespace and
+/// lowercase letters, and on success write the normalized LEI as a NUL-terminated string to
+/// `out`, unless it is `NULL`. Returns `LEI_OK` or an error status.
+///
+/// # Safety
+///
+/// As for `lei_parse()`.
+#[no_mangle]
+pub unsafe extern "C" fn lei_parse_loose(
+    value: *const c_char,
+    len: usize,
+    out: *mut c_char,
+) -> c_int {
+    unsafe {
+        parse_into(value, len, out, |value| match std::str::from_utf8(value) {
+            Ok(value) => crate::parse_loose(value),
+            Err(_) => crate::parse_bytes(value), // Not ASCII, so this reports the right error
+        })
+    }
+}
+
+/// Validate `len` bytes at `value` strictly. Returns `LEI_OK` or an error status.
+///
+/// # Safety
+///
+/// `value` must point to `len` readable bytes (or be `NULL` with `len` 0).
+#[no_mangle]
+pub unsafe extern "C" fn lei_validate(value: *const c_char, len: usize) -> c_int {
+    unsafe { parse_into(value, len, ptr::null_mut(), crate::parse_bytes) }
+}
+
+/// Compute the _Check Digits_ for the 18-character _Payload_ (_LOU ID_ and _Entity ID_) of
+/// `len` bytes at `payload`, and write them as a NUL-terminated string to `out`. Returns
+/// `LEI_OK` or an error status.
+///
+/// # Safety
+///
+/// `payload` must point to `len` readable bytes (or be `NULL` with `len` 0), and `out` must
+/// point to `LEI_CHECK_DIGITS_BUFFER_LEN` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn lei_check_digits(
+    payload: *const c_char,
+    len: usize,
+    out: *mut c_char,
+) -> c_int {
+    let Some(payload) = (unsafe { input(payload, len) }) else {
+        return LEI_ERR_NULL_POINTER;
+    };
+    if out.is_null() {
+        return LEI_ERR_NULL_POINTER;
+    }
+    if payload.len() != 18 {
+        return status(&LEIError::InvalidPayloadLength { was: payload.len() });
+    }
+    let checked = crate::validate_lou_id_format(&payload[0..4])
+        .and_then(|()| crate::validate_entity_id_format(&payload[4..18]));
+    match checked {
+        Ok(()) => {
+            unsafe { write(&crate::compute_check_digits(payload), out) };
+            LEI_OK
+        }
+        Err(err) => status(&err),
+    }
+}
+
+/// The `LEIError` code for a status, like `"LEI-E009"`, as a static NUL-terminated string, or
+/// `NULL` for `LEI_OK`, `LEI_ERR_NULL_POINTER` and unknown statuses.
+#[no_mangle]
+pub extern "C" fn lei_error_code(status: c_int) -> *const c_char {
+    let code: &'static [u8] = match status {
+        LEI_E_INVALID_LENGTH => b"LEI-E001\0",
+        LEI_E_INVALID_PAYLOAD_LENGTH => b"LEI-E002\0",
+        LEI_E_INVALID_LOU_ID_LENGTH => b"LEI-E003\0",
+        LEI_E_INVALID_ENTITY_ID_LENGTH => b"LEI-E004\0",
+        LEI_E_INVALID_CHECK_DIGITS_LENGTH => b"LEI-E005\0",
+        LEI_E_INVALID_LOU_ID => b"LEI-E006\0",
+        LEI_E_INVALID_ENTITY_ID => b"LEI-E007\0",
+        LEI_E_INVALID_CHECK_DIGITS => b"LEI-E008\0",
+        LEI_E_INCORRECT_CHECK_DIGITS => b"LEI-E009\0",
+        LEI_E_RESERVED_CHECK_DIGITS => b"LEI-E010\0",
+        _ => return ptr::null(),
+    };
+    code.as_ptr().cast()
+}
+
+/// The name of the error kind for a status, like `"IncorrectCheckDigits"`, as a static
+/// NUL-terminated string, or `NULL` for `LEI_OK` and unknown statuses.
+#[no_mangle]
+pub extern "C" fn lei_error_name(status: c_int) -> *const c_char {
+    let name: &'static [u8] = match status {
+        LEI_ERR_NULL_POINTER => b"NullPointer\0",
+        LEI_E_INVALID_LENGTH => b"InvalidLength\0",
+        LEI_E_INVALID_PAYLOAD_LENGTH => b"InvalidPayloadLength\0",
+        LEI_E_INVALID_LOU_ID_LENGTH => b"InvalidLouIdLength\0",
+        LEI_E_INVALID_ENTITY_ID_LENGTH => b"InvalidEntityIdLength\0",
+        LEI_E_INVALID_CHECK_DIGITS_LENGTH => b"InvalidCheckDigitsLength\0",
+        LEI_E_INVALID_LOU_ID => b"InvalidLouId\0",
+        LEI_E_INVALID_ENTITY_ID => b"InvalidEntityId\0",
+        LEI_E_INVALID_CHECK_DIGITS => b"InvalidCheckDigits\0",
+        LEI_E_INCORRECT_CHECK_DIGITS => b"IncorrectCheckDigits\0",
+        LEI_E_RESERVED_CHECK_DIGITS => b"ReservedCheckDigits\0",
+        _ => return ptr::null(),
+    };
+    name.as_ptr().cast()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    fn call(
+        f: unsafe extern "C" fn(*const c_char, usize, *mut c_char) -> c_int,
+        value: &str,
+    ) -> (c_int, String) {
+        let mut out = [0 as c_char; LEI_BUFFER_LEN];
+        let status = unsafe { f(value.as_ptr().cast(), value.len(), out.as_mut_ptr()) };
+        let out = unsafe { CStr::from_ptr(out.as_ptr()) };
+        (status, out.to_str().unwrap().to_owned())
+    }
+
+    #[test]
+    fn parses_into_buffers() {
+        assert_eq!(
+            call(lei_parse, "YZ83GD8L7GG84979J516"),
+            (LEI_OK, "YZ83GD8L7GG84979J516".to_owned())
+        );
+        assert_eq!(
+            call(lei_parse, "YZ83GD8L7GG84979J517"),
+            (LEI_E_INCORRECT_CHECK_DIGITS, String::new())
+        );
+        assert_eq!(
+            call(lei_parse_loose, " yz83gd8l7gg84979j516\n"),
+            (LEI_OK, "YZ83GD8L7GG84979J516".to_owned())
+        );
+        assert_eq!(
+            call(lei_parse_loose, "YZ83GD8L7GG84979J5\u{e9}"),
+            (LEI_E_INVALID_CHECK_DIGITS, String::new())
+        );
+        assert_eq!(
+            call(lei_check_digits, "YZ83GD8L7GG84979J5"),
+            (LEI_OK, "16".to_owned())
+        );
+        assert_eq!(
+            call(lei_check_digits, "YZ8-GD8L7GG84979J5"),
+            (LEI_E_INVALID_LOU_ID, String::new())
+        );
+
+        let value = "549-00IYKILIU506KA05";
+        assert_eq!(
+            unsafe { lei_validate(value.as_ptr().cast(), value.len()) },
+            LEI_E_INVALID_LOU_ID
+        );
+        assert_eq!(
+            unsafe { lei_validate(ptr::null(), 20) },
+            LEI_ERR_NULL_POINTER
+        );
+        assert_eq!(
+            unsafe { lei_validate(ptr::null(), 0) },
+            LEI_E_INVALID_LENGTH
+        );
+    }
+
+    #[test]
+    fn error_codes_match_lei_error() {
+        for value in ["YZ83", "YZ83GD8L7GG84979J517", "YZ83GD8L7GG84979J5A6"] {
+            let err = crate::parse(value).unwrap_err();
+            let status = unsafe { lei_validate(value.as_ptr().cast(), value.len()) };
+            let code = unsafe { CStr::from_ptr(lei_error_code(status)) };
+            let name = unsafe { CStr::from_ptr(lei_error_name(status)) };
+            assert_eq!(code.to_str(), Ok(err.code()));
+            assert_eq!(name.to_str(), Ok(err.kind().name()));
+        }
+        assert!(lei_error_code(LEI_OK).is_null());
+        assert!(lei_error_code(LEI_ERR_NULL_POINTER).is_null());
+        assert!(lei_error_name(42).is_null());
+    }
+}
@@ -0,0 +1,105 @@
+#![warn(missing_docs)]
+//! # lei::check_digits
+//!
+//! A type for the _Check Digit Pair_ of an LEI.
+
+use std::fmt;
+use std::str::from_utf8_unchecked;
+
+use crate::LEIError;
+
+/// The two decimal _Check Digits_ at the end of an LEI.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Hash)]
+pub struct CheckDigits(pub(crate) [u8; 2]);
+
+impl fmt::Display for CheckDigits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl fmt::Debug for CheckDigits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CheckDigits({})", self.as_str())
+    }
+}
+
+impl CheckDigits {
+    /// Check that a string is two ASCII decimal digits, the format of valid _Check Digits_. This
+    /// does not (and cannot, without the rest of the LEI) check that they are correct.
+    pub fn validate(value: &str) -> Result<(), LEIError> {
+        let b = value.as_bytes();
+        if b.len() != 2 {
+            return Err(LEIError::InvalidCheckDigitsLength { was: b.len() });
+        }
+        crate::validate_check_digits_format(b)
+    }
+
+    /// Return the _Check Digits_ as a two-character string.
+    pub fn as_str(&self) -> &str {
+        unsafe { from_utf8_unchecked(&self.0[..]) } // This is safe because we know it is ASCII
+    }
+
+    /// Return the numeric value of the _Check Digits_, from 0 to 99.
+    pub fn as_u8(&self) -> u8 {
+        (self.0[0] - b'0') * 10 + (self.0[1] - b'0')
+    }
+
+    /// Return true if the value is one The Standard reserves as never valid: "00", "01" or "99"
+    /// (section 5.1). Note that LEIs with "00" and "01" have been issued in practice, and they
+    /// pass the MOD 97-10 validation of `parse()`; use `parse_strict_iso()` to reject them.
+    pub fn is_reserved(&self) -> bool {
+        matches!(self.as_u8(), 0 | 1 | 99)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accessors() {
+        let cd = crate::parse("549300IYKILIU506KA05")
+            .unwrap()
+            .check_digits_typed();
+        assert_eq!(cd.as_u8(), 5);
+        assert_eq!(cd.as_str(), "05");
+        assert_eq!(cd.to_string(), "05");
+        assert_eq!(format!("{cd:?}"), "CheckDigits(05)");
+        assert!(!cd.is_reserved());
+    }
+
+    #[test]
+    fn reserved_values() {
+        assert!(CheckDigits(*b"00").is_reserved());
+        assert!(CheckDigits(*b"01").is_reserved());
+        assert!(CheckDigits(*b"99").is_reserved());
+        assert!(!CheckDigits(*b"02").is_reserved());
+        assert!(!CheckDigits(*b"98").is_reserved());
+    }
+
+    #[test]
+    fn incorrect_check_digits_error() {
+        match crate::parse("549300IYKILIU506KA06") {
+            Err(LEIError::IncorrectCheckDigits { was, expected }) => {
+                assert_eq!(was.as_u8(), 6);
+                assert_eq!(expected, CheckDigits(*b"05"));
+                assert!(!expected.is_reserved());
+            }
+            other => panic!("unexpected result {other:?}"),
+        }
+    }
+
+    #[test]
+    fn validate_format() {
+        assert_eq!(CheckDigits::validate("05"), Ok(()));
+        assert_eq!(
+            CheckDigits::validate("5"),
+            Err(LEIError::InvalidCheckDigitsLength { was: 1 })
+        );
+        assert_eq!(
+            CheckDigits::validate("0A"),
+            Err(LEIError::InvalidCheckDigits { was: *b"0A" })
+        );
+    }
+}
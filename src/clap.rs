@@ -0,0 +1,133 @@
+#![warn(missing_docs)]
+//! # lei::clap
+//!
+//! A [clap](https://crates.io/crates/clap) value parser for LEI arguments. `LeiValueParser`
+//! parses strictly (like `parse()`) by default, or loosely (like `parse_loose()`), and reports
+//! invalid values with the `LEIError` message and, where one is likely, a suggested correction.
+//!
+//! `LEI` implements `ValueParserFactory`, so `#[derive(Parser)]` fields of type `LEI` are parsed
+//! strictly without any attribute. Use `lei::clap_parser()` or `LeiValueParser::loose()` to
+//! choose explicitly:
+//!
+//! ```
+//! use clap::{Arg, Command};
+//! use lei::clap::LeiValueParser;
+//! use lei::LEI;
+//!
+//! let cmd = Command::new("lookup")
+//!     .arg(Arg::new("lei").long("lei").value_parser(lei::clap_parser()))
+//!     .arg(Arg::new("parent").long("parent").value_parser(LeiValueParser::loose()));
+//!
+//! let matches = cmd
+//!     .try_get_matches_from(["lookup", "--lei", "YZ83GD8L7GG84979J516", "--parent", " yz83gd8l7gg84979j516"])
+//!     .unwrap();
+//! assert_eq!(matches.get_one::<LEI>("lei"), matches.get_one::<LEI>("parent"));
+//! ```
+
+use std::ffi::OsStr;
+
+use ::clap::builder::{TypedValueParser, ValueParserFactory};
+use ::clap::error::ErrorKind;
+use ::clap::{Arg, Command, Error};
+
+use crate::LEI;
+
+/// Parses argument values to `LEI`s, strictly or loosely.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LeiValueParser {
+    loose: bool,
+}
+
+impl LeiValueParser {
+    /// Parse values with `parse()`.
+    pub fn strict() -> LeiValueParser {
+        LeiValueParser { loose: false }
+    }
+
+    /// Parse values with `parse_loose()`, allowing surrounding whitespace and lowercase letters.
+    pub fn loose() -> LeiValueParser {
+        LeiValueParser { loose: true }
+    }
+}
+
+impl Default for LeiValueParser {
+    fn default() -> LeiValueParser {
+        LeiValueParser::strict()
+    }
+}
+
+impl TypedValueParser for LeiValueParser {
+    type Value = LEI;
+
+    fn parse_ref(&self, cmd: &Command, arg: Option<&Arg>, value: &OsStr) -> Result<LEI, Error> {
+        let value = value.to_str().ok_or_else(|| {
+            cmd.clone()
+                .error(ErrorKind::InvalidUtf8, "invalid UTF-8 in LEI value")
+        })?;
+        let result = if self.loose {
+            crate::parse_loose(value)
+        } else {
+            crate::parse(value)
+        };
+        result.map_err(|err| {
+            let arg = arg.map_or_else(|| "...".to_owned(), |arg| arg.to_string());
+            let mut message = format!("invalid value '{value}' for '{arg}': {err}");
+            if let Some(suggestion) = crate::suggest(value).first() {
+                message.push_str(&format!("\n\n  tip: did you mean '{suggestion}'?"));
+            }
+            cmd.clone().error(ErrorKind::ValueValidation, message)
+        })
+    }
+}
+
+impl ValueParserFactory for LEI {
+    type Parser = LeiValueParser;
+
+    fn value_parser() -> LeiValueParser {
+        LeiValueParser::strict()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command() -> Command {
+        Command::new("lookup").arg(
+            Arg::new("lei")
+                .long("lei")
+                .value_parser(::clap::value_parser!(LEI)),
+        )
+    }
+
+    #[test]
+    fn factory_is_strict() {
+        let matches = command()
+            .try_get_matches_from(["lookup", "--lei", "549300IYKILIU506KA05"])
+            .unwrap();
+        assert_eq!(
+            matches.get_one::<LEI>("lei").unwrap().as_str(),
+            "549300IYKILIU506KA05"
+        );
+        assert!(command()
+            .try_get_matches_from(["lookup", "--lei", "549300iykiliu506ka05"])
+            .is_err());
+    }
+
+    #[test]
+    fn errors_suggest_corrections() {
+        let err = command()
+            .try_get_matches_from(["lookup", "--lei", "549300IYKILIU5O6KA05"])
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
+        let message = err.to_string();
+        assert!(
+            message.contains("invalid value '549300IYKILIU5O6KA05' for '--lei <lei>'"),
+            "{message}"
+        );
+        assert!(
+            message.contains("did you mean '549300IYKILIU506KA05'?"),
+            "{message}"
+        );
+    }
+}
@@ -0,0 +1,337 @@
+#![warn(missing_docs)]
+//! # lei::csv
+//!
+//! A one-call cleaning pipeline for CSV files with an LEI column: values are normalized (trimmed
+//! and uppercased), incorrect _Check Digits_ are optionally repaired, unfixable rows are dropped,
+//! flagged or kept per policy, and every change is recorded in an audit log.
+//!
+//! ```
+//! use lei::csv::{clean, CleanOptions, InvalidPolicy};
+//!
+//! let input = "name,lei\nAcme, yz83gd8l7gg84979j516\nGlobex,549300IYKILIU506KA06\nInitech,bogus\n";
+//! let mut output = Vec::new();
+//! let options = CleanOptions::new("lei").policy(InvalidPolicy::Drop);
+//! let audit = clean(input.as_bytes(), &mut output, &options).unwrap();
+//!
+//! assert_eq!(
+//!     String::from_utf8(output).unwrap(),
+//!     "name,lei\nAcme,YZ83GD8L7GG84979J516\nGlobex,549300IYKILIU506KA05\n"
+//! );
+//! assert_eq!(audit.len(), 3);
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::io;
+
+use crate::{LEIError, LEI};
+
+/// What to do with rows whose LEI cannot be fixed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InvalidPolicy {
+    /// Leave the row out of the output.
+    Drop,
+    /// Keep the row unchanged and describe the problem in an extra column.
+    Flag,
+    /// Keep the row unchanged.
+    Keep,
+}
+
+/// Options for `clean()`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CleanOptions {
+    column: String,
+    policy: InvalidPolicy,
+    repair_check_digits: bool,
+    flag_column: String,
+    delimiter: u8,
+}
+
+impl CleanOptions {
+    /// Clean the column with the given header. By default, incorrect _Check Digits_ are
+    /// repaired, unfixable rows are flagged in a column named `lei_error`, and the delimiter is
+    /// a comma.
+    pub fn new(column: &str) -> CleanOptions {
+        CleanOptions {
+            column: column.to_owned(),
+            policy: InvalidPolicy::Flag,
+            repair_check_digits: true,
+            flag_column: "lei_error".to_owned(),
+            delimiter: b',',
+        }
+    }
+
+    /// Set the policy for rows that cannot be fixed.
+    pub fn policy(mut self, policy: InvalidPolicy) -> CleanOptions {
+        self.policy = policy;
+        self
+    }
+
+    /// Set whether to replace incorrect _Check Digits_ with the correct ones.
+    pub fn repair_check_digits(mut self, repair: bool) -> CleanOptions {
+        self.repair_check_digits = repair;
+        self
+    }
+
+    /// Set the header of the column added by `InvalidPolicy::Flag`.
+    pub fn flag_column(mut self, name: &str) -> CleanOptions {
+        self.flag_column = name.to_owned();
+        self
+    }
+
+    /// Set the field delimiter for both input and output.
+    pub fn delimiter(mut self, delimiter: u8) -> CleanOptions {
+        self.delimiter = delimiter;
+        self
+    }
+}
+
+/// What `clean()` did to one value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Whitespace or case was corrected.
+    Normalized {
+        /// The corrected value.
+        to: LEI,
+    },
+    /// Incorrect _Check Digits_ were replaced.
+    Repaired {
+        /// The corrected value.
+        to: LEI,
+    },
+    /// The row was left out of the output.
+    Dropped(LEIError),
+    /// The row was flagged.
+    Flagged(LEIError),
+    /// The row was kept unchanged.
+    Kept(LEIError),
+}
+
+/// One entry of the audit log: a change (or refusal to change) made to one row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuditEntry {
+    /// The 1-based data row, not counting the header row.
+    pub row: u64,
+    /// The value as found in the input.
+    pub original: String,
+    /// What was done.
+    pub action: Action,
+}
+
+impl Display for AuditEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let row = self.row;
+        let original = &self.original;
+        match &self.action {
+            Action::Normalized { to } => write!(f, "row {row}: normalized {original:?} to {to}"),
+            Action::Repaired { to } => write!(f, "row {row}: repaired {original:?} to {to}"),
+            Action::Dropped(err) => write!(f, "row {row}: dropped {original:?}: {err}"),
+            Action::Flagged(err) => write!(f, "row {row}: flagged {original:?}: {err}"),
+            Action::Kept(err) => write!(f, "row {row}: kept {original:?}: {err}"),
+        }
+    }
+}
+
+/// All the ways cleaning could fail, other than invalid values.
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum CleanError {
+    /// Reading or writing CSV failed.
+    Csv(::csv::Error),
+    /// The header row has no column with the configured name.
+    ColumnNotFound(String),
+}
+
+impl Display for CleanError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CleanError::Csv(err) => write!(f, "CSV error: {err}"),
+            CleanError::ColumnNotFound(name) => write!(f, "column {name:?} not found"),
+        }
+    }
+}
+
+impl Error for CleanError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CleanError::Csv(err) => Some(err),
+            CleanError::ColumnNotFound(_) => None,
+        }
+    }
+}
+
+impl From<::csv::Error> for CleanError {
+    fn from(err: ::csv::Error) -> Self {
+        CleanError::Csv(err)
+    }
+}
+
+/// Fix a single value, returning the canonical LEI and whether a repair was needed, or the
+/// error that makes it unfixable.
+fn fix(value: &str, repair: bool) -> Result<(LEI, bool), LEIError> {
+    match crate::parse_loose(value) {
+        Ok(lei) => Ok((lei, false)),
+        Err(err @ LEIError::IncorrectCheckDigits { .. }) if repair => {
+            crate::repair(&value.trim().to_ascii_uppercase())
+                .map(|lei| (lei, true))
+                .ok_or(err)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Read CSV from `input`, clean the LEI column, and write the result to `output`. Empty values
+/// are passed through untouched. Returns the audit log, in row order, with one entry for every
+/// row whose LEI value was changed or could not be fixed.
+pub fn clean<R: io::Read, W: io::Write>(
+    input: R,
+    output: W,
+    options: &CleanOptions,
+) -> Result<Vec<AuditEntry>, CleanError> {
+    let mut reader = ::csv::ReaderBuilder::new()
+        .delimiter(options.delimiter)
+        .flexible(true)
+        .from_reader(input);
+    let mut writer = ::csv::WriterBuilder::new()
+        .delimiter(options.delimiter)
+        .flexible(true)
+        .from_writer(output);
+
+    let mut headers = reader.headers()?.clone();
+    let index = headers
+        .iter()
+        .position(|h| h.trim() == options.column)
+        .ok_or_else(|| CleanError::ColumnNotFound(options.column.clone()))?;
+    if options.policy == InvalidPolicy::Flag {
+        headers.push_field(&options.flag_column);
+    }
+    writer.write_record(&headers)?;
+
+    let mut audit = Vec::new();
+    for (i, record) in reader.records().enumerate() {
+        let mut record = record?;
+        let row = i as u64 + 1;
+        let original = record.get(index).unwrap_or("");
+
+        if original.is_empty() {
+            if options.policy == InvalidPolicy::Flag {
+                record.push_field("");
+            }
+            writer.write_record(&record)?;
+            continue;
+        }
+
+        let (fixed, action) = match fix(original, options.repair_check_digits) {
+            Ok((lei, repaired)) => {
+                let action = if repaired {
+                    Some(Action::Repaired { to: lei })
+                } else if lei.as_str() != original {
+                    Some(Action::Normalized { to: lei })
+                } else {
+                    None
+                };
+                (Some(lei), action)
+            }
+            Err(err) => match options.policy {
+                InvalidPolicy::Drop => (None, Some(Action::Dropped(err))),
+                InvalidPolicy::Flag => (None, Some(Action::Flagged(err))),
+                InvalidPolicy::Keep => (None, Some(Action::Kept(err))),
+            },
+        };
+
+        let dropped = matches!(action, Some(Action::Dropped(_)));
+        let flag = match &action {
+            Some(Action::Flagged(err)) => err.to_string(),
+            _ => String::new(),
+        };
+        if let Some(action) = action {
+            audit.push(AuditEntry {
+                row,
+                original: original.to_owned(),
+                action,
+            });
+        }
+        if dropped {
+            continue;
+        }
+
+        let mut out = ::csv::StringRecord::new();
+        for (n, field) in record.iter().enumerate() {
+            match fixed {
+                Some(lei) if n == index => out.push_field(lei.as_str()),
+                _ => out.push_field(field),
+            }
+        }
+        if options.policy == InvalidPolicy::Flag {
+            out.push_field(&flag);
+        }
+        writer.write_record(&out)?;
+    }
+
+    writer.flush().map_err(::csv::Error::from)?;
+    Ok(audit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "name,lei,country\n\
+        Acme, yz83gd8l7gg84979j516 ,US\n\
+        Globex,549300IYKILIU506KA06,DE\n\
+        Initech,bogus,FR\n\
+        Hooli,,US\n\
+        Umbrella,JJKC32MCHWDI71265Z06,GB\n";
+
+    fn run(options: &CleanOptions) -> (String, Vec<AuditEntry>) {
+        let mut output = Vec::new();
+        let audit = clean(INPUT.as_bytes(), &mut output, options).unwrap();
+        (String::from_utf8(output).unwrap(), audit)
+    }
+
+    #[test]
+    fn flags_unfixable_rows_by_default() {
+        let (output, audit) = run(&CleanOptions::new("lei"));
+        assert_eq!(
+            output,
+            "name,lei,country,lei_error\n\
+             Acme,YZ83GD8L7GG84979J516,US,\n\
+             Globex,549300IYKILIU506KA05,DE,\n\
+             Initech,bogus,FR,invalid length 5 bytes when expecting 20\n\
+             Hooli,,US,\n\
+             Umbrella,JJKC32MCHWDI71265Z06,GB,\n"
+        );
+        let log: Vec<String> = audit.iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            log,
+            vec![
+                "row 1: normalized \" yz83gd8l7gg84979j516 \" to YZ83GD8L7GG84979J516",
+                "row 2: repaired \"549300IYKILIU506KA06\" to 549300IYKILIU506KA05",
+                "row 3: flagged \"bogus\": invalid length 5 bytes when expecting 20",
+            ]
+        );
+    }
+
+    #[test]
+    fn keep_without_repair_leaves_bad_values_alone() {
+        let options = CleanOptions::new("lei")
+            .policy(InvalidPolicy::Keep)
+            .repair_check_digits(false);
+        let (output, audit) = run(&options);
+        assert!(output.contains("Globex,549300IYKILIU506KA06,DE\n"));
+        assert!(output.contains("Initech,bogus,FR\n"));
+        assert_eq!(audit.len(), 3);
+        assert!(matches!(
+            audit[1].action,
+            Action::Kept(LEIError::IncorrectCheckDigits { .. })
+        ));
+    }
+
+    #[test]
+    fn missing_column_is_an_error() {
+        let mut output = Vec::new();
+        let result = clean(INPUT.as_bytes(), &mut output, &CleanOptions::new("LEI"));
+        assert!(matches!(result, Err(CleanError::ColumnNotFound(_))));
+    }
+}
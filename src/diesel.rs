@@ -0,0 +1,179 @@
+#![warn(missing_docs)]
+//! # lei::diesel
+//!
+//! [Diesel](https://crates.io/crates/diesel) support for `LEI` as a `Text` column (which Diesel
+//! also uses for `VARCHAR` and `CHAR(20)`) on every backend that supports `Text`: `ToSql`,
+//! `FromSql` (validating with the strict `parse()`), `Queryable`, and `AsExpression` for `LEI`
+//! and `&LEI`, so LEIs can be used in filters, inserts and loaded rows.
+//!
+//! ```
+//! use diesel::prelude::*;
+//!
+//! diesel::table! {
+//!     entities (id) {
+//!         id -> Integer,
+//!         lei -> Text,
+//!     }
+//! }
+//!
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//! diesel::sql_query("CREATE TABLE entities (id INTEGER PRIMARY KEY, lei CHAR(20) NOT NULL)")
+//!     .execute(&mut conn)
+//!     .unwrap();
+//!
+//! let lei = lei::parse("YZ83GD8L7GG84979J516").unwrap();
+//! diesel::insert_into(entities::table)
+//!     .values((entities::id.eq(1), entities::lei.eq(lei)))
+//!     .execute(&mut conn)
+//!     .unwrap();
+//! let loaded: lei::LEI = entities::table
+//!     .select(entities::lei)
+//!     .first(&mut conn)
+//!     .unwrap();
+//! assert_eq!(loaded, lei);
+//! ```
+
+use ::diesel::backend::Backend;
+use ::diesel::deserialize::{FromSql, Queryable};
+use ::diesel::expression::AsExpression;
+use ::diesel::serialize::{Output, ToSql};
+use ::diesel::sql_types::{Nullable, Text};
+use ::diesel::{deserialize, serialize};
+
+use crate::LEI;
+
+impl<DB> ToSql<Text, DB> for LEI
+where
+    DB: Backend,
+    str: ToSql<Text, DB>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+        <str as ToSql<Text, DB>>::to_sql(self.as_str(), out)
+    }
+}
+
+impl<DB> FromSql<Text, DB> for LEI
+where
+    DB: Backend,
+    String: FromSql<Text, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<LEI> {
+        let value = <String as FromSql<Text, DB>>::from_sql(bytes)?;
+        Ok(crate::parse(&value)?)
+    }
+}
+
+impl<DB> Queryable<Text, DB> for LEI
+where
+    DB: Backend,
+    LEI: FromSql<Text, DB>,
+{
+    type Row = LEI;
+
+    fn build(row: LEI) -> deserialize::Result<LEI> {
+        Ok(row)
+    }
+}
+
+impl AsExpression<Text> for LEI {
+    type Expression = <String as AsExpression<Text>>::Expression;
+
+    fn as_expression(self) -> Self::Expression {
+        AsExpression::<Text>::as_expression(self.to_string())
+    }
+}
+
+impl AsExpression<Nullable<Text>> for LEI {
+    type Expression = <String as AsExpression<Nullable<Text>>>::Expression;
+
+    fn as_expression(self) -> Self::Expression {
+        AsExpression::<Nullable<Text>>::as_expression(self.to_string())
+    }
+}
+
+impl<'a> AsExpression<Text> for &'a LEI {
+    type Expression = <&'a str as AsExpression<Text>>::Expression;
+
+    fn as_expression(self) -> Self::Expression {
+        AsExpression::<Text>::as_expression(self.as_str())
+    }
+}
+
+impl<'a> AsExpression<Nullable<Text>> for &'a LEI {
+    type Expression = <&'a str as AsExpression<Nullable<Text>>>::Expression;
+
+    fn as_expression(self) -> Self::Expression {
+        AsExpression::<Nullable<Text>>::as_expression(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LEIError;
+    use ::diesel::prelude::*;
+    use ::diesel::result::{DeserializeFieldError, Error};
+
+    ::diesel::table! {
+        entities (id) {
+            id -> Integer,
+            lei -> Nullable<Text>,
+        }
+    }
+
+    fn connect() -> SqliteConnection {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        ::diesel::sql_query("CREATE TABLE entities (id INTEGER PRIMARY KEY, lei CHAR(20))")
+            .execute(&mut conn)
+            .unwrap();
+        conn
+    }
+
+    #[test]
+    fn nullable_columns_round_trip() {
+        let mut conn = connect();
+        let lei = crate::parse("549300IYKILIU506KA05").unwrap();
+        ::diesel::insert_into(entities::table)
+            .values(&vec![
+                (entities::id.eq(1), entities::lei.eq(Some(lei))),
+                (entities::id.eq(2), entities::lei.eq(None)),
+            ])
+            .execute(&mut conn)
+            .unwrap();
+
+        let found: Vec<i32> = entities::table
+            .select(entities::id)
+            .filter(entities::lei.eq(&lei))
+            .load(&mut conn)
+            .unwrap();
+        assert_eq!(found, vec![1]);
+
+        let loaded: Vec<Option<LEI>> = entities::table
+            .select(entities::lei)
+            .order(entities::id)
+            .load(&mut conn)
+            .unwrap();
+        assert_eq!(loaded, vec![Some(lei), None]);
+    }
+
+    #[test]
+    fn invalid_values_fail_to_load() {
+        let mut conn = connect();
+        ::diesel::sql_query("INSERT INTO entities VALUES (1, '549300IYKILIU506KA06')")
+            .execute(&mut conn)
+            .unwrap();
+
+        let result: Result<Option<LEI>, Error> =
+            entities::table.select(entities::lei).first(&mut conn);
+        let err = match result {
+            Err(Error::DeserializationError(err)) => err,
+            other => panic!("expected a deserialization error, got {other:?}"),
+        };
+        let err = err.downcast_ref::<DeserializeFieldError>().unwrap();
+        assert_eq!(err.field_name.as_deref(), Some("lei"));
+        assert!(matches!(
+            err.error.downcast_ref::<LEIError>(),
+            Some(LEIError::IncorrectCheckDigits { .. })
+        ));
+    }
+}
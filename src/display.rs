@@ -0,0 +1,74 @@
+#![warn(missing_docs)]
+//! # lei::display
+//!
+//! Alternate renderings of an LEI for user interfaces and printed reports, returned by
+//! `LEI::display_lowercase()` and `LEI::display_grouped()`.
+//!
+//! ```
+//! let lei = lei::parse("549300IYKILIU506KA05").unwrap();
+//! assert_eq!(lei.display_lowercase().to_string(), "549300iykiliu506ka05");
+//! assert_eq!(lei.display_grouped().to_string(), "5493 00IYKILIU506KA 05");
+//! assert_eq!(format!("{lei:#}"), "5493 00IYKILIU506KA 05");
+//! ```
+
+use std::fmt;
+
+use crate::LEI;
+
+/// Displays an LEI in lowercase.
+#[derive(Clone, Copy, Debug)]
+pub struct LowercaseDisplay<'a>(pub(crate) &'a LEI);
+
+impl fmt::Display for LowercaseDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut bb = self.0.into_bytes();
+        bb.make_ascii_lowercase();
+        let temp = unsafe { std::str::from_utf8_unchecked(&bb) }; // This is safe because we know it is ASCII
+        f.pad(temp)
+    }
+}
+
+/// Displays an LEI with its _LOU ID_, _Entity ID_ and _Check Digits_ separated by spaces, as
+/// in "5493 00IYKILIU506KA 05". This is also the alternate (`{:#}`) form of `LEI`'s `Display`.
+#[derive(Clone, Copy, Debug)]
+pub struct GroupedDisplay<'a>(pub(crate) &'a LEI);
+
+impl fmt::Display for GroupedDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let b = self.0.as_bytes();
+        let mut bb = [b' '; 22];
+        bb[0..4].copy_from_slice(&b[LEI::LOU_ID_RANGE]);
+        bb[5..19].copy_from_slice(&b[LEI::ENTITY_ID_RANGE]);
+        bb[20..22].copy_from_slice(&b[LEI::CHECK_DIGITS_RANGE]);
+        let temp = unsafe { std::str::from_utf8_unchecked(&bb) }; // This is safe because we know it is ASCII
+        f.pad(temp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn renderings() {
+        let lei = crate::parse("YZ83GD8L7GG84979J516").unwrap();
+        assert_eq!(lei.display_lowercase().to_string(), "yz83gd8l7gg84979j516");
+        assert_eq!(lei.display_grouped().to_string(), "YZ83 GD8L7GG84979J5 16");
+        assert_eq!(format!("{lei:#}"), "YZ83 GD8L7GG84979J5 16");
+        assert_eq!(format!("{lei}"), "YZ83GD8L7GG84979J516");
+    }
+
+    #[test]
+    fn respects_width_and_alignment() {
+        let lei = crate::parse("YZ83GD8L7GG84979J516").unwrap();
+        assert_eq!(format!("{lei:>22}"), "  YZ83GD8L7GG84979J516");
+        assert_eq!(format!("{lei:*<21}"), "YZ83GD8L7GG84979J516*");
+        assert_eq!(format!("{lei:^24}"), "  YZ83GD8L7GG84979J516  ");
+        assert_eq!(format!("{lei:>#23}"), " YZ83 GD8L7GG84979J5 16");
+        assert_eq!(
+            format!("{:>21}", lei.display_lowercase()),
+            " yz83gd8l7gg84979j516"
+        );
+        assert_eq!(format!("[{:<6}]", lei.lou_id_typed()), "[YZ83  ]");
+        assert_eq!(format!("{:>4}", lei.check_digits_typed()), "  16");
+        assert_eq!(format!("{:>21}", lei.as_lei_str()), " YZ83GD8L7GG84979J516");
+    }
+}
@@ -0,0 +1,124 @@
+#![warn(missing_docs)]
+//! # lei::entity_id
+//!
+//! A validated _Entity ID_: the 14 characters an _LOU_ assigns to identify an entity.
+
+use std::fmt;
+use std::str::from_utf8_unchecked;
+use std::str::FromStr;
+
+use crate::LEIError;
+
+/// An _Entity ID_ in confirmed valid format: 14 uppercase ASCII alphanumerics.
+///
+/// ```
+/// use lei::EntityId;
+///
+/// let entity: EntityId = "00IYKILIU506KA".parse().unwrap();
+/// assert_eq!(entity.to_string(), "00IYKILIU506KA");
+///
+/// let lei = lei::parse("549300IYKILIU506KA05").unwrap();
+/// assert_eq!(lei.entity_id_typed(), entity);
+/// ```
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Hash)]
+pub struct EntityId(pub(crate) [u8; 14]);
+
+impl fmt::Display for EntityId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl fmt::Debug for EntityId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "EntityId({})", self.as_str())
+    }
+}
+
+impl FromStr for EntityId {
+    type Err = LEIError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        EntityId::parse(s)
+    }
+}
+
+impl TryFrom<&str> for EntityId {
+    type Error = LEIError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        EntityId::parse(value)
+    }
+}
+
+impl AsRef<str> for EntityId {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl EntityId {
+    /// Parse a string to a valid _Entity ID_ or an error, requiring exactly 14 uppercase ASCII
+    /// alphanumerics.
+    pub fn parse(value: &str) -> Result<EntityId, LEIError> {
+        EntityId::validate(value)?;
+        let b = value.as_bytes();
+        let mut bb = [0u8; 14];
+        bb.copy_from_slice(b);
+        Ok(EntityId(bb))
+    }
+
+    /// Check that a string is a valid _Entity ID_ without constructing one, for validating the
+    /// part on its own (for example, as it is typed into a form field).
+    pub fn validate(value: &str) -> Result<(), LEIError> {
+        let b = value.as_bytes();
+        if b.len() != 14 {
+            return Err(LEIError::InvalidEntityIdLength { was: b.len() });
+        }
+        crate::validate_entity_id_format(b)
+    }
+
+    /// Return the _Entity ID_ as a string slice.
+    pub fn as_str(&self) -> &str {
+        unsafe { from_utf8_unchecked(&self.0[..]) } // This is safe because we know it is ASCII
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_display() {
+        let entity = EntityId::parse("00IYKILIU506KA").unwrap();
+        assert_eq!(entity.as_str(), "00IYKILIU506KA");
+        assert_eq!(format!("{entity:?}"), "EntityId(00IYKILIU506KA)");
+        assert_eq!(
+            EntityId::try_from("GD8L7GG84979J5"),
+            "GD8L7GG84979J5".parse()
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            EntityId::parse("00IYKILIU506K"),
+            Err(LEIError::InvalidEntityIdLength { was: 13 })
+        );
+        assert_eq!(
+            EntityId::parse("00IYKILIU506Ka"),
+            Err(LEIError::InvalidEntityId {
+                was: *b"00IYKILIU506Ka"
+            })
+        );
+    }
+
+    #[test]
+    fn validate_without_constructing() {
+        assert_eq!(EntityId::validate("00IYKILIU506KA"), Ok(()));
+        assert_eq!(
+            EntityId::validate("00IYK"),
+            Err(LEIError::InvalidEntityIdLength { was: 5 })
+        );
+    }
+}
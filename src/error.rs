@@ -6,7 +6,10 @@
 use std::error::Error;
 use std::fmt::Formatter;
 use std::fmt::{Debug, Display};
-use std::str::from_utf8_unchecked;
+
+use std::ops::Range;
+
+use crate::{CheckDigits, Part};
 
 /// All the ways parsing or building could fail.
 #[non_exhaustive]
@@ -32,6 +35,11 @@ pub enum LEIError {
         /// The length we found
         was: usize,
     },
+    /// The _Check Digits_ length is not exactly 2 bytes (checked when validating them alone).
+    InvalidCheckDigitsLength {
+        /// The length we found
+        was: usize,
+    },
     /// The input _LOU ID_ is not 4 uppercase ASCII alphanumeric characters.
     InvalidLouId {
         /// The _LOU ID_ we found
@@ -50,10 +58,184 @@ pub enum LEIError {
     /// The input _Check Digits_ is in a valid format, but has an incorrect value.
     IncorrectCheckDigits {
         /// The _Check Digits_ we found
-        was: [u8; 2],
+        was: CheckDigits,
         /// The _Check Digits_ we expected
-        expected: [u8; 2],
+        expected: CheckDigits,
     },
+    /// The input _Check Digits_ are correct, but are one of the pairs The Standard reserves as
+    /// never valid: "00", "01" or "99" (checked only by `parse_strict_iso()`).
+    ReservedCheckDigits {
+        /// The _Check Digits_ we found
+        was: CheckDigits,
+    },
+}
+
+/// The kind of an `LEIError`, without its details, as returned by `LEIError::kind()`.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LEIErrorKind {
+    /// See `LEIError::InvalidLength`.
+    InvalidLength,
+    /// See `LEIError::InvalidPayloadLength`.
+    InvalidPayloadLength,
+    /// See `LEIError::InvalidLouIdLength`.
+    InvalidLouIdLength,
+    /// See `LEIError::InvalidEntityIdLength`.
+    InvalidEntityIdLength,
+    /// See `LEIError::InvalidCheckDigitsLength`.
+    InvalidCheckDigitsLength,
+    /// See `LEIError::InvalidLouId`.
+    InvalidLouId,
+    /// See `LEIError::InvalidEntityId`.
+    InvalidEntityId,
+    /// See `LEIError::InvalidCheckDigits`.
+    InvalidCheckDigits,
+    /// See `LEIError::IncorrectCheckDigits`.
+    IncorrectCheckDigits,
+    /// See `LEIError::ReservedCheckDigits`.
+    ReservedCheckDigits,
+}
+
+impl LEIErrorKind {
+    const ALL: [LEIErrorKind; 10] = [
+        LEIErrorKind::InvalidLength,
+        LEIErrorKind::InvalidPayloadLength,
+        LEIErrorKind::InvalidLouIdLength,
+        LEIErrorKind::InvalidEntityIdLength,
+        LEIErrorKind::InvalidCheckDigitsLength,
+        LEIErrorKind::InvalidLouId,
+        LEIErrorKind::InvalidEntityId,
+        LEIErrorKind::InvalidCheckDigits,
+        LEIErrorKind::IncorrectCheckDigits,
+        LEIErrorKind::ReservedCheckDigits,
+    ];
+
+    /// The name of the kind, which is also the name of the `LEIError` variant, such as
+    /// `"IncorrectCheckDigits"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            LEIErrorKind::InvalidLength => "InvalidLength",
+            LEIErrorKind::InvalidPayloadLength => "InvalidPayloadLength",
+            LEIErrorKind::InvalidLouIdLength => "InvalidLouIdLength",
+            LEIErrorKind::InvalidEntityIdLength => "InvalidEntityIdLength",
+            LEIErrorKind::InvalidCheckDigitsLength => "InvalidCheckDigitsLength",
+            LEIErrorKind::InvalidLouId => "InvalidLouId",
+            LEIErrorKind::InvalidEntityId => "InvalidEntityId",
+            LEIErrorKind::InvalidCheckDigits => "InvalidCheckDigits",
+            LEIErrorKind::IncorrectCheckDigits => "IncorrectCheckDigits",
+            LEIErrorKind::ReservedCheckDigits => "ReservedCheckDigits",
+        }
+    }
+
+    /// Find the kind with the given name, as returned by `name()`.
+    pub fn from_name(name: &str) -> Option<LEIErrorKind> {
+        LEIErrorKind::ALL
+            .into_iter()
+            .find(|kind| kind.name() == name)
+    }
+
+    /// The stable code for this kind of error. See `LEIError::code()`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            LEIErrorKind::InvalidLength => "LEI-E001",
+            LEIErrorKind::InvalidPayloadLength => "LEI-E002",
+            LEIErrorKind::InvalidLouIdLength => "LEI-E003",
+            LEIErrorKind::InvalidEntityIdLength => "LEI-E004",
+            LEIErrorKind::InvalidCheckDigitsLength => "LEI-E005",
+            LEIErrorKind::InvalidLouId => "LEI-E006",
+            LEIErrorKind::InvalidEntityId => "LEI-E007",
+            LEIErrorKind::InvalidCheckDigits => "LEI-E008",
+            LEIErrorKind::IncorrectCheckDigits => "LEI-E009",
+            LEIErrorKind::ReservedCheckDigits => "LEI-E010",
+        }
+    }
+}
+
+impl LEIError {
+    /// The kind of error, without its details.
+    pub fn kind(&self) -> LEIErrorKind {
+        match self {
+            LEIError::InvalidLength { .. } => LEIErrorKind::InvalidLength,
+            LEIError::InvalidPayloadLength { .. } => LEIErrorKind::InvalidPayloadLength,
+            LEIError::InvalidLouIdLength { .. } => LEIErrorKind::InvalidLouIdLength,
+            LEIError::InvalidEntityIdLength { .. } => LEIErrorKind::InvalidEntityIdLength,
+            LEIError::InvalidCheckDigitsLength { .. } => LEIErrorKind::InvalidCheckDigitsLength,
+            LEIError::InvalidLouId { .. } => LEIErrorKind::InvalidLouId,
+            LEIError::InvalidEntityId { .. } => LEIErrorKind::InvalidEntityId,
+            LEIError::InvalidCheckDigits { .. } => LEIErrorKind::InvalidCheckDigits,
+            LEIError::IncorrectCheckDigits { .. } => LEIErrorKind::IncorrectCheckDigits,
+            LEIError::ReservedCheckDigits { .. } => LEIErrorKind::ReservedCheckDigits,
+        }
+    }
+
+    /// A stable, machine-readable code for the kind of error, for logging, aggregating and
+    /// mapping errors without matching on `Display` output. Codes are never reused.
+    ///
+    /// | Code       | Kind                       |
+    /// |------------|----------------------------|
+    /// | `LEI-E001` | `InvalidLength`            |
+    /// | `LEI-E002` | `InvalidPayloadLength`     |
+    /// | `LEI-E003` | `InvalidLouIdLength`       |
+    /// | `LEI-E004` | `InvalidEntityIdLength`    |
+    /// | `LEI-E005` | `InvalidCheckDigitsLength` |
+    /// | `LEI-E006` | `InvalidLouId`             |
+    /// | `LEI-E007` | `InvalidEntityId`          |
+    /// | `LEI-E008` | `InvalidCheckDigits`       |
+    /// | `LEI-E009` | `IncorrectCheckDigits`     |
+    /// | `LEI-E010` | `ReservedCheckDigits`      |
+    ///
+    /// ```
+    /// let err = lei::parse("549300IYKILIU506KA06").unwrap_err();
+    /// assert_eq!(err.code(), "LEI-E009");
+    /// assert_eq!(err.kind(), lei::LEIErrorKind::IncorrectCheckDigits);
+    /// ```
+    pub fn code(&self) -> &'static str {
+        self.kind().code()
+    }
+
+    /// The part of the LEI the error concerns, or `None` for errors about the length of the
+    /// whole input or of a part.
+    pub fn part(&self) -> Option<Part> {
+        match self {
+            LEIError::InvalidLouId { .. } => Some(Part::LouId),
+            LEIError::InvalidEntityId { .. } => Some(Part::EntityId),
+            LEIError::InvalidCheckDigits { .. }
+            | LEIError::IncorrectCheckDigits { .. }
+            | LEIError::ReservedCheckDigits { .. } => Some(Part::CheckDigits),
+            _ => None,
+        }
+    }
+
+    /// The byte range of the offending characters within a 20-character input, from the first
+    /// to the last invalid character of the part, or the whole _Check Digits_ when their value
+    /// is wrong. Errors about lengths have no span. For errors from validating a part on its
+    /// own, such as `LouId::parse()`, subtract the start of the part within an LEI.
+    ///
+    /// ```
+    /// let err = lei::parse("5493-0IYKILIU5*6KA05").unwrap_err();
+    /// assert_eq!(err.part(), Some(lei::Part::EntityId));
+    /// assert_eq!(err.span(), Some(4..15));
+    /// ```
+    pub fn span(&self) -> Option<Range<usize>> {
+        fn bad(offset: usize, was: &[u8], valid: fn(&u8) -> bool) -> Option<Range<usize>> {
+            let first = was.iter().position(|b| !valid(b))?;
+            let last = was.iter().rposition(|b| !valid(b))?;
+            Some(offset + first..offset + last + 1)
+        }
+        fn alphanumeric(b: &u8) -> bool {
+            b.is_ascii_digit() || b.is_ascii_uppercase()
+        }
+
+        match self {
+            LEIError::InvalidLouId { was } => bad(0, was, alphanumeric),
+            LEIError::InvalidEntityId { was } => bad(4, was, alphanumeric),
+            LEIError::InvalidCheckDigits { was } => bad(18, was, u8::is_ascii_digit),
+            LEIError::IncorrectCheckDigits { .. } | LEIError::ReservedCheckDigits { .. } => {
+                Some(18..20)
+            }
+            _ => None,
+        }
+    }
 }
 
 impl Debug for LEIError {
@@ -71,6 +253,9 @@ impl Debug for LEIError {
             LEIError::InvalidEntityIdLength { was } => {
                 write!(f, "InvalidEntityIdLength {{ was: {was:?} }}")
             }
+            LEIError::InvalidCheckDigitsLength { was } => {
+                write!(f, "InvalidCheckDigitsLength {{ was: {was:?} }}")
+            }
             LEIError::InvalidLouId { was } => match std::str::from_utf8(was) {
                 Ok(s) => {
                     write!(f, "InvalidLouId {{ was: {s:?} }}")
@@ -96,14 +281,16 @@ impl Debug for LEIError {
                 }
             },
             LEIError::IncorrectCheckDigits { was, expected } => {
-                let was_utf8 = unsafe { from_utf8_unchecked(was) }; // This is safe because we know it is ASCII
-                let expected_utf8 = unsafe { from_utf8_unchecked(expected) }; // This is safe because we know it is ASCII
-
                 write!(
                     f,
-                    "IncorrectCheckDigits {{ was: {was_utf8:?}, expected: {expected_utf8:?} }}"
+                    "IncorrectCheckDigits {{ was: {:?}, expected: {:?} }}",
+                    was.as_str(),
+                    expected.as_str()
                 )
             }
+            LEIError::ReservedCheckDigits { was } => {
+                write!(f, "ReservedCheckDigits {{ was: {:?} }}", was.as_str())
+            }
         }
     }
 }
@@ -123,6 +310,12 @@ impl Display for LEIError {
             LEIError::InvalidEntityIdLength { was } => {
                 write!(f, "invalid Entity ID length {was} bytes when expecting 14")
             }
+            LEIError::InvalidCheckDigitsLength { was } => {
+                write!(
+                    f,
+                    "invalid Check Digits length {was} bytes when expecting 2"
+                )
+            }
             LEIError::InvalidLouId { was } => match std::str::from_utf8(was) {
                 Ok(s) => {
                     write!(
@@ -161,16 +354,106 @@ impl Display for LEIError {
                 }
             },
             LEIError::IncorrectCheckDigits { was, expected } => {
-                let was_utf8 = unsafe { from_utf8_unchecked(was) }; // This is safe because we know it is ASCII
-                let expected_utf8 = unsafe { from_utf8_unchecked(expected) }; // This is safe because we know it is ASCII
-
                 write!(
                     f,
-                    "incorrect check digits {was_utf8:?} when expecting {expected_utf8:?}"
+                    "incorrect check digits {:?} when expecting {:?}",
+                    was.as_str(),
+                    expected.as_str()
+                )
+            }
+            LEIError::ReservedCheckDigits { was } => {
+                write!(
+                    f,
+                    "check digits {:?} are reserved by ISO 17442 and not valid",
+                    was.as_str()
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for LEIError {
+    fn format(&self, f: defmt::Formatter) {
+        match self {
+            LEIError::InvalidLength { was } => {
+                defmt::write!(f, "InvalidLength {{ was: {=usize} }}", was)
+            }
+            LEIError::InvalidPayloadLength { was } => {
+                defmt::write!(f, "InvalidPayloadLength {{ was: {=usize} }}", was)
+            }
+            LEIError::InvalidLouIdLength { was } => {
+                defmt::write!(f, "InvalidLouIdLength {{ was: {=usize} }}", was)
+            }
+            LEIError::InvalidEntityIdLength { was } => {
+                defmt::write!(f, "InvalidEntityIdLength {{ was: {=usize} }}", was)
+            }
+            LEIError::InvalidCheckDigitsLength { was } => {
+                defmt::write!(f, "InvalidCheckDigitsLength {{ was: {=usize} }}", was)
+            }
+            LEIError::InvalidLouId { was } => {
+                defmt::write!(f, "InvalidLouId {{ was: {=[u8]:a} }}", &was[..])
+            }
+            LEIError::InvalidEntityId { was } => {
+                defmt::write!(f, "InvalidEntityId {{ was: {=[u8]:a} }}", &was[..])
+            }
+            LEIError::InvalidCheckDigits { was } => {
+                defmt::write!(f, "InvalidCheckDigits {{ was: {=[u8]:a} }}", &was[..])
+            }
+            LEIError::IncorrectCheckDigits { was, expected } => {
+                defmt::write!(
+                    f,
+                    "IncorrectCheckDigits {{ was: {=[u8]:a}, expected: {=[u8]:a} }}",
+                    &was.0[..],
+                    &expected.0[..]
                 )
             }
+            LEIError::ReservedCheckDigits { was } => {
+                defmt::write!(f, "ReservedCheckDigits {{ was: {=[u8]:a} }}", &was.0[..])
+            }
         }
     }
 }
 
 impl Error for LEIError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_are_distinct() {
+        let errors = [
+            LEIError::InvalidLength { was: 0 },
+            LEIError::InvalidPayloadLength { was: 0 },
+            LEIError::InvalidLouIdLength { was: 0 },
+            LEIError::InvalidEntityIdLength { was: 0 },
+            LEIError::InvalidCheckDigitsLength { was: 0 },
+            LEIError::InvalidLouId { was: *b"    " },
+            LEIError::InvalidEntityId { was: [b' '; 14] },
+            LEIError::InvalidCheckDigits { was: *b"  " },
+            LEIError::IncorrectCheckDigits {
+                was: CheckDigits(*b"06"),
+                expected: CheckDigits(*b"05"),
+            },
+            LEIError::ReservedCheckDigits {
+                was: CheckDigits(*b"00"),
+            },
+        ];
+        let codes: std::collections::HashSet<&str> = errors.iter().map(|e| e.code()).collect();
+        assert_eq!(codes.len(), errors.len());
+        assert_eq!(errors[0].code(), "LEI-E001");
+        assert_eq!(errors[9].kind(), LEIErrorKind::ReservedCheckDigits);
+    }
+
+    #[test]
+    fn spans() {
+        let span = |s: &str| crate::parse(s).unwrap_err().span();
+        assert_eq!(span("549300IYKILIU506KA"), None);
+        assert_eq!(span("54a300IYKILIU506KA05"), Some(2..3));
+        assert_eq!(span("549300IYKILIU506Ka05"), Some(17..18));
+        assert_eq!(span("549300IYKILIU506KA0x"), Some(19..20));
+        assert_eq!(span("549300IYKILIU506KA06"), Some(18..20));
+        assert_eq!(span("5493ÄIYKILIU506KA05"), Some(4..6));
+    }
+}
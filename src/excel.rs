@@ -0,0 +1,250 @@
+#![warn(missing_docs)]
+//! # lei::excel
+//!
+//! Reading and validating a column of LEIs from Excel (`.xlsx`, `.xls`, `.xlsb`) and OpenDocument
+//! (`.ods`) workbooks via [calamine](https://crates.io/crates/calamine).
+//!
+//! Spreadsheet cells are validated with `parse_loose()`, so stray whitespace (including the
+//! non-breaking spaces Excel likes to keep) and lowercase letters are tolerated. Numeric cells
+//! are accepted too: an LEI made only of digits is often coerced to a number on entry, and is
+//! read back as its integer value. Because a 20-digit number exceeds the precision of an Excel
+//! number, such cells usually fail check-digit validation and show up in the error list.
+
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+use calamine::{open_workbook_auto, Data, Range, Reader};
+
+use crate::{LEIError, LEI};
+
+/// All the ways reading a column could fail, other than invalid cell values.
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum ExcelError {
+    /// The workbook could not be opened or read.
+    Workbook(calamine::Error),
+    /// The named sheet does not exist.
+    SheetNotFound(String),
+    /// The workbook has no sheets.
+    NoSheets,
+    /// No header cell in the first row of the sheet matches the column name.
+    ColumnNotFound(String),
+}
+
+impl Display for ExcelError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ExcelError::Workbook(err) => write!(f, "unable to read workbook: {err}"),
+            ExcelError::SheetNotFound(name) => write!(f, "sheet {name:?} not found"),
+            ExcelError::NoSheets => write!(f, "workbook has no sheets"),
+            ExcelError::ColumnNotFound(name) => write!(f, "column {name:?} not found"),
+        }
+    }
+}
+
+impl Error for ExcelError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ExcelError::Workbook(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<calamine::Error> for ExcelError {
+    fn from(err: calamine::Error) -> Self {
+        ExcelError::Workbook(err)
+    }
+}
+
+/// What is wrong with a cell.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CellProblem {
+    /// The cell holds a boolean, date, duration or error value rather than text or a number.
+    NotText,
+    /// The cell value is not a valid LEI.
+    Invalid(LEIError),
+}
+
+/// A cell that did not hold a valid LEI.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CellError {
+    /// The 1-based row number, as displayed by Excel.
+    pub row: u32,
+    /// The cell value as text.
+    pub value: String,
+    /// What is wrong with it.
+    pub problem: CellProblem,
+}
+
+/// The result of validating one column of a sheet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnReport {
+    /// The 1-based row number of the header row.
+    pub header_row: u32,
+    /// The cleaned column: one entry per data row below the header, holding the canonical LEI
+    /// or `None` for empty and invalid cells.
+    pub cleaned: Vec<Option<LEI>>,
+    /// The cells of the header row.
+    pub header: Vec<Data>,
+    /// The cleaned sheet: every data row below the header, with each valid LEI cell replaced by
+    /// the canonical LEI as text. Other cells, including invalid LEI cells, are unchanged.
+    pub rows: Vec<Vec<Data>>,
+    /// Every cell that did not hold a valid LEI, in row order.
+    pub errors: Vec<CellError>,
+}
+
+impl ColumnReport {
+    /// The number of valid LEIs in the column.
+    pub fn valid_count(&self) -> usize {
+        self.cleaned.iter().filter(|c| c.is_some()).count()
+    }
+}
+
+/// Read and validate the named column of a workbook file. The column is located by matching
+/// `column` against the trimmed cells of the first row. If `sheet` is `None`, the first sheet is
+/// used.
+pub fn validate_column<P: AsRef<Path>>(
+    path: P,
+    sheet: Option<&str>,
+    column: &str,
+) -> Result<ColumnReport, ExcelError> {
+    let mut workbook = open_workbook_auto(path)?;
+    let sheet = match sheet {
+        Some(name) => {
+            if !workbook.sheet_names().iter().any(|s| s == name) {
+                return Err(ExcelError::SheetNotFound(name.to_owned()));
+            }
+            name.to_owned()
+        }
+        None => workbook
+            .sheet_names()
+            .first()
+            .cloned()
+            .ok_or(ExcelError::NoSheets)?,
+    };
+    let range = workbook.worksheet_range(&sheet)?;
+    validate_range_column(&range, column)
+}
+
+/// Validate the named column of an already-loaded sheet range.
+pub fn validate_range_column(
+    range: &Range<Data>,
+    column: &str,
+) -> Result<ColumnReport, ExcelError> {
+    let (start_row, _) = range.start().unwrap_or((0, 0));
+    let mut rows = range.rows();
+
+    let header = rows
+        .next()
+        .ok_or_else(|| ExcelError::ColumnNotFound(column.to_owned()))?;
+    let index = header
+        .iter()
+        .position(|cell| matches!(cell, Data::String(s) if s.trim() == column))
+        .ok_or_else(|| ExcelError::ColumnNotFound(column.to_owned()))?;
+
+    let mut report = ColumnReport {
+        header_row: start_row + 1,
+        cleaned: Vec::new(),
+        header: header.to_vec(),
+        rows: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    for (i, row) in rows.enumerate() {
+        let row_number = start_row + 2 + i as u32;
+        let cell = row.get(index).unwrap_or(&Data::Empty);
+        let value = match cell {
+            Data::Empty => None,
+            Data::String(s) if s.trim().is_empty() => None,
+            Data::String(s) => Some(Ok(s.clone())),
+            Data::Int(n) => Some(Ok(n.to_string())),
+            Data::Float(n) if n.fract() == 0.0 => Some(Ok(format!("{n:.0}"))),
+            other => Some(Err(other.to_string())),
+        };
+        let lei = match value {
+            None => None,
+            Some(Ok(value)) => match crate::parse_loose(&value) {
+                Ok(lei) => Some(lei),
+                Err(err) => {
+                    report.errors.push(CellError {
+                        row: row_number,
+                        value,
+                        problem: CellProblem::Invalid(err),
+                    });
+                    None
+                }
+            },
+            Some(Err(value)) => {
+                report.errors.push(CellError {
+                    row: row_number,
+                    value,
+                    problem: CellProblem::NotText,
+                });
+                None
+            }
+        };
+        let mut cleaned_row = row.to_vec();
+        if let (Some(lei), Some(cell)) = (lei, cleaned_row.get_mut(index)) {
+            *cell = Data::String(lei.to_string());
+        }
+        report.cleaned.push(lei);
+        report.rows.push(cleaned_row);
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_column_with_excel_quirks() {
+        let mut range = Range::new((0, 0), (5, 1));
+        range.set_value((0, 0), Data::String("Name".to_owned()));
+        range.set_value((0, 1), Data::String(" LEI ".to_owned()));
+        range.set_value(
+            (1, 1),
+            Data::String("YZ83GD8L7GG84979J516\u{a0} ".to_owned()),
+        );
+        range.set_value((2, 1), Data::String("yz83gd8l7gg84979j516".to_owned()));
+        range.set_value((3, 1), Data::Float(95980020140005346817.0));
+        range.set_value((4, 1), Data::Bool(true));
+        // Row 6 (index 5) is left empty.
+
+        let report = validate_range_column(&range, "LEI").unwrap();
+        assert_eq!(report.header_row, 1);
+        assert_eq!(report.cleaned.len(), 5);
+        assert_eq!(report.valid_count(), 2);
+        assert_eq!(
+            report.cleaned[0].unwrap().to_string(),
+            "YZ83GD8L7GG84979J516"
+        );
+        assert_eq!(report.cleaned[4], None);
+        assert_eq!(report.header[1], Data::String(" LEI ".to_owned()));
+        assert_eq!(report.rows.len(), 5);
+        assert_eq!(
+            report.rows[1],
+            vec![Data::Empty, Data::String("YZ83GD8L7GG84979J516".to_owned())]
+        );
+        assert_eq!(report.rows[3], vec![Data::Empty, Data::Bool(true)]);
+
+        let located: Vec<u32> = report.errors.iter().map(|e| e.row).collect();
+        assert_eq!(located, vec![4, 5]);
+        assert!(matches!(report.errors[0].problem, CellProblem::Invalid(_)));
+        assert_eq!(report.errors[1].problem, CellProblem::NotText);
+    }
+
+    #[test]
+    fn missing_column_is_an_error() {
+        let mut range = Range::new((0, 0), (0, 0));
+        range.set_value((0, 0), Data::String("Name".to_owned()));
+        assert!(matches!(
+            validate_range_column(&range, "LEI"),
+            Err(ExcelError::ColumnNotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,78 @@
+#![warn(missing_docs)]
+//! # lei::garde
+//!
+//! Custom rules for the [garde](https://crates.io/crates/garde) derive, for request DTOs that
+//! keep LEIs as strings. `is_lei` validates with `parse()`, and `is_lei_loose` with
+//! `parse_loose()` for input typed by people, which may have surrounding whitespace or lowercase
+//! letters. Both work on any field whose type dereferences to `str`, with any context type:
+//!
+//! ```
+//! use garde::Validate;
+//!
+//! #[derive(Validate)]
+//! struct Registration {
+//!     #[garde(custom(lei::garde::is_lei))]
+//!     lei: String,
+//!     #[garde(custom(lei::garde::is_lei_loose))]
+//!     parent_lei: String,
+//! }
+//!
+//! let ok = Registration {
+//!     lei: "YZ83GD8L7GG84979J516".to_owned(),
+//!     parent_lei: " 549300iykiliu506ka05".to_owned(),
+//! };
+//! assert!(ok.validate().is_ok());
+//!
+//! let typo = Registration {
+//!     lei: "YZ83GD8L7GG84979J517".to_owned(),
+//!     parent_lei: "549300IYKILIU506KA05".to_owned(),
+//! };
+//! let report = typo.validate().unwrap_err();
+//! let (path, error) = report.iter().next().unwrap();
+//! assert_eq!(path.to_string(), "lei");
+//! assert_eq!(
+//!     error.message(),
+//!     "not a valid LEI: incorrect check digits \"17\" when expecting \"16\""
+//! );
+//! ```
+//!
+//! These check a value that stays a string; to keep the parsed `LEI` instead, deserialize into an
+//! `LEI` field with the `serde` feature.
+
+/// Validate `value` as an LEI with `parse()`.
+pub fn is_lei<T: AsRef<str> + ?Sized, C: ?Sized>(value: &T, _context: &C) -> ::garde::Result {
+    crate::parse(value.as_ref()).map(|_| ()).map_err(error)
+}
+
+/// Validate `value` as an LEI with `parse_loose()`.
+pub fn is_lei_loose<T: AsRef<str> + ?Sized, C: ?Sized>(value: &T, _context: &C) -> ::garde::Result {
+    crate::parse_loose(value.as_ref())
+        .map(|_| ())
+        .map_err(error)
+}
+
+fn error(err: crate::LEIError) -> ::garde::Error {
+    ::garde::Error::new(format!("not a valid LEI: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strict() {
+        assert!(is_lei("YZ83GD8L7GG84979J516", &()).is_ok());
+        assert!(is_lei(&String::from("549300IYKILIU506KA05"), &()).is_ok());
+        assert_eq!(
+            is_lei("YZ83GD8L7GG84979J517", &()).unwrap_err().message(),
+            "not a valid LEI: incorrect check digits \"17\" when expecting \"16\""
+        );
+        assert!(is_lei(" YZ83GD8L7GG84979J516", &()).is_err());
+    }
+
+    #[test]
+    fn loose() {
+        assert!(is_lei_loose(" yz83gd8l7gg84979j516\n", &()).is_ok());
+        assert!(is_lei_loose("YZ83GD8L7GG84979J517", &()).is_err());
+    }
+}
@@ -0,0 +1,108 @@
+//! Conversions between `LEI` and the inline string types of the `compact_str` and `smartstring`
+//! crates. An LEI is 20 bytes, short enough for either type to store it without a heap
+//! allocation. Conversions into `LEI` use the strict `parse()`.
+
+use crate::{LEIError, LEI};
+
+#[cfg(feature = "compact_str")]
+mod compact {
+    use super::*;
+    use compact_str::CompactString;
+
+    impl From<LEI> for CompactString {
+        fn from(lei: LEI) -> Self {
+            CompactString::new(lei.as_str())
+        }
+    }
+
+    impl From<&LEI> for CompactString {
+        fn from(lei: &LEI) -> Self {
+            CompactString::from(*lei)
+        }
+    }
+
+    impl TryFrom<CompactString> for LEI {
+        type Error = LEIError;
+
+        fn try_from(value: CompactString) -> Result<Self, Self::Error> {
+            crate::parse(value.as_str())
+        }
+    }
+
+    impl TryFrom<&CompactString> for LEI {
+        type Error = LEIError;
+
+        fn try_from(value: &CompactString) -> Result<Self, Self::Error> {
+            crate::parse(value.as_str())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn compact_str_round_trip() {
+            let lei = crate::parse("YZ83GD8L7GG84979J516").unwrap();
+            let s = CompactString::from(lei);
+            assert!(!s.is_heap_allocated());
+            assert_eq!(s, "YZ83GD8L7GG84979J516");
+            assert_eq!(LEI::try_from(&s), Ok(lei));
+            assert_eq!(LEI::try_from(s), Ok(lei));
+            assert!(LEI::try_from(CompactString::new("YZ83GD8L7GG84979J517")).is_err());
+        }
+    }
+}
+
+#[cfg(feature = "smartstring")]
+mod smart {
+    use super::*;
+    use smartstring::{SmartString, SmartStringMode};
+
+    impl<Mode: SmartStringMode> From<LEI> for SmartString<Mode> {
+        fn from(lei: LEI) -> Self {
+            SmartString::from(lei.as_str())
+        }
+    }
+
+    impl<Mode: SmartStringMode> From<&LEI> for SmartString<Mode> {
+        fn from(lei: &LEI) -> Self {
+            SmartString::from(*lei)
+        }
+    }
+
+    impl<Mode: SmartStringMode> TryFrom<SmartString<Mode>> for LEI {
+        type Error = LEIError;
+
+        fn try_from(value: SmartString<Mode>) -> Result<Self, Self::Error> {
+            crate::parse(value.as_str())
+        }
+    }
+
+    impl<Mode: SmartStringMode> TryFrom<&SmartString<Mode>> for LEI {
+        type Error = LEIError;
+
+        fn try_from(value: &SmartString<Mode>) -> Result<Self, Self::Error> {
+            crate::parse(value.as_str())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn smartstring_round_trip() {
+            let lei = crate::parse("YZ83GD8L7GG84979J516").unwrap();
+            let s = smartstring::alias::String::from(lei);
+            assert!(s.is_inline());
+            assert_eq!(s, "YZ83GD8L7GG84979J516");
+            assert_eq!(LEI::try_from(&s), Ok(lei));
+            assert_eq!(LEI::try_from(s), Ok(lei));
+            assert!(LEI::try_from(SmartString::<smartstring::LazyCompact>::from(
+                "yz83gd8l7gg84979j516"
+            ))
+            .is_err());
+        }
+    }
+}
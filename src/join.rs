@@ -0,0 +1,194 @@
+#![warn(missing_docs)]
+//! # lei::join
+//!
+//! Joining two datasets on their LEI columns. Keys on both sides are normalized and validated
+//! with `parse_loose()` before matching, so differences in case or surrounding whitespace do not
+//! cause rows to go unmatched, and keys that are not valid LEIs are reported separately instead
+//! of silently dropped.
+//!
+//! ```
+//! use lei::join::join;
+//!
+//! let trades = vec![("T1", "yz83gd8l7gg84979j516 "), ("T2", "549300IYKILIU506KA05"), ("T3", "n/a")];
+//! let entities = vec![("YZ83GD8L7GG84979J516", "Acme"), ("JJKC32MCHWDI71265Z06", "Globex")];
+//!
+//! let report = join(trades, entities, |t| t.1, |e| e.0);
+//!
+//! let matched: Vec<(&str, &str)> = report
+//!     .matched()
+//!     .flat_map(|j| j.left.iter().flat_map(move |t| j.right.iter().map(move |e| (t.0, e.1))))
+//!     .collect();
+//! assert_eq!(matched, vec![("T1", "Acme")]);
+//! assert_eq!(report.left_unmatched().count(), 1);
+//! assert_eq!(report.right_unmatched().count(), 1);
+//! assert_eq!(report.left_invalid[0].value, "n/a");
+//! ```
+
+use std::collections::BTreeMap;
+
+use crate::{LEIError, LEI};
+
+/// All the records from both sides that share one LEI. Either side may be empty.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Joined<A, B> {
+    /// The normalized LEI.
+    pub lei: LEI,
+    /// The left records with this LEI, in input order.
+    pub left: Vec<A>,
+    /// The right records with this LEI, in input order.
+    pub right: Vec<B>,
+}
+
+/// A record whose key is not a valid LEI.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidKey<T> {
+    /// The key as found in the record.
+    pub value: String,
+    /// Why it is not a valid LEI.
+    pub error: LEIError,
+    /// The record.
+    pub record: T,
+}
+
+/// The result of `join()`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JoinReport<A, B> {
+    /// Every distinct valid LEI found on either side, in LEI order.
+    pub joined: Vec<Joined<A, B>>,
+    /// Left records whose key is not a valid LEI, in input order.
+    pub left_invalid: Vec<InvalidKey<A>>,
+    /// Right records whose key is not a valid LEI, in input order.
+    pub right_invalid: Vec<InvalidKey<B>>,
+}
+
+impl<A, B> JoinReport<A, B> {
+    /// The LEIs found on both sides.
+    pub fn matched(&self) -> impl Iterator<Item = &Joined<A, B>> {
+        self.joined
+            .iter()
+            .filter(|j| !j.left.is_empty() && !j.right.is_empty())
+    }
+
+    /// The LEIs found only on the left side.
+    pub fn left_unmatched(&self) -> impl Iterator<Item = &Joined<A, B>> {
+        self.joined.iter().filter(|j| j.right.is_empty())
+    }
+
+    /// The LEIs found only on the right side.
+    pub fn right_unmatched(&self) -> impl Iterator<Item = &Joined<A, B>> {
+        self.joined.iter().filter(|j| j.left.is_empty())
+    }
+}
+
+/// Join `left` and `right` on the LEIs extracted from each record by `left_key` and `right_key`.
+pub fn join<L, R, KL, KR>(
+    left: L,
+    right: R,
+    mut left_key: KL,
+    mut right_key: KR,
+) -> JoinReport<L::Item, R::Item>
+where
+    L: IntoIterator,
+    R: IntoIterator,
+    KL: FnMut(&L::Item) -> &str,
+    KR: FnMut(&R::Item) -> &str,
+{
+    let mut groups: BTreeMap<LEI, Joined<L::Item, R::Item>> = BTreeMap::new();
+    let mut left_invalid = Vec::new();
+    let mut right_invalid = Vec::new();
+
+    for record in left {
+        match crate::parse_loose(left_key(&record)) {
+            Ok(lei) => groups
+                .entry(lei)
+                .or_insert_with(|| Joined {
+                    lei,
+                    left: Vec::new(),
+                    right: Vec::new(),
+                })
+                .left
+                .push(record),
+            Err(error) => left_invalid.push(InvalidKey {
+                value: left_key(&record).to_owned(),
+                error,
+                record,
+            }),
+        }
+    }
+
+    for record in right {
+        match crate::parse_loose(right_key(&record)) {
+            Ok(lei) => groups
+                .entry(lei)
+                .or_insert_with(|| Joined {
+                    lei,
+                    left: Vec::new(),
+                    right: Vec::new(),
+                })
+                .right
+                .push(record),
+            Err(error) => right_invalid.push(InvalidKey {
+                value: right_key(&record).to_owned(),
+                error,
+                record,
+            }),
+        }
+    }
+
+    JoinReport {
+        joined: groups.into_values().collect(),
+        left_invalid,
+        right_invalid,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn joins_on_normalized_keys() {
+        let left = vec![
+            ("a", " yz83gd8l7gg84979j516"),
+            ("b", "YZ83GD8L7GG84979J516"),
+            ("c", "549300IYKILIU506KA05"),
+            ("d", "549300IYKILIU506KA06"),
+        ];
+        let right = vec![
+            (1, "YZ83GD8L7GG84979J516"),
+            (2, "JJKC32MCHWDI71265Z06"),
+            (3, ""),
+        ];
+
+        let report = join(left, right, |l| l.1, |r| r.1);
+
+        let matched: Vec<&Joined<_, _>> = report.matched().collect();
+        assert_eq!(matched.len(), 1);
+        assert_eq!(matched[0].lei.to_string(), "YZ83GD8L7GG84979J516");
+        assert_eq!(
+            matched[0].left.iter().map(|l| l.0).collect::<Vec<_>>(),
+            vec!["a", "b"]
+        );
+        assert_eq!(matched[0].right.len(), 1);
+
+        let left_only: Vec<String> = report.left_unmatched().map(|j| j.lei.to_string()).collect();
+        assert_eq!(left_only, vec!["549300IYKILIU506KA05"]);
+        let right_only: Vec<String> = report
+            .right_unmatched()
+            .map(|j| j.lei.to_string())
+            .collect();
+        assert_eq!(right_only, vec!["JJKC32MCHWDI71265Z06"]);
+
+        assert_eq!(report.left_invalid.len(), 1);
+        assert_eq!(report.left_invalid[0].record.0, "d");
+        assert!(matches!(
+            report.left_invalid[0].error,
+            LEIError::IncorrectCheckDigits { .. }
+        ));
+        assert_eq!(report.right_invalid.len(), 1);
+        assert_eq!(
+            report.right_invalid[0].error,
+            LEIError::InvalidLength { was: 0 }
+        );
+    }
+}
@@ -0,0 +1,20 @@
+//! A minimal JSON string writer for the hand-built response bodies of the web integrations, which
+//! don't otherwise need a JSON library.
+
+use std::fmt::Write;
+
+/// Append `s` to `out` as a quoted, escaped JSON string.
+pub(crate) fn push_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
@@ -0,0 +1,137 @@
+#![warn(missing_docs)]
+//! # lei::key
+//!
+//! A hash-map key for LEIs that hashes a precomputed `u64` instead of the 20 bytes of the code,
+//! for hot lookup and join paths.
+//!
+//! `LeiKey` computes its hash once, when it is created, and its `Hash` implementation feeds
+//! just that one `u64` to the hasher. With the `nohash-hasher` feature it also implements
+//! `nohash_hasher::IsEnabled`, so it can be used with `BuildNoHashHasher`, which passes the
+//! value through unchanged, making each lookup's hashing step nearly free.
+//!
+//! The hash is unkeyed: it is the same in every process, and anyone can compute it. A map keyed
+//! by `LeiKey` and filled from untrusted input is therefore open to collision flooding (HashDoS),
+//! especially with `BuildNoHashHasher`. Keep the default `RandomState` hasher, or plain `LEI`
+//! keys, for such maps.
+//!
+//! ```
+//! use std::collections::HashMap;
+//! use lei::key::LeiKey;
+//!
+//! let lei = lei::parse("YZ83GD8L7GG84979J516").unwrap();
+//! let mut map = HashMap::new();
+//! map.insert(LeiKey::new(lei), "Acme");
+//! assert_eq!(map.get(&LeiKey::new(lei)), Some(&"Acme"));
+//! assert_eq!(LeiKey::new(lei).lei(), lei);
+//! ```
+
+use std::hash::{Hash, Hasher};
+
+use crate::LEI;
+
+/// An `LEI` with a precomputed, unkeyed hash, for use as a hash-map key. See the module
+/// documentation for why maps built from untrusted input should not rely on it.
+#[derive(Clone, Copy, Debug)]
+pub struct LeiKey {
+    lei: LEI,
+    hash: u64,
+}
+
+impl LeiKey {
+    /// Create a key, computing its hash.
+    pub fn new(lei: LEI) -> LeiKey {
+        // Every LEI packs losslessly into a u128. The two halves are folded so that the LOU ID
+        // prefix and the entity-specific suffix both contribute, then multiplied by a large odd
+        // constant, which is a bijection on u64. Std's hashbrown takes the bucket index from the
+        // low bits, which the multiply leaves depending on the low bits of the folded value, and
+        // the control bytes from the top 7 bits, which the multiply makes depend on all of them.
+        let packed = lei.to_u128();
+        let folded = (packed >> 64) as u64 ^ packed as u64;
+        LeiKey {
+            lei,
+            hash: folded.wrapping_mul(0x9E37_79B9_7F4A_7C15),
+        }
+    }
+
+    /// The LEI.
+    pub fn lei(&self) -> LEI {
+        self.lei
+    }
+
+    /// The precomputed hash.
+    pub fn hash_value(&self) -> u64 {
+        self.hash
+    }
+}
+
+impl From<LEI> for LeiKey {
+    fn from(lei: LEI) -> Self {
+        LeiKey::new(lei)
+    }
+}
+
+impl From<LeiKey> for LEI {
+    fn from(key: LeiKey) -> Self {
+        key.lei
+    }
+}
+
+impl PartialEq for LeiKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.hash == other.hash && self.lei == other.lei
+    }
+}
+
+impl Eq for LeiKey {}
+
+impl Hash for LeiKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.hash);
+    }
+}
+
+#[cfg(feature = "nohash-hasher")]
+impl nohash_hasher::IsEnabled for LeiKey {}
+
+/// A `HashMap` keyed by `LeiKey` that uses each key's precomputed hash directly.
+#[cfg(feature = "nohash-hasher")]
+pub type LeiKeyMap<V> =
+    std::collections::HashMap<LeiKey, V, nohash_hasher::BuildNoHashHasher<LeiKey>>;
+
+/// A `HashSet` of `LeiKey` that uses each key's precomputed hash directly.
+#[cfg(feature = "nohash-hasher")]
+pub type LeiKeySet = std::collections::HashSet<LeiKey, nohash_hasher::BuildNoHashHasher<LeiKey>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_are_consistent_and_distinct() {
+        let a = crate::parse("YZ83GD8L7GG84979J516").unwrap();
+        let b = crate::parse("549300IYKILIU506KA05").unwrap();
+        let c = crate::parse("549300RIPPWJB5Z0FK07").unwrap();
+
+        assert_eq!(LeiKey::new(a), LeiKey::new(a));
+        assert_eq!(LeiKey::new(a).hash_value(), LeiKey::new(a).hash_value());
+        assert_ne!(LeiKey::new(a), LeiKey::new(b));
+        assert_ne!(LeiKey::new(b).hash_value(), LeiKey::new(c).hash_value());
+        assert_eq!(LEI::from(LeiKey::from(a)), a);
+    }
+
+    #[cfg(feature = "nohash-hasher")]
+    #[test]
+    fn nohash_map() {
+        let a = crate::parse("YZ83GD8L7GG84979J516").unwrap();
+        let b = crate::parse("549300IYKILIU506KA05").unwrap();
+
+        let mut map = LeiKeyMap::default();
+        map.insert(LeiKey::new(a), 1);
+        map.insert(LeiKey::new(b), 2);
+        assert_eq!(map.get(&LeiKey::new(a)), Some(&1));
+        assert_eq!(map.get(&LeiKey::new(b)), Some(&2));
+
+        let set: LeiKeySet = [a, b, a].into_iter().map(LeiKey::new).collect();
+        assert_eq!(set.len(), 2);
+    }
+}
@@ -0,0 +1,200 @@
+#![warn(missing_docs)]
+//! # lei::lei_str
+//!
+//! A borrowed, validated LEI: `LeiStr` is to `LEI` as `Path` is to `PathBuf`.
+//!
+//! A `&LeiStr` is a view of a 20-character `str` that has been confirmed to be a valid LEI, so
+//! large inputs such as memory-mapped golden copy files can be validated in place without
+//! copying each code into an owned `LEI`.
+//!
+//! ```
+//! use lei::{LeiStr, LEI};
+//!
+//! let line = "549300IYKILIU506KA05,Example Corp";
+//! let lei: &LeiStr = LeiStr::new(&line[..20]).unwrap();
+//! assert_eq!(lei.lou_id(), "5493");
+//!
+//! let owned: LEI = lei.to_owned();
+//! assert_eq!(owned.as_lei_str(), lei);
+//! ```
+
+use std::borrow::Borrow;
+use std::fmt;
+use std::ops::Deref;
+
+use crate::{LEIError, Part, LEI};
+
+/// A borrowed LEI in confirmed valid format. Only ever seen behind a reference, as `&LeiStr`.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[repr(transparent)]
+pub struct LeiStr(str);
+
+impl LeiStr {
+    /// Validate `value` as an LEI, with the same strict rules as `parse()`, and return it as a
+    /// `&LeiStr` borrowing the same memory.
+    pub fn new(value: &str) -> Result<&LeiStr, LEIError> {
+        crate::parse_bytes_unrecorded(value.as_bytes())?;
+        Ok(LeiStr::from_str_unchecked(value))
+    }
+
+    /// The caller must ensure `value` is a valid LEI.
+    pub(crate) fn from_str_unchecked(value: &str) -> &LeiStr {
+        unsafe { &*(value as *const str as *const LeiStr) } // This is safe because LeiStr is a transparent wrapper of str
+    }
+
+    /// Return the full 20-character LEI as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Return just the _LOU ID_ portion of the LEI.
+    pub fn lou_id(&self) -> &str {
+        &self.0[LEI::LOU_ID_RANGE]
+    }
+
+    /// Return just the _Entity ID_ portion of the LEI.
+    pub fn entity_id(&self) -> &str {
+        &self.0[LEI::ENTITY_ID_RANGE]
+    }
+
+    /// Return just the _Payload_ portion of the LEI (everything except the _Check Digits_).
+    pub fn payload(&self) -> &str {
+        &self.0[LEI::PAYLOAD_RANGE]
+    }
+
+    /// Return just the _Check Digits_ portion of the LEI.
+    pub fn check_digits(&self) -> &str {
+        &self.0[LEI::CHECK_DIGITS_RANGE]
+    }
+
+    /// Return the given part of the LEI.
+    pub fn get(&self, part: Part) -> &str {
+        &self.0[part.range()]
+    }
+}
+
+impl fmt::Display for LeiStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&self.0)
+    }
+}
+
+impl fmt::Debug for LeiStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "LeiStr({})", &self.0)
+    }
+}
+
+impl AsRef<str> for LeiStr {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for LeiStr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<'a> TryFrom<&'a str> for &'a LeiStr {
+    type Error = LEIError;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        LeiStr::new(value)
+    }
+}
+
+impl ToOwned for LeiStr {
+    type Owned = LEI;
+
+    fn to_owned(&self) -> LEI {
+        let mut bb = [0u8; 20];
+        bb.copy_from_slice(self.0.as_bytes());
+        LEI(bb)
+    }
+}
+
+/// Allows looking up `LEI` values in maps and sets by `&LeiStr`.
+impl Borrow<LeiStr> for LEI {
+    fn borrow(&self) -> &LeiStr {
+        self.as_lei_str()
+    }
+}
+
+impl AsRef<LeiStr> for LEI {
+    fn as_ref(&self) -> &LeiStr {
+        self.as_lei_str()
+    }
+}
+
+impl PartialEq<LEI> for LeiStr {
+    fn eq(&self, other: &LEI) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl PartialEq<LeiStr> for LEI {
+    fn eq(&self, other: &LeiStr) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl PartialEq<str> for LeiStr {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl From<&LeiStr> for LEI {
+    fn from(value: &LeiStr) -> LEI {
+        value.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::borrow::Cow;
+    use std::collections::HashSet;
+
+    #[test]
+    fn validates_without_copying() {
+        let text = String::from("YZ83GD8L7GG84979J516");
+        let lei = LeiStr::new(&text).unwrap();
+        assert_eq!(lei.as_str().as_ptr(), text.as_ptr());
+        assert_eq!(lei.lou_id(), "YZ83");
+        assert_eq!(lei.entity_id(), "GD8L7GG84979J5");
+        assert_eq!(lei.payload(), "YZ83GD8L7GG84979J5");
+        assert_eq!(lei.check_digits(), "16");
+        assert_eq!(format!("{lei:?}"), "LeiStr(YZ83GD8L7GG84979J516)");
+        assert_eq!(lei.to_string(), text);
+    }
+
+    #[test]
+    fn rejects_invalid() {
+        assert_eq!(
+            LeiStr::new("YZ83GD8L7GG84979J5"),
+            Err(LEIError::InvalidLength { was: 18 })
+        );
+        assert!(LeiStr::new("yz83gd8l7gg84979j516").is_err());
+        assert!(<&LeiStr>::try_from("YZ83GD8L7GG84979J517").is_err());
+    }
+
+    #[test]
+    fn owned_round_trip() {
+        let lei = crate::parse("549300IYKILIU506KA05").unwrap();
+        let borrowed = lei.as_lei_str();
+        assert_eq!(borrowed.to_owned(), lei);
+        assert_eq!(*borrowed, lei);
+        assert_eq!(LEI::from(borrowed), lei);
+
+        let cow: Cow<LeiStr> = Cow::Borrowed(borrowed);
+        assert_eq!(cow.into_owned(), lei);
+
+        let set: HashSet<LEI> = [lei].into_iter().collect();
+        assert!(set.contains(LeiStr::new("549300IYKILIU506KA05").unwrap()));
+    }
+}
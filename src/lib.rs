@@ -30,16 +30,143 @@
 //!
 //! * [ISO/IEC 7064](https://crates.io/crates/iso_iec_7064): Check character systems (ISO/IEC 7064:2003)
 
+use std::borrow::Borrow;
+use std::cmp::Ordering;
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Deref, Range};
 use std::str::from_utf8_unchecked;
 use std::str::FromStr;
 
 use iso_iec_7064::{System, MOD_97_10};
 
+pub mod builder;
+pub use builder::LEIBuilder;
+
+pub mod check_digits;
+pub use check_digits::CheckDigits;
+
+pub mod display;
+
+pub mod entity_id;
+pub use entity_id::EntityId;
+
 pub mod error;
-pub use error::LEIError;
+pub use error::{LEIError, LEIErrorKind};
+
+pub mod lei_str;
+pub use lei_str::LeiStr;
+
+pub mod lou_id;
+pub use lou_id::LouId;
+
+pub mod options;
+pub use options::ParseOptions;
+
+pub mod part;
+pub use part::Part;
+
+pub mod report;
+pub use report::ValidationReport;
+
+pub mod unchecked;
+pub use unchecked::UncheckedLei;
+
+pub mod key;
+pub mod ordering;
+pub mod test_vectors;
+
+#[cfg(feature = "actix-web")]
+pub mod actix;
+#[cfg(feature = "arrow")]
+pub mod arrow;
+#[cfg(feature = "avro")]
+pub mod avro;
+#[cfg(feature = "axum")]
+pub mod axum;
+#[cfg(feature = "borsh")]
+pub mod borsh;
+#[cfg(feature = "bson")]
+pub mod bson;
+#[cfg(feature = "capi")]
+pub mod capi;
+#[cfg(feature = "clap")]
+pub mod clap;
+#[cfg(feature = "csv")]
+pub mod csv;
+#[cfg(feature = "diesel")]
+pub mod diesel;
+#[cfg(feature = "calamine")]
+pub mod excel;
+#[cfg(feature = "garde")]
+pub mod garde;
+#[cfg(feature = "join")]
+pub mod join;
+#[cfg(feature = "miette")]
+pub mod miette;
+#[cfg(feature = "mutate")]
+pub mod mutate;
+#[cfg(feature = "names")]
+pub mod names;
+#[cfg(feature = "near-duplicates")]
+pub mod near_duplicates;
+#[cfg(feature = "nom")]
+pub mod nom;
+#[cfg(feature = "parquet")]
+pub mod parquet;
+#[cfg(feature = "polars")]
+pub mod polars;
+#[cfg(feature = "postgres-types")]
+pub mod postgres;
+#[cfg(feature = "prost")]
+pub mod prost;
+#[cfg(feature = "qr")]
+pub mod qr;
+#[cfg(feature = "redis")]
+pub mod redis;
+#[cfg(feature = "regex")]
+pub mod regex;
+#[cfg(feature = "registry")]
+pub mod registry;
+#[cfg(feature = "regulatory")]
+pub mod regulatory;
+#[cfg(feature = "rkyv")]
+pub mod rkyv;
+#[cfg(feature = "rules")]
+pub mod rules;
+#[cfg(feature = "rusqlite")]
+pub mod rusqlite;
+#[cfg(feature = "schemars")]
+pub mod schemars;
+#[cfg(feature = "sea-orm")]
+pub mod sea_orm;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "sqlx")]
+pub mod sqlx;
+#[cfg(feature = "stats")]
+pub mod stats;
+#[cfg(feature = "tower")]
+pub mod tower;
+#[cfg(feature = "utoipa")]
+pub mod utoipa;
+#[cfg(feature = "validator")]
+pub mod validator;
+#[cfg(feature = "wasm-bindgen")]
+pub mod wasm;
+#[cfg(feature = "winnow")]
+pub mod winnow;
+#[cfg(feature = "xbrl")]
+pub mod xbrl;
 
 mod digits;
+mod typos;
+
+#[cfg(any(feature = "compact_str", feature = "smartstring"))]
+mod inline_strings;
+
+#[cfg(any(feature = "actix-web", feature = "axum", feature = "tower"))]
+mod json;
 
 use digits::DigitsIterator;
 
@@ -107,21 +234,156 @@ fn validate_check_digits_format(cd: &[u8]) -> Result<(), LEIError> {
     Ok(())
 }
 
+/// Compute the _Check Digits_ for the first 18 bytes of `b`, with the same result as
+/// `compute_check_digits()` but in a form usable in `const` contexts. This panics on an illegal
+/// character.
+const fn compute_check_digits_const(b: &[u8]) -> [u8; 2] {
+    let mut r: u32 = 0;
+    let mut i = 0;
+    while i < 18 {
+        r = match b[i] {
+            d @ b'0'..=b'9' => (r * 10 + (d - b'0') as u32) % 97,
+            c @ b'A'..=b'Z' => (r * 100 + (c - b'A' + 10) as u32) % 97,
+            _ => panic!("compute_check_digits_const() called on an invalid payload"),
+        };
+        i += 1;
+    }
+    let sum = (98 - (r * 100) % 97) % 97;
+    [b'0' + (sum / 10) as u8, b'0' + (sum % 10) as u8]
+}
+
+/// Map _Check Digits_ computed by `compute_check_digits()` into the range 02 to 98 that ISO/IEC
+/// 7064 MOD 97-10 assigns: "00" becomes "97" and "01" becomes "98". Both forms pass the same
+/// MOD 97-10 check, but The Standard reserves "00" and "01".
+const fn iso_check_digits(computed: [u8; 2]) -> [u8; 2] {
+    match computed {
+        [b'0', b'0'] => *b"97",
+        [b'0', b'1'] => *b"98",
+        other => other,
+    }
+}
+
+/// Whether `check_digits` are correct for a _Payload_ whose computed _Check Digits_ are
+/// `computed`, either exactly or in the ISO 7064 form of `iso_check_digits()`.
+const fn check_digits_match(check_digits: &[u8], computed: [u8; 2]) -> bool {
+    let iso = iso_check_digits(computed);
+    (check_digits[0] == computed[0] && check_digits[1] == computed[1])
+        || (check_digits[0] == iso[0] && check_digits[1] == iso[1])
+}
+
+/// Report the expected _Check Digits_ of `err` in the ISO 7064 form, for the strict ISO mode.
+fn with_iso_expected(err: LEIError) -> LEIError {
+    match err {
+        LEIError::IncorrectCheckDigits { was, expected } => LEIError::IncorrectCheckDigits {
+            was,
+            expected: CheckDigits(iso_check_digits(expected.0)),
+        },
+        other => other,
+    }
+}
+
+/// The value of an uppercase ASCII alphanumeric character in base 36.
+fn base36(b: u8) -> u128 {
+    match b {
+        b'0'..=b'9' => (b - b'0') as u128,
+        _ => (b - b'A' + 10) as u128,
+    }
+}
+
+/// Find the first reason `b` is not a valid LEI, if any, in a form usable in `const` contexts.
+const fn const_problem(b: &[u8]) -> Option<&'static str> {
+    if b.len() != 20 {
+        return Some("an LEI must be exactly 20 bytes");
+    }
+
+    let mut i = 0;
+    while i < 18 {
+        if !(b[i].is_ascii_digit() || b[i].is_ascii_uppercase()) {
+            return Some("the LOU ID and Entity ID must be uppercase ASCII alphanumerics");
+        }
+        i += 1;
+    }
+
+    if !(b[18].is_ascii_digit() && b[19].is_ascii_digit()) {
+        return Some("the Check Digits must be ASCII decimal digits");
+    }
+
+    if !check_digits_match(&[b[18], b[19]], compute_check_digits_const(b)) {
+        return Some("incorrect Check Digits");
+    }
+
+    None
+}
+
 /// Parse a string to a valid LEI or an error message, requiring the string to already be only
 /// uppercase alphanumerics with no leading or trailing whitespace in addition to being the
-/// right length and format.
+/// right length and format. Where the computed _Check Digits_ are "00" or "01", the "97" or "98"
+/// that ISO/IEC 7064 MOD 97-10 assigns instead are accepted too, as both pass its check.
+///
+/// Equivalent to `LEI::parse()`.
 pub fn parse(value: &str) -> Result<LEI, LEIError> {
-    let v: String = value.into();
+    LEI::parse(value)
+}
+
+/// Parse a string to a valid LEI or an error, with the same rules as `parse()` plus the rule
+/// of section 5.1 of The Standard that "00", "01" and "99" are not valid _Check Digits_. Such
+/// LEIs pass the MOD 97-10 check, and some have been issued, so `parse()` accepts them.
+///
+/// Equivalent to `LEI::parse_strict_iso()`.
+pub fn parse_strict_iso(value: &str) -> Result<LEI, LEIError> {
+    LEI::parse_strict_iso(value)
+}
+
+/// Parse a string to a valid LEI or an error, with the rules chosen by `options`.
+///
+/// Equivalent to `LEI::parse_with()`.
+pub fn parse_with(value: &str, options: &ParseOptions) -> Result<LEI, LEIError> {
+    LEI::parse_with(value, options)
+}
 
-    if v.len() != 20 {
-        return Err(LEIError::InvalidLength { was: v.len() });
+/// Emit metrics for one call to `parse()`, `parse_bytes()` or `parse_with()`: `lei_parse_total`
+/// counts every call and `lei_parse_failures_total` counts failures, labeled with the error
+/// `kind`.
+#[cfg(feature = "metrics")]
+fn record_parse(result: &Result<LEI, LEIError>) {
+    metrics::counter!("lei_parse_total").increment(1);
+    if let Err(err) = result {
+        metrics::counter!("lei_parse_failures_total", "kind" => err.kind().name()).increment(1);
     }
+}
 
+fn parse_unrecorded(value: &str) -> Result<LEI, LEIError> {
     // We make the preliminary assumption that the string is pure ASCII, so we work with the
     // underlying bytes. If there is Unicode in the string, the bytes will be outside the
     // allowed range and format validations will fail.
 
-    let b = v.as_bytes();
+    parse_bytes_unrecorded(value.as_bytes())
+}
+
+/// Parse a byte slice to a valid LEI or an error, with the same rules as `parse()`. This avoids
+/// the UTF-8 check of converting binary input (such as fixed-width records) to `&str` first;
+/// any non-ASCII bytes simply fail format validation.
+///
+/// Equivalent to `LEI::parse_bytes()`.
+pub fn parse_bytes(value: &[u8]) -> Result<LEI, LEIError> {
+    LEI::parse_bytes(value)
+}
+
+fn parse_with_unrecorded(value: &str, options: &ParseOptions) -> Result<LEI, LEIError> {
+    let result = parse_bytes_unrecorded(&options.normalize(value)?);
+    let lei = if options.reject_reserved_check_digits {
+        result.map_err(with_iso_expected)?
+    } else {
+        result?
+    };
+    options.check_reserved(lei.check_digits_typed())?;
+    Ok(lei)
+}
+
+fn parse_bytes_unrecorded(b: &[u8]) -> Result<LEI, LEIError> {
+    if b.len() != 20 {
+        return Err(LEIError::InvalidLength { was: b.len() });
+    }
 
     // We slice out the three fields and validate their formats.
 
@@ -141,13 +403,13 @@ pub fn parse(value: &str) -> Result<LEI, LEIError> {
 
     let computed_check_digits = compute_check_digits(payload);
 
-    let incorrect_check_digits = check_digits != computed_check_digits;
+    let incorrect_check_digits = !check_digits_match(check_digits, computed_check_digits);
     if incorrect_check_digits {
         let mut cd_copy: [u8; 2] = [0; 2];
         cd_copy.copy_from_slice(check_digits);
         return Err(LEIError::IncorrectCheckDigits {
-            was: cd_copy,
-            expected: computed_check_digits,
+            was: CheckDigits(cd_copy),
+            expected: CheckDigits(computed_check_digits),
         });
     }
 
@@ -160,105 +422,131 @@ pub fn parse(value: &str) -> Result<LEI, LEIError> {
 /// Parse a string to a valid LEI or an error, allowing the string to contain leading
 /// or trailing whitespace and/or lowercase letters as long as it is otherwise the right length
 /// and format.
+///
+/// Equivalent to `LEI::parse_loose()`.
 pub fn parse_loose(value: &str) -> Result<LEI, LEIError> {
-    let uc = value.to_ascii_uppercase();
-    let temp = uc.trim();
-    parse(temp)
+    LEI::parse_loose(value)
 }
 
 /// Build an LEI from a _Payload_ (an already-concatenated _LOU ID_ and _Entity ID_). The
-/// _Check Digits_ are automatically computed.
+/// _Check Digits_ are automatically computed, as "00" or "01" for the payloads that
+/// `build_from_payload_strict_iso()` gives "97" or "98".
+///
+/// Equivalent to `LEI::from_payload()`.
 pub fn build_from_payload(payload: &str) -> Result<LEI, LEIError> {
-    if payload.len() != 18 {
-        return Err(LEIError::InvalidPayloadLength { was: payload.len() });
-    }
-    let b = &payload.as_bytes()[0..18];
-
-    let lou_id = &b[0..4];
-    validate_lou_id_format(lou_id)?;
-
-    let entity_id = &b[4..18];
-    validate_entity_id_format(entity_id)?;
-
-    let mut bb = [0u8; 20];
-
-    bb[0..18].copy_from_slice(b);
-    let temp = compute_check_digits(b);
-    bb[18..20].copy_from_slice(&temp);
+    LEI::from_payload(payload)
+}
 
-    Ok(LEI(bb))
+/// Build an LEI from a _Payload_ with _Check Digits_ in the range 02 to 98, which
+/// `parse_strict_iso()` accepts.
+///
+/// Equivalent to `LEI::from_payload_strict_iso()`.
+pub fn build_from_payload_strict_iso(payload: &str) -> Result<LEI, LEIError> {
+    LEI::from_payload_strict_iso(payload)
 }
 
 /// Build an LEI from its parts: an _LOU ID_ and an _Entity ID_. The _Check Digits_ are
 /// automatically computed.
+///
+/// Equivalent to `LEI::from_parts()`.
 pub fn build_from_parts(lou_id: &str, entity_id: &str) -> Result<LEI, LEIError> {
-    if lou_id.len() != 4 {
-        return Err(LEIError::InvalidLouIdLength { was: lou_id.len() });
-    }
-    let lou_id: &[u8] = &lou_id.as_bytes()[0..4];
-    validate_lou_id_format(lou_id)?;
-
-    if entity_id.len() != 14 {
-        return Err(LEIError::InvalidEntityIdLength {
-            was: entity_id.len(),
-        });
-    }
-    let entity_id: &[u8] = &entity_id.as_bytes()[0..14];
-    validate_entity_id_format(entity_id)?;
-
-    let mut bb = [0u8; 20];
-
-    bb[0..4].copy_from_slice(lou_id);
-    bb[4..18].copy_from_slice(entity_id);
-    let temp = compute_check_digits(&bb[0..18]);
-    bb[18..20].copy_from_slice(&temp);
-
-    Ok(LEI(bb))
+    LEI::from_parts(lou_id, entity_id)
 }
 
 /// Test whether or not the passed string is in valid LEI format, without producing an LEI struct
 /// value.
-pub fn validate(value: &str) -> bool {
-    if value.len() != 20 {
-        return false;
-    }
-
-    // We make the preliminary assumption that the string is pure ASCII, so we work with the
-    // underlying bytes. If there is Unicode in the string, the bytes will be outside the
-    // allowed range and format validations will fail.
-
-    let b = value.as_bytes();
-
-    // We slice out the three fields and validate their formats.
+///
+/// Equivalent to `LEI::validate()`.
+pub const fn validate(value: &str) -> bool {
+    LEI::validate(value)
+}
 
-    let lou_id: &[u8] = &b[0..4];
-    if validate_lou_id_format(lou_id).is_err() {
-        return false;
-    }
+/// Test whether or not the passed bytes are a valid LEI, without producing an LEI struct value.
+///
+/// Equivalent to `LEI::validate_bytes()`.
+pub const fn validate_bytes(value: &[u8]) -> bool {
+    LEI::validate_bytes(value)
+}
 
-    let entity_id: &[u8] = &b[4..18];
-    if validate_entity_id_format(entity_id).is_err() {
-        return false;
-    }
+/// Correct the _Check Digits_ of a string that is otherwise a valid LEI.
+///
+/// Equivalent to `LEI::repair()`.
+pub fn repair(value: &str) -> Option<LEI> {
+    LEI::repair(value)
+}
 
-    let check_digits = &b[18..20];
-    if validate_check_digits_format(check_digits).is_err() {
-        return false;
-    }
+/// Correct the _Check Digits_ of a string that is otherwise a valid LEI into the range 02 to 98,
+/// which `parse_strict_iso()` accepts.
+///
+/// Equivalent to `LEI::repair_strict_iso()`.
+pub fn repair_strict_iso(value: &str) -> Option<LEI> {
+    LEI::repair_strict_iso(value)
+}
 
-    let payload = &b[0..18];
+/// Suggest valid LEIs that a mistyped string was likely meant to be.
+///
+/// Equivalent to `LEI::suggest()`.
+pub fn suggest(value: &str) -> Vec<LEI> {
+    LEI::suggest(value)
+}
 
-    let computed_check_digits = compute_check_digits(payload);
+/// A `clap` value parser for strict LEI arguments, for `#[arg(value_parser = lei::clap_parser())]`.
+///
+/// Equivalent to `clap::LeiValueParser::strict()`.
+#[cfg(feature = "clap")]
+pub fn clap_parser() -> crate::clap::LeiValueParser {
+    crate::clap::LeiValueParser::strict()
+}
 
-    if check_digits[0] != computed_check_digits[0] {
-        return false;
-    }
+/// Check a string with the same rules as `parse()`, but report every problem found rather than
+/// just the first.
+///
+/// Equivalent to `LEI::validate_report()`.
+pub fn validate_report(value: &str) -> ValidationReport {
+    LEI::validate_report(value)
+}
 
-    if check_digits[1] != computed_check_digits[1] {
-        return false;
+/// Parse every value with `parse()` in one pass, splitting the results into the valid LEIs and
+/// the invalid values paired with their errors. Both lists preserve input order.
+///
+/// ```
+/// let (valid, invalid) = lei::collect_valid(["YZ83GD8L7GG84979J516", "bogus", "549300IYKILIU506KA05"]);
+/// assert_eq!(valid.len(), 2);
+/// assert_eq!(invalid[0].0, "bogus");
+/// ```
+pub fn collect_valid<I, S>(values: I) -> (Vec<LEI>, Vec<(S, LEIError)>)
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut valid = Vec::new();
+    let mut invalid = Vec::new();
+    for value in values {
+        match parse(value.as_ref()) {
+            Ok(lei) => valid.push(lei),
+            Err(err) => invalid.push((value, err)),
+        }
     }
+    (valid, invalid)
+}
 
-    true
+/// Create an `LEI` from a string literal, checked at compile time. A literal with an invalid
+/// format or incorrect _Check Digits_ fails the build.
+///
+/// ```
+/// let lei = lei::lei!("549300IYKILIU506KA05");
+/// assert_eq!(lei.entity_id(), "00IYKILIU506KA");
+/// ```
+///
+/// ```compile_fail
+/// let lei = lei::lei!("549300IYKILIU506KA06");
+/// ```
+#[macro_export]
+macro_rules! lei {
+    ($value:literal) => {{
+        const LEI: $crate::LEI = $crate::LEI::parse_const($value);
+        LEI
+    }};
 }
 
 #[doc = include_str!("../README.md")]
@@ -273,22 +561,65 @@ pub struct ReadmeDoctests;
 /// use lei;
 /// let cannot_construct = lei::LEI([0_u8; 20]);
 /// ```
-#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Hash)]
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Copy)]
 #[repr(transparent)]
 #[allow(clippy::upper_case_acronyms)]
 pub struct LEI([u8; 20]);
 
+/// Hashes the same as the equivalent `str`, as required by the `Borrow<str>` implementation.
+///
+/// That is already a single `write()` of the 20 bytes (plus one terminating byte), not a byte at
+/// a time. Hashing the `to_u128()` form instead measured only about 20% faster per hash with the
+/// default hasher (see `benches/hash.rs`), and would break lookups by `&str` and `&LeiStr`. Hot
+/// lookup paths that can afford a different key type should use `key::LeiKey`.
+impl Hash for LEI {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl AsRef<str> for LEI {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+/// Makes every `&str` method, such as `starts_with()`, available directly on an `LEI`.
+impl Deref for LEI {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+/// Allows looking up `LEI` values in maps and sets keyed by `String` or `&str` and vice versa.
+impl Borrow<str> for LEI {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+/// The alternate form (`{:#}`) is grouped, as by `LEI::display_grouped()`.
 impl fmt::Display for LEI {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let temp = unsafe { from_utf8_unchecked(self.as_bytes()) }; // This is safe because we know it is ASCII
-        write!(f, "{temp}")
+        if f.alternate() {
+            return fmt::Display::fmt(&self.display_grouped(), f);
+        }
+        f.pad(self.as_str())
     }
 }
 
 impl fmt::Debug for LEI {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let temp = unsafe { from_utf8_unchecked(self.as_bytes()) }; // This is safe because we know it is ASCII
-        write!(f, "LEI({temp})")
+        write!(f, "LEI({})", self.as_str())
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for LEI {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "LEI({=str})", self.as_str())
     }
 }
 
@@ -300,36 +631,543 @@ impl FromStr for LEI {
     }
 }
 
+impl PartialEq<str> for LEI {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for LEI {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for LEI {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<LEI> for str {
+    fn eq(&self, other: &LEI) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl PartialEq<LEI> for &str {
+    fn eq(&self, other: &LEI) -> bool {
+        *self == other.as_str()
+    }
+}
+
+impl PartialEq<LEI> for String {
+    fn eq(&self, other: &LEI) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl From<LEI> for String {
+    fn from(lei: LEI) -> Self {
+        lei.as_str().to_owned()
+    }
+}
+
+impl From<LEI> for [u8; 20] {
+    fn from(lei: LEI) -> Self {
+        lei.0
+    }
+}
+
+/// Strict conversion, equivalent to `parse_bytes()`.
+impl TryFrom<&[u8; 20]> for LEI {
+    type Error = LEIError;
+
+    fn try_from(value: &[u8; 20]) -> Result<Self, Self::Error> {
+        parse_bytes(value)
+    }
+}
+
+/// Strict conversion, equivalent to `parse()`. Note that `FromStr` is loose.
+impl TryFrom<&str> for LEI {
+    type Error = LEIError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        parse(value)
+    }
+}
+
+/// Strict conversion, equivalent to `parse()`. The `String` is consumed and dropped; the LEI
+/// is copied out of it into a fixed-size array.
+impl TryFrom<String> for LEI {
+    type Error = LEIError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        parse(&value)
+    }
+}
+
 impl LEI {
-    /// Internal convenience function for treating the ASCII characters as a byte-array slice.
-    fn as_bytes(&self) -> &[u8] {
+    /// A regular expression matching the format of an LEI: 18 uppercase ASCII alphanumerics
+    /// followed by two ASCII decimal digits. The pattern is not anchored, so it can be embedded
+    /// in larger expressions; wrap it in `^...$` or `\b...\b` as needed. Matching the pattern
+    /// does not verify the _Check Digits_, so pass matches to `parse()` to complete validation.
+    pub const PATTERN: &'static str = "[0-9A-Z]{18}[0-9]{2}";
+
+    /// The length of an LEI in bytes (and characters).
+    pub const LENGTH: usize = 20;
+
+    /// The length of the _LOU ID_.
+    pub const LOU_ID_LENGTH: usize = 4;
+
+    /// The length of the _Entity ID_.
+    pub const ENTITY_ID_LENGTH: usize = 14;
+
+    /// The length of the _Payload_ (the _LOU ID_ followed by the _Entity ID_).
+    pub const PAYLOAD_LENGTH: usize = 18;
+
+    /// The length of the _Check Digits_.
+    pub const CHECK_DIGITS_LENGTH: usize = 2;
+
+    /// The byte range of the _LOU ID_.
+    pub const LOU_ID_RANGE: Range<usize> = 0..4;
+
+    /// The byte range of the _Entity ID_.
+    pub const ENTITY_ID_RANGE: Range<usize> = 4..18;
+
+    /// The byte range of the _Payload_.
+    pub const PAYLOAD_RANGE: Range<usize> = 0..18;
+
+    /// The byte range of the _Check Digits_.
+    pub const CHECK_DIGITS_RANGE: Range<usize> = 18..20;
+
+    /// The smallest valid LEI, for the lower bound of range queries over ordered collections.
+    /// Its _Check Digits_ "01" are accepted by `parse()` but not by `parse_strict_iso()`.
+    ///
+    /// ```
+    /// use std::collections::BTreeSet;
+    /// use lei::LEI;
+    ///
+    /// let set: BTreeSet<LEI> = [lei::parse("549300IYKILIU506KA05").unwrap()].into();
+    /// assert_eq!(set.range(LEI::MIN..=LEI::MAX).count(), 1);
+    /// assert!(LEI::MIN < LEI::MAX);
+    /// ```
+    pub const MIN: LEI = LEI::parse_const("00000000000000000001");
+
+    /// The largest valid LEI, for the upper bound of range queries over ordered collections.
+    pub const MAX: LEI = LEI::parse_const("ZZZZZZZZZZZZZZZZZZ40");
+
+    /// Parse a string to a valid LEI or an error message, requiring the string to already be
+    /// only uppercase alphanumerics with no leading or trailing whitespace in addition to being
+    /// the right length and format.
+    pub fn parse(value: &str) -> Result<LEI, LEIError> {
+        let result = parse_unrecorded(value);
+        #[cfg(feature = "metrics")]
+        record_parse(&result);
+        result
+    }
+
+    /// Parse a string to a valid LEI or an error, with the rules chosen by `options`.
+    pub fn parse_with(value: &str, options: &ParseOptions) -> Result<LEI, LEIError> {
+        let result = parse_with_unrecorded(value, options);
+        #[cfg(feature = "metrics")]
+        record_parse(&result);
+        result
+    }
+
+    /// Parse a string to a valid LEI or an error, with the same rules as `parse()` plus the rule
+    /// of section 5.1 of The Standard that "00", "01" and "99" are not valid _Check Digits_.
+    pub fn parse_strict_iso(value: &str) -> Result<LEI, LEIError> {
+        let lei = LEI::parse(value).map_err(with_iso_expected)?;
+        let check_digits = lei.check_digits_typed();
+        if check_digits.is_reserved() {
+            return Err(LEIError::ReservedCheckDigits { was: check_digits });
+        }
+        Ok(lei)
+    }
+
+    /// Parse a byte slice to a valid LEI or an error, with the same rules as `parse()`. This
+    /// avoids the UTF-8 check of converting binary input (such as fixed-width records) to `&str`
+    /// first; any non-ASCII bytes simply fail format validation.
+    pub fn parse_bytes(value: &[u8]) -> Result<LEI, LEIError> {
+        let result = parse_bytes_unrecorded(value);
+        #[cfg(feature = "metrics")]
+        record_parse(&result);
+        result
+    }
+
+    /// Parse a string to a valid LEI or an error, allowing the string to contain leading
+    /// or trailing whitespace and/or lowercase letters as long as it is otherwise the right
+    /// length and format.
+    pub fn parse_loose(value: &str) -> Result<LEI, LEIError> {
+        let trimmed = value.trim().as_bytes();
+        if trimmed.len() != 20 {
+            return LEI::parse_bytes(trimmed);
+        }
+
+        // Case-fold into a buffer on the stack rather than allocating an uppercased copy.
+
+        let mut bb = [0u8; 20];
+        for (to, from) in bb.iter_mut().zip(trimmed) {
+            *to = from.to_ascii_uppercase();
+        }
+        LEI::parse_bytes(&bb)
+    }
+
+    /// Build an LEI from a _Payload_ (an already-concatenated _LOU ID_ and _Entity ID_). The
+    /// _Check Digits_ are automatically computed. For about 2 in 97 payloads they are "00" or
+    /// "01", which `parse()` accepts (and some issued LEIs have) but `parse_strict_iso()` rejects
+    /// as reserved; use `from_payload_strict_iso()` to get "97" or "98" instead.
+    pub fn from_payload(payload: &str) -> Result<LEI, LEIError> {
+        if payload.len() != 18 {
+            return Err(LEIError::InvalidPayloadLength { was: payload.len() });
+        }
+        let b = &payload.as_bytes()[0..18];
+
+        let lou_id = &b[0..4];
+        validate_lou_id_format(lou_id)?;
+
+        let entity_id = &b[4..18];
+        validate_entity_id_format(entity_id)?;
+
+        let mut bb = [0u8; 20];
+
+        bb[0..18].copy_from_slice(b);
+        let temp = compute_check_digits(b);
+        bb[18..20].copy_from_slice(&temp);
+
+        Ok(LEI(bb))
+    }
+
+    /// Build an LEI from a _Payload_ like `from_payload()`, but with _Check Digits_ in the range
+    /// 02 to 98 that ISO/IEC 7064 MOD 97-10 assigns, so that `parse_strict_iso()` accepts it.
+    ///
+    /// ```
+    /// use lei::LEI;
+    ///
+    /// assert_eq!(LEI::from_payload("315700WH3YMKHCVYW2").unwrap().check_digits(), "01");
+    /// let lei = LEI::from_payload_strict_iso("315700WH3YMKHCVYW2").unwrap();
+    /// assert_eq!(lei.check_digits(), "98");
+    /// assert_eq!(LEI::parse_strict_iso(lei.as_str()), Ok(lei));
+    /// ```
+    pub fn from_payload_strict_iso(payload: &str) -> Result<LEI, LEIError> {
+        LEI::from_payload(payload).map(LEI::with_iso_check_digits)
+    }
+
+    /// Replace reserved _Check Digits_ "00" or "01", which are correct only where the ISO 7064
+    /// ones are "97" or "98", with those.
+    fn with_iso_check_digits(self) -> LEI {
+        let mut bb = self.0;
+        let check_digits = iso_check_digits([bb[18], bb[19]]);
+        bb[Self::CHECK_DIGITS_RANGE].copy_from_slice(&check_digits);
+        LEI(bb)
+    }
+
+    /// Build an LEI from its parts: an _LOU ID_ and an _Entity ID_. The _Check Digits_ are
+    /// automatically computed.
+    pub fn from_parts(lou_id: &str, entity_id: &str) -> Result<LEI, LEIError> {
+        if lou_id.len() != 4 {
+            return Err(LEIError::InvalidLouIdLength { was: lou_id.len() });
+        }
+        let lou_id: &[u8] = &lou_id.as_bytes()[0..4];
+        validate_lou_id_format(lou_id)?;
+
+        if entity_id.len() != 14 {
+            return Err(LEIError::InvalidEntityIdLength {
+                was: entity_id.len(),
+            });
+        }
+        let entity_id: &[u8] = &entity_id.as_bytes()[0..14];
+        validate_entity_id_format(entity_id)?;
+
+        let mut bb = [0u8; 20];
+
+        bb[0..4].copy_from_slice(lou_id);
+        bb[4..18].copy_from_slice(entity_id);
+        let temp = compute_check_digits(&bb[0..18]);
+        bb[18..20].copy_from_slice(&temp);
+
+        Ok(LEI(bb))
+    }
+
+    /// Build an LEI from an already-validated _LOU ID_ and _Entity ID_. The _Check Digits_ are
+    /// automatically computed, and no further validation is needed, so this cannot fail.
+    pub fn from_typed_parts(lou_id: LouId, entity_id: EntityId) -> LEI {
+        let mut bb = [0u8; 20];
+
+        bb[Self::LOU_ID_RANGE].copy_from_slice(&lou_id.0);
+        bb[Self::ENTITY_ID_RANGE].copy_from_slice(&entity_id.0);
+        let temp = compute_check_digits(&bb[Self::PAYLOAD_RANGE]);
+        bb[Self::CHECK_DIGITS_RANGE].copy_from_slice(&temp);
+
+        LEI(bb)
+    }
+
+    /// Test whether or not the passed string is in valid LEI format, without producing an LEI
+    /// struct value. This is a `const fn`, so it can be used in `const` contexts.
+    pub const fn validate(value: &str) -> bool {
+        const_problem(value.as_bytes()).is_none()
+    }
+
+    /// Test whether or not the passed bytes are a valid LEI, with the same rules as `validate()`,
+    /// without first converting them to a `str`. Any byte that is not ASCII makes the input
+    /// invalid, so a successful result also means the bytes are valid UTF-8.
+    ///
+    /// ```
+    /// use lei::LEI;
+    ///
+    /// assert!(LEI::validate_bytes(b"549300IYKILIU506KA05"));
+    /// assert!(!LEI::validate_bytes(b"549300IYKILIU506KA06"));
+    /// assert!(!LEI::validate_bytes(&[0xFF; 20]));
+    /// ```
+    pub const fn validate_bytes(value: &[u8]) -> bool {
+        const_problem(value).is_none()
+    }
+
+    /// Correct the _Check Digits_ of a string that is otherwise a valid LEI. Returns the LEI if
+    /// `parse()` accepts `value` as is, the LEI with recomputed _Check Digits_ if the only problem
+    /// is `LEIError::IncorrectCheckDigits`, and `None` for any other problem. Like
+    /// `from_payload()`, this may produce the reserved "00" or "01"; see `repair_strict_iso()`.
+    ///
+    /// ```
+    /// use lei::LEI;
+    ///
+    /// let lei = LEI::repair("549300IYKILIU506KA50").unwrap();
+    /// assert_eq!(lei.as_str(), "549300IYKILIU506KA05");
+    /// assert_eq!(LEI::repair("549300IYKILIU506ka50"), None);
+    /// ```
+    pub fn repair(value: &str) -> Option<LEI> {
+        match LEI::parse(value) {
+            Ok(lei) => Some(lei),
+            Err(LEIError::IncorrectCheckDigits { expected, .. }) => {
+                let mut bb = [0u8; 20];
+                bb.copy_from_slice(value.as_bytes());
+                bb[Self::CHECK_DIGITS_RANGE].copy_from_slice(&expected.0);
+                Some(LEI(bb))
+            }
+            Err(_) => None,
+        }
+    }
+
+    /// Correct the _Check Digits_ of a string that is otherwise a valid LEI like `repair()`, but
+    /// into the range 02 to 98 that ISO/IEC 7064 MOD 97-10 assigns, so that `parse_strict_iso()`
+    /// accepts the result.
+    pub fn repair_strict_iso(value: &str) -> Option<LEI> {
+        LEI::repair(value).map(LEI::with_iso_check_digits)
+    }
+
+    /// Suggest valid LEIs that a mistyped string was likely meant to be. After trimming and
+    /// uppercasing, a 20-character `value` is tried with each of these edits, and every result
+    /// with correct _Check Digits_ is returned, most likely first:
+    ///
+    /// 1. swapping one of the commonly confused characters O/0 or I/1 for the other,
+    /// 2. transposing two adjacent characters,
+    /// 3. substituting any other single character.
+    ///
+    /// A `value` that is already valid is returned alone, and one of any other length gives no
+    /// suggestions. Since a single substitution always has some correction, most inputs get
+    /// several candidates; they are for a person to choose between, not to apply blindly.
+    ///
+    /// ```
+    /// use lei::LEI;
+    ///
+    /// let found = LEI::suggest("5493001YKILIU506KA05");
+    /// assert_eq!(found[0].as_str(), "549300IYKILIU506KA05");
+    /// ```
+    pub fn suggest(value: &str) -> Vec<LEI> {
+        typos::suggest(value)
+    }
+
+    /// Check a string with the same rules as `parse()`, but report every problem found, with the
+    /// part of the LEI and the byte range of the input it concerns, rather than just the first.
+    pub fn validate_report(value: &str) -> ValidationReport {
+        report::validate_report(value)
+    }
+
+    /// Parse a string to a valid LEI in a `const` context, with the same rules as `parse()`.
+    /// Since there is no way to return an error from a constant, this panics if `value` is not
+    /// a valid LEI, which fails the build when used to initialize a `const` or `static`.
+    ///
+    /// ```
+    /// use lei::LEI;
+    ///
+    /// const EXAMPLE: LEI = LEI::parse_const("5493002F3N6V3Z14SP04");
+    /// assert_eq!(EXAMPLE.lou_id(), "5493");
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use lei::LEI;
+    ///
+    /// const BAD: LEI = LEI::parse_const("5493002F3N6V3Z14SP05");
+    /// ```
+    pub const fn parse_const(value: &str) -> LEI {
+        let b = value.as_bytes();
+        if let Some(problem) = const_problem(b) {
+            panic!("{}", problem);
+        }
+
+        let mut bb = [0u8; 20];
+        let mut i = 0;
+        while i < 20 {
+            bb[i] = b[i];
+            i += 1;
+        }
+
+        LEI(bb)
+    }
+
+    /// Encode the LEI as an integer: the _Payload_ read as a base 36 number, times 100, plus the
+    /// _Check Digits_. Every LEI fits losslessly, and the encoding preserves `LEI`'s ordering, so
+    /// the result is suitable as a compact integer key. `from_u128()` reverses it.
+    ///
+    /// ```
+    /// use lei::LEI;
+    ///
+    /// let lei = lei::parse("YZ83GD8L7GG84979J516").unwrap();
+    /// let n = lei.to_u128();
+    /// assert_eq!(LEI::from_u128(n), Some(lei));
+    /// ```
+    pub fn to_u128(&self) -> u128 {
+        let payload = self.0[Self::PAYLOAD_RANGE]
+            .iter()
+            .fold(0u128, |n, &b| n * 36 + base36(b));
+        payload * 100 + ((self.0[18] - b'0') * 10 + (self.0[19] - b'0')) as u128
+    }
+
+    /// Decode an integer produced by `to_u128()`. Returns `None` if `value` is too large to be an
+    /// encoded LEI or its _Check Digits_ are incorrect.
+    pub fn from_u128(value: u128) -> Option<LEI> {
+        let mut bb = [0u8; 20];
+
+        let check_digits = (value % 100) as u8;
+        bb[18] = b'0' + check_digits / 10;
+        bb[19] = b'0' + check_digits % 10;
+
+        let mut n = value / 100;
+        for b in bb[Self::PAYLOAD_RANGE].iter_mut().rev() {
+            let d = (n % 36) as u8;
+            *b = if d < 10 { b'0' + d } else { b'A' + d - 10 };
+            n /= 36;
+        }
+        if n != 0 {
+            return None;
+        }
+
+        parse_bytes_unrecorded(&bb).ok()
+    }
+
+    /// Compare two LEIs by their _Entity IDs_, then their _LOU IDs_, then their _Check Digits_,
+    /// for use with `sort_by()` and similar. See also `ordering::ByEntityId`.
+    pub fn cmp_by_entity_id(&self, other: &LEI) -> Ordering {
+        self.entity_id()
+            .cmp(other.entity_id())
+            .then_with(|| self.lou_id().cmp(other.lou_id()))
+            .then_with(|| self.check_digits().cmp(other.check_digits()))
+    }
+
+    /// Copy 20 bytes already known to be a valid LEI into an `LEI`. Panics if `b` is not 20
+    /// bytes long.
+    #[cfg(feature = "arrow")]
+    pub(crate) fn from_validated_bytes(b: &[u8]) -> LEI {
+        let mut bb = [0u8; 20];
+        bb.copy_from_slice(b);
+        LEI(bb)
+    }
+
+    /// Return the 20 ASCII bytes of the LEI as a slice, for writing to fixed-width records and
+    /// binary protocols without string conversion.
+    pub fn as_bytes(&self) -> &[u8] {
         &self.0[..]
     }
 
+    /// Return the 20 ASCII bytes of the LEI as an array.
+    pub fn into_bytes(self) -> [u8; 20] {
+        self.0
+    }
+
+    /// Return the full 20-character LEI as a string slice, without allocating.
+    pub fn as_str(&self) -> &str {
+        unsafe { from_utf8_unchecked(&self.0[..]) } // This is safe because we know it is ASCII
+    }
+
+    /// Return a value that displays the LEI in lowercase.
+    pub fn display_lowercase(&self) -> display::LowercaseDisplay<'_> {
+        display::LowercaseDisplay(self)
+    }
+
+    /// Return a value that displays the LEI with its _LOU ID_, _Entity ID_ and _Check Digits_
+    /// separated by spaces, as in "5493 00IYKILIU506KA 05".
+    pub fn display_grouped(&self) -> display::GroupedDisplay<'_> {
+        display::GroupedDisplay(self)
+    }
+
+    /// Return the LEI as a borrowed `&LeiStr`, without allocating.
+    pub fn as_lei_str(&self) -> &LeiStr {
+        LeiStr::from_str_unchecked(self.as_str())
+    }
+
     /// Return just the _LOU ID_ portion of the LEI.
     pub fn lou_id(&self) -> &str {
-        unsafe { from_utf8_unchecked(&self.0[0..4]) } // This is safe because we know it is ASCII
+        unsafe { from_utf8_unchecked(&self.0[Self::LOU_ID_RANGE]) } // This is safe because we know it is ASCII
+    }
+
+    /// Return the _LOU ID_ as a `LouId`.
+    pub fn lou_id_typed(&self) -> LouId {
+        LouId([self.0[0], self.0[1], self.0[2], self.0[3]])
     }
 
     /// Return just the _Entity ID_ portion of the LEI.
     pub fn entity_id(&self) -> &str {
-        unsafe { from_utf8_unchecked(&self.0[4..18]) } // This is safe because we know it is ASCII
+        unsafe { from_utf8_unchecked(&self.0[Self::ENTITY_ID_RANGE]) } // This is safe because we know it is ASCII
+    }
+
+    /// Return the _Entity ID_ as an `EntityId`.
+    pub fn entity_id_typed(&self) -> EntityId {
+        let mut bb = [0u8; 14];
+        bb.copy_from_slice(&self.0[Self::ENTITY_ID_RANGE]);
+        EntityId(bb)
     }
 
     /// Return the _Payload_ &mdash; everything except the _Check Digits_.
     pub fn payload(&self) -> &str {
-        unsafe { from_utf8_unchecked(&self.0[0..18]) } // This is safe because we know it is ASCII
+        unsafe { from_utf8_unchecked(&self.0[Self::PAYLOAD_RANGE]) } // This is safe because we know it is ASCII
     }
 
     /// Return just the _Check Digit_ portion of the ISIN.
     pub fn check_digits(&self) -> &str {
-        unsafe { from_utf8_unchecked(&self.0[18..20]) } // This is safe because we know it is ASCII
+        unsafe { from_utf8_unchecked(&self.0[Self::CHECK_DIGITS_RANGE]) } // This is safe because we know it is ASCII
+    }
+
+    /// Return the _Check Digits_ as a `CheckDigits`, for numeric access without re-parsing.
+    pub fn check_digits_typed(&self) -> CheckDigits {
+        CheckDigits([self.0[18], self.0[19]])
+    }
+
+    /// Return the given part of the LEI, for iterating over the parts without naming each
+    /// accessor.
+    pub fn get(&self, part: Part) -> &str {
+        unsafe { from_utf8_unchecked(&self.0[part.range()]) } // This is safe because we know it is ASCII
+    }
+
+    /// Copy the canonical 20-byte ASCII form of the LEI into a caller-provided buffer without
+    /// allocating. Returns the number of bytes written, which is always 20.
+    pub fn write_to(&self, buf: &mut [u8; 20]) -> usize {
+        buf.copy_from_slice(&self.0);
+        self.0.len()
+    }
+
+    /// Write the canonical form of the LEI to any `fmt::Write` without allocating. Returns the
+    /// number of bytes written, which is always 20.
+    pub fn write_to_fmt<W: fmt::Write>(&self, w: &mut W) -> Result<usize, fmt::Error> {
+        w.write_str(self.as_str())?;
+        Ok(Self::LENGTH)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     /// This is from the ISIN_LEI_20210209.csv file from GLEIF.
     #[test]
@@ -413,4 +1251,326 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn write_to_buffers() {
+        let lei = parse("YZ83GD8L7GG84979J516").unwrap();
+
+        let mut buf = [0u8; 20];
+        assert_eq!(lei.write_to(&mut buf), 20);
+        assert_eq!(&buf, b"YZ83GD8L7GG84979J516");
+
+        let mut record = String::from("LEI=");
+        assert_eq!(lei.write_to_fmt(&mut record).unwrap(), 20);
+        assert_eq!(record, "LEI=YZ83GD8L7GG84979J516");
+    }
+
+    #[test]
+    fn collect_valid_partitions_in_order() {
+        let inputs = vec![
+            "YZ83GD8L7GG84979J516".to_owned(),
+            "yz83gd8l7gg84979j516".to_owned(),
+            "549300IYKILIU506KA05".to_owned(),
+            "549300IYKILIU506KA06".to_owned(),
+        ];
+        let (valid, invalid) = collect_valid(inputs);
+        let valid: Vec<String> = valid.iter().map(|l| l.to_string()).collect();
+        assert_eq!(valid, vec!["YZ83GD8L7GG84979J516", "549300IYKILIU506KA05"]);
+        assert_eq!(invalid.len(), 2);
+        assert_eq!(invalid[0].0, "yz83gd8l7gg84979j516");
+        assert!(matches!(
+            invalid[1].1,
+            LEIError::IncorrectCheckDigits { .. }
+        ));
+    }
+
+    #[test]
+    fn as_str_is_full_code() {
+        let lei = parse("YZ83GD8L7GG84979J516").unwrap();
+        assert_eq!(lei.as_str(), "YZ83GD8L7GG84979J516");
+        assert_eq!(lei.as_str(), lei.to_string());
+    }
+
+    #[test]
+    fn borrow_as_str_in_collections() {
+        use std::collections::{BTreeSet, HashMap, HashSet};
+
+        let lei = parse("YZ83GD8L7GG84979J516").unwrap();
+
+        let by_string: HashMap<String, u32> = [("YZ83GD8L7GG84979J516".to_owned(), 1)].into();
+        assert_eq!(by_string.get(lei.as_ref() as &str), Some(&1));
+
+        let leis: HashSet<LEI> = [lei].into();
+        assert!(leis.contains("YZ83GD8L7GG84979J516"));
+        let ordered: BTreeSet<LEI> = [lei].into();
+        assert!(ordered.contains("YZ83GD8L7GG84979J516"));
+
+        fn takes_as_ref<S: AsRef<str>>(s: S) -> usize {
+            s.as_ref().len()
+        }
+        assert_eq!(takes_as_ref(lei), 20);
+    }
+
+    #[test]
+    fn deref_to_str() {
+        let lei = parse("549300IYKILIU506KA05").unwrap();
+        assert!(lei.starts_with("5493"));
+        assert_eq!(lei.len(), 20);
+        assert_eq!(lei.find("KA"), Some(16));
+        assert_eq!(&*lei, "549300IYKILIU506KA05");
+    }
+
+    #[test]
+    fn try_from_strings_is_strict() {
+        let lei = parse("YZ83GD8L7GG84979J516").unwrap();
+        assert_eq!(LEI::try_from("YZ83GD8L7GG84979J516"), Ok(lei));
+        assert_eq!(LEI::try_from("YZ83GD8L7GG84979J516".to_owned()), Ok(lei));
+        assert!(LEI::try_from("yz83gd8l7gg84979j516").is_err());
+        assert!(LEI::try_from(" YZ83GD8L7GG84979J516".to_owned()).is_err());
+    }
+
+    #[test]
+    fn parse_from_bytes() {
+        let lei = parse("YZ83GD8L7GG84979J516").unwrap();
+        assert_eq!(parse_bytes(b"YZ83GD8L7GG84979J516"), Ok(lei));
+        assert_eq!(LEI::try_from(b"YZ83GD8L7GG84979J516"), Ok(lei));
+        assert_eq!(
+            parse_bytes(b"YZ83GD8L7GG84979J51"),
+            Err(LEIError::InvalidLength { was: 19 })
+        );
+        assert!(matches!(
+            parse_bytes(b"\xffZ83GD8L7GG84979J516"),
+            Err(LEIError::InvalidLouId { .. })
+        ));
+        assert!(matches!(
+            LEI::try_from(b"YZ83GD8L7GG84979J517"),
+            Err(LEIError::IncorrectCheckDigits { .. })
+        ));
+    }
+
+    #[test]
+    fn into_string_and_array() {
+        let lei = parse("YZ83GD8L7GG84979J516").unwrap();
+        let s: String = lei.into();
+        assert_eq!(s, "YZ83GD8L7GG84979J516");
+        let a: [u8; 20] = lei.into();
+        assert_eq!(&a, b"YZ83GD8L7GG84979J516");
+    }
+
+    #[test]
+    fn byte_accessors() {
+        let lei = parse("YZ83GD8L7GG84979J516").unwrap();
+        assert_eq!(lei.as_bytes(), b"YZ83GD8L7GG84979J516");
+        assert_eq!(lei.into_bytes(), *b"YZ83GD8L7GG84979J516");
+        assert_eq!(parse_bytes(&lei.into_bytes()), Ok(lei));
+    }
+
+    #[test]
+    fn compare_with_strings() {
+        let lei = parse("YZ83GD8L7GG84979J516").unwrap();
+        assert_eq!(lei, "YZ83GD8L7GG84979J516");
+        assert_eq!("YZ83GD8L7GG84979J516", lei);
+        assert_eq!(lei, *"YZ83GD8L7GG84979J516");
+        assert_eq!(lei, "YZ83GD8L7GG84979J516".to_owned());
+        assert_eq!("YZ83GD8L7GG84979J516".to_owned(), lei);
+        assert_ne!(lei, "yz83gd8l7gg84979j516");
+    }
+
+    #[test]
+    fn length_and_range_constants() {
+        let lei = parse("549300IYKILIU506KA05").unwrap();
+        assert_eq!(lei.len(), LEI::LENGTH);
+        assert_eq!(&lei[LEI::LOU_ID_RANGE], lei.lou_id());
+        assert_eq!(&lei[LEI::ENTITY_ID_RANGE], lei.entity_id());
+        assert_eq!(&lei[LEI::PAYLOAD_RANGE], lei.payload());
+        assert_eq!(&lei[LEI::CHECK_DIGITS_RANGE], lei.check_digits());
+        assert_eq!(LEI::LOU_ID_RANGE.len(), LEI::LOU_ID_LENGTH);
+        assert_eq!(LEI::ENTITY_ID_RANGE.len(), LEI::ENTITY_ID_LENGTH);
+        assert_eq!(LEI::PAYLOAD_RANGE.len(), LEI::PAYLOAD_LENGTH);
+        assert_eq!(LEI::CHECK_DIGITS_RANGE.len(), LEI::CHECK_DIGITS_LENGTH);
+    }
+
+    #[test]
+    fn associated_functions_match_free_functions() {
+        let lei = LEI::parse("YZ83GD8L7GG84979J516").unwrap();
+        assert_eq!(parse("YZ83GD8L7GG84979J516"), Ok(lei));
+        assert_eq!(LEI::parse_loose(" yz83gd8l7gg84979j516 "), Ok(lei));
+        assert_eq!(LEI::parse_bytes(b"YZ83GD8L7GG84979J516"), Ok(lei));
+        assert_eq!(LEI::from_payload("YZ83GD8L7GG84979J5"), Ok(lei));
+        assert_eq!(LEI::from_parts("YZ83", "GD8L7GG84979J5"), Ok(lei));
+        assert!(LEI::validate("YZ83GD8L7GG84979J516"));
+        assert!(!LEI::validate("YZ83GD8L7GG84979J517"));
+    }
+
+    #[test]
+    fn typed_parts_round_trip() {
+        let lei = parse("549300IYKILIU506KA05").unwrap();
+        let lou_id = lei.lou_id_typed();
+        let entity_id = lei.entity_id_typed();
+        assert_eq!(lou_id.as_str(), lei.lou_id());
+        assert_eq!(entity_id.as_str(), lei.entity_id());
+        assert_eq!(LEI::from_typed_parts(lou_id, entity_id), lei);
+    }
+
+    const CONST_LEI: LEI = LEI::parse_const("5493002F3N6V3Z14SP04");
+    const _: () = assert!(LEI::validate("YZ83GD8L7GG84979J516"));
+
+    #[test]
+    fn const_parse_and_validate() {
+        assert_eq!(CONST_LEI, parse("5493002F3N6V3Z14SP04").unwrap());
+        assert!(!validate("YZ83GD8L7GG84979J517"));
+        assert!(!validate("yz83gd8l7gg84979j516"));
+        assert!(!validate("YZ83GD8L7GG84979J5"));
+    }
+
+    #[test]
+    #[should_panic(expected = "incorrect Check Digits")]
+    fn parse_const_panics_at_runtime_too() {
+        LEI::parse_const("5493002F3N6V3Z14SP05");
+    }
+
+    proptest! {
+        #[test]
+        fn const_check_digits_match(payload in "[0-9A-Z]{18}") {
+            let b = payload.as_bytes();
+            prop_assert_eq!(compute_check_digits_const(b), compute_check_digits(b));
+        }
+
+        #[test]
+        fn validate_matches_parse(value in "[0-9A-Za-z]{18}[0-9]{2}") {
+            prop_assert_eq!(validate(&value), parse(&value).is_ok());
+        }
+
+        #[test]
+        fn u128_round_trip(payload in "[0-9A-Z]{18}") {
+            let lei = build_from_payload(&payload).unwrap();
+            prop_assert_eq!(LEI::from_u128(lei.to_u128()), Some(lei));
+        }
+
+        #[test]
+        fn u128_preserves_order(a in "[0-9A-Z]{18}", b in "[0-9A-Z]{18}") {
+            let a = build_from_payload(&a).unwrap();
+            let b = build_from_payload(&b).unwrap();
+            prop_assert_eq!(a.to_u128().cmp(&b.to_u128()), a.cmp(&b));
+        }
+
+        #[test]
+        fn from_u128_is_valid_or_none(n in any::<u128>()) {
+            if let Some(lei) = LEI::from_u128(n) {
+                prop_assert_eq!(lei.to_u128(), n);
+            }
+        }
+
+        #[test]
+        fn min_and_max_bound_every_lei(payload in "[0-9A-Z]{18}") {
+            let lei = build_from_payload(&payload).unwrap();
+            prop_assert!(LEI::MIN <= lei && lei <= LEI::MAX);
+        }
+
+        #[test]
+        fn validate_bytes_matches_parse_bytes(value in proptest::collection::vec(any::<u8>(), 18..22)) {
+            prop_assert_eq!(validate_bytes(&value), parse_bytes(&value).is_ok());
+        }
+    }
+
+    #[test]
+    fn u128_limits() {
+        assert_eq!(LEI::MIN.to_u128(), 1);
+        assert_eq!(LEI::from_u128(LEI::MIN.to_u128() + 1), None);
+        assert_eq!(LEI::from_u128(LEI::MAX.to_u128() + 100), None);
+        assert_eq!(LEI::from_u128(u128::MAX), None);
+    }
+
+    #[test]
+    fn min_and_max() {
+        assert_eq!(build_from_payload("000000000000000000"), Ok(LEI::MIN));
+        assert_eq!(build_from_payload("ZZZZZZZZZZZZZZZZZZ"), Ok(LEI::MAX));
+    }
+
+    #[test]
+    fn lei_macro() {
+        let lei = lei!("YZ83GD8L7GG84979J516");
+        assert_eq!(lei, parse("YZ83GD8L7GG84979J516").unwrap());
+    }
+
+    #[test]
+    fn parse_loose_trims_and_folds_case() {
+        let lei = parse("549300IYKILIU506KA05").unwrap();
+        assert_eq!(parse_loose("\t549300iykiliu506ka05\n"), Ok(lei));
+        assert_eq!(parse_loose("\u{a0}549300iykiliu506ka05"), Ok(lei));
+        assert_eq!(
+            parse_loose(" 549300IYKILIU506KA055 "),
+            Err(LEIError::InvalidLength { was: 21 })
+        );
+        assert!(parse_loose("549300IYKILIU506KÄ5").is_err());
+    }
+
+    #[test]
+    fn parse_strict_iso_rejects_reserved_check_digits() {
+        assert!(parse("31570010000000045200").is_ok());
+        assert_eq!(
+            parse_strict_iso("31570010000000045200"),
+            Err(LEIError::ReservedCheckDigits {
+                was: CheckDigits(*b"00")
+            })
+        );
+        assert_eq!(
+            parse_strict_iso("315700WH3YMKHCVYW201"),
+            Err(LEIError::ReservedCheckDigits {
+                was: CheckDigits(*b"01")
+            })
+        );
+        assert!(parse_strict_iso("549300IYKILIU506KA05").is_ok());
+        assert_eq!(
+            LEI::parse_strict_iso("549300IYKILIU506KA06"),
+            parse("549300IYKILIU506KA06")
+        );
+    }
+
+    #[test]
+    fn iso_check_digits_for_reserved_results() {
+        for (legacy, iso) in [
+            ("315700WH3YMKHCVYW201", "315700WH3YMKHCVYW298"),
+            ("31570010000000045200", "31570010000000045297"),
+        ] {
+            let payload = &legacy[..18];
+            assert_eq!(build_from_payload(payload).unwrap().as_str(), legacy);
+            let lei = build_from_payload_strict_iso(payload).unwrap();
+            assert_eq!(lei.as_str(), iso);
+
+            assert_eq!(parse(iso), Ok(lei));
+            assert_eq!(parse_strict_iso(iso), Ok(lei));
+            assert!(validate(iso));
+            assert!(parse_strict_iso(legacy).is_err());
+
+            assert_eq!(repair_strict_iso(legacy), Some(lei));
+            assert_eq!(repair_strict_iso(&format!("{payload}17")), Some(lei));
+            assert_eq!(repair(&format!("{payload}17")).unwrap().as_str(), legacy);
+        }
+
+        let lei = build_from_payload_strict_iso("YZ83GD8L7GG84979J5").unwrap();
+        assert_eq!(lei.as_str(), "YZ83GD8L7GG84979J516");
+        assert_eq!(
+            parse_strict_iso("315700WH3YMKHCVYW217"),
+            Err(LEIError::IncorrectCheckDigits {
+                was: CheckDigits(*b"17"),
+                expected: CheckDigits(*b"98"),
+            })
+        );
+    }
+
+    #[test]
+    fn repair_corrects_only_check_digits() {
+        assert_eq!(
+            crate::repair("YZ83GD8L7GG84979J516"),
+            crate::parse("YZ83GD8L7GG84979J516").ok()
+        );
+        assert_eq!(
+            crate::repair("YZ83GD8L7GG84979J599").unwrap().as_str(),
+            "YZ83GD8L7GG84979J516"
+        );
+        assert_eq!(crate::repair("YZ83GD8L7GG84979J5"), None);
+        assert_eq!(crate::repair("YZ83GD8L7GG84979J5XX"), None);
+        assert_eq!(crate::repair("YZ83-D8L7GG84979J516"), None);
+    }
 }
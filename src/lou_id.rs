@@ -0,0 +1,123 @@
+#![warn(missing_docs)]
+//! # lei::lou_id
+//!
+//! A validated _LOU ID_: the four-character prefix identifying the _LOU_ that issued an LEI.
+
+use std::fmt;
+use std::str::from_utf8_unchecked;
+use std::str::FromStr;
+
+use crate::LEIError;
+
+/// A _LOU ID_ in confirmed valid format: four uppercase ASCII alphanumerics.
+///
+/// ```
+/// use lei::LouId;
+///
+/// let lou: LouId = "5493".parse().unwrap();
+/// assert_eq!(lou.to_string(), "5493");
+///
+/// let lei = lei::parse("549300IYKILIU506KA05").unwrap();
+/// assert_eq!(lei.lou_id_typed(), lou);
+/// ```
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Hash)]
+pub struct LouId(pub(crate) [u8; 4]);
+
+impl fmt::Display for LouId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl fmt::Debug for LouId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "LouId({})", self.as_str())
+    }
+}
+
+impl FromStr for LouId {
+    type Err = LEIError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        LouId::parse(s)
+    }
+}
+
+impl TryFrom<&str> for LouId {
+    type Error = LEIError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        LouId::parse(value)
+    }
+}
+
+impl AsRef<str> for LouId {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl LouId {
+    /// Parse a string to a valid _LOU ID_ or an error, requiring exactly four uppercase ASCII
+    /// alphanumerics.
+    pub fn parse(value: &str) -> Result<LouId, LEIError> {
+        LouId::validate(value)?;
+        let b = value.as_bytes();
+        let mut bb = [0u8; 4];
+        bb.copy_from_slice(b);
+        Ok(LouId(bb))
+    }
+
+    /// Check that a string is a valid _LOU ID_ without constructing one, for validating the
+    /// part on its own (for example, as it is typed into a form field).
+    pub fn validate(value: &str) -> Result<(), LEIError> {
+        let b = value.as_bytes();
+        if b.len() != 4 {
+            return Err(LEIError::InvalidLouIdLength { was: b.len() });
+        }
+        crate::validate_lou_id_format(b)
+    }
+
+    /// Return the _LOU ID_ as a string slice.
+    pub fn as_str(&self) -> &str {
+        unsafe { from_utf8_unchecked(&self.0[..]) } // This is safe because we know it is ASCII
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_display() {
+        let lou = LouId::parse("5493").unwrap();
+        assert_eq!(lou.as_str(), "5493");
+        assert_eq!(format!("{lou:?}"), "LouId(5493)");
+        assert_eq!(LouId::try_from("YZ83"), "YZ83".parse());
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            LouId::parse("549"),
+            Err(LEIError::InvalidLouIdLength { was: 3 })
+        );
+        assert_eq!(
+            LouId::parse("54a3"),
+            Err(LEIError::InvalidLouId { was: *b"54a3" })
+        );
+    }
+
+    #[test]
+    fn validate_without_constructing() {
+        assert_eq!(LouId::validate("5493"), Ok(()));
+        assert_eq!(
+            LouId::validate(""),
+            Err(LEIError::InvalidLouIdLength { was: 0 })
+        );
+        assert_eq!(
+            LouId::validate("54 3"),
+            Err(LEIError::InvalidLouId { was: *b"54 3" })
+        );
+    }
+}
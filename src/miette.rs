@@ -0,0 +1,100 @@
+#![warn(missing_docs)]
+//! # lei::miette
+//!
+//! [miette](https://crates.io/crates/miette) `Diagnostic` implementations for `LEIError` and
+//! `ValidationReport`, labeling the offending characters and suggesting the correct _Check
+//! Digits_ where possible.
+//!
+//! Neither type holds the input, so attach it to the report to see the labels in context:
+//!
+//! ```
+//! use miette::{Diagnostic, Report};
+//!
+//! let input = "549300IYKILIU506KA06";
+//! let err = lei::parse(input).unwrap_err();
+//! assert_eq!(err.help().unwrap().to_string(), "did you mean check digits '05'?");
+//!
+//! let report = Report::new(err).with_source_code(input);
+//! println!("{report:?}");
+//! ```
+
+use std::fmt::Display;
+
+use ::miette::{Diagnostic, LabeledSpan};
+
+use crate::report::ValidationReport;
+use crate::LEIError;
+
+impl Diagnostic for LEIError {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(LEIError::code(self)))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        match self {
+            LEIError::IncorrectCheckDigits { expected, .. } => {
+                Some(Box::new(format!("did you mean check digits '{expected}'?")))
+            }
+            LEIError::InvalidLouId { .. } | LEIError::InvalidEntityId { .. } => Some(Box::new(
+                "only uppercase ASCII letters and digits are allowed",
+            )),
+            LEIError::InvalidCheckDigits { .. } => {
+                Some(Box::new("check digits must be two ASCII digits"))
+            }
+            _ => None,
+        }
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let span = self.span()?;
+        let label = match self.part() {
+            Some(part) => format!("invalid {part}"),
+            None => "here".to_owned(),
+        };
+        Some(Box::new(std::iter::once(LabeledSpan::new_with_span(
+            Some(label),
+            span,
+        ))))
+    }
+}
+
+impl Diagnostic for ValidationReport {
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        if self.is_valid() {
+            return None;
+        }
+        Some(Box::new(self.violations.iter().map(|v| {
+            LabeledSpan::new_with_span(Some(v.message.clone()), v.span.clone())
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_diagnostics() {
+        let err = crate::parse("549300IYKILIU506KA06").unwrap_err();
+        assert_eq!(Diagnostic::code(&err).unwrap().to_string(), "LEI-E009");
+        let labels: Vec<LabeledSpan> = err.labels().unwrap().collect();
+        assert_eq!(labels.len(), 1);
+        assert_eq!(labels[0].offset(), 18);
+        assert_eq!(labels[0].len(), 2);
+        assert_eq!(labels[0].label(), Some("invalid Check Digits"));
+
+        let err = crate::parse("549300IYKILIU506KA").unwrap_err();
+        assert!(err.labels().is_none());
+    }
+
+    #[test]
+    fn report_diagnostics() {
+        let report = crate::validate_report("yz83GD8L7GG84979J517");
+        let labels: Vec<LabeledSpan> = report.labels().unwrap().collect();
+        let offsets: Vec<usize> = labels.iter().map(|l| l.offset()).collect();
+        assert_eq!(offsets, vec![0, 1, 18]);
+        assert!(crate::validate_report("YZ83GD8L7GG84979J516")
+            .labels()
+            .is_none());
+    }
+}
@@ -0,0 +1,186 @@
+#![warn(missing_docs)]
+//! # lei::mutate
+//!
+//! Generation of labeled invalid LEIs from valid ones, for negative testing of systems that are
+//! supposed to reject them.
+//!
+//! ```
+//! use lei::mutate::{mutations, MutationKind};
+//!
+//! let lei = lei::parse("YZ83GD8L7GG84979J516").unwrap();
+//! for m in mutations(&lei) {
+//!     assert!(lei::parse(&m.value).is_err(), "{:?} should be invalid", m.kind);
+//! }
+//! ```
+
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::{LEIError, LEI};
+
+/// The kind of corruption applied to produce an invalid LEI.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MutationKind {
+    /// The last character is removed.
+    TooShort,
+    /// An extra character is appended.
+    TooLong,
+    /// All letters are lowercased.
+    Lowercase,
+    /// Two adjacent, different characters of the _Payload_ are swapped.
+    Transposition,
+    /// A single _Payload_ character is replaced by a different alphanumeric character.
+    Substitution,
+    /// The _Check Digits_ are replaced by a different pair.
+    CorruptedCheckDigits,
+    /// A character is replaced by a visually identical non-ASCII character (such as Cyrillic
+    /// "А" for Latin "A").
+    UnicodeLookalike,
+}
+
+impl Display for MutationKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            MutationKind::TooShort => "too short",
+            MutationKind::TooLong => "too long",
+            MutationKind::Lowercase => "lowercase",
+            MutationKind::Transposition => "transposition",
+            MutationKind::Substitution => "substitution",
+            MutationKind::CorruptedCheckDigits => "corrupted check digits",
+            MutationKind::UnicodeLookalike => "Unicode lookalike",
+        };
+        f.write_str(name)
+    }
+}
+
+/// An invalid variant of a valid LEI.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mutation {
+    /// How the original was corrupted.
+    pub kind: MutationKind,
+    /// The corrupted value.
+    pub value: String,
+    /// The error the strict `parse()` reports for the corrupted value.
+    pub error: LEIError,
+}
+
+/// The visually identical non-ASCII replacement for an ASCII character, if there is one.
+fn lookalike(c: u8) -> Option<char> {
+    Some(match c {
+        b'A' => 'А',
+        b'B' => 'В',
+        b'C' => 'С',
+        b'E' => 'Е',
+        b'H' => 'Н',
+        b'K' => 'К',
+        b'M' => 'М',
+        b'O' | b'0' => 'О',
+        b'P' => 'Р',
+        b'T' => 'Т',
+        b'X' => 'Х',
+        b'Y' => 'У',
+        b'3' => 'З',
+        _ => return None,
+    })
+}
+
+/// Produce one invalid variant of `lei` for each `MutationKind` that applies to it. Every
+/// variant is checked to be rejected by the strict `parse()`, and kinds that cannot corrupt
+/// this particular LEI (such as `Lowercase` for an LEI made only of digits) are left out.
+pub fn mutations(lei: &LEI) -> Vec<Mutation> {
+    let original = lei.to_string();
+    let bytes = original.as_bytes();
+    let mut candidates: Vec<(MutationKind, String)> = Vec::new();
+
+    candidates.push((MutationKind::TooShort, original[..19].to_owned()));
+    candidates.push((MutationKind::TooLong, format!("{original}0")));
+    candidates.push((MutationKind::Lowercase, original.to_ascii_lowercase()));
+
+    // Not every transposition or substitution changes the checksum, so look for the first one
+    // that does.
+    let transposition = (0..17).find_map(|i| {
+        if bytes[i] == bytes[i + 1] {
+            return None;
+        }
+        let mut b = bytes.to_vec();
+        b.swap(i, i + 1);
+        let s = String::from_utf8(b).expect("swapping ASCII bytes yields ASCII");
+        crate::parse(&s).is_err().then_some(s)
+    });
+    if let Some(s) = transposition {
+        candidates.push((MutationKind::Transposition, s));
+    }
+
+    let substitution = (0..18).find_map(|i| {
+        let replacement = if bytes[i] == b'0' { b'1' } else { b'0' };
+        let mut b = bytes.to_vec();
+        b[i] = replacement;
+        let s = String::from_utf8(b).expect("replacing ASCII bytes yields ASCII");
+        crate::parse(&s).is_err().then_some(s)
+    });
+    if let Some(s) = substitution {
+        candidates.push((MutationKind::Substitution, s));
+    }
+
+    let cd: u8 = (bytes[18] - b'0') * 10 + (bytes[19] - b'0');
+    let corrupted = (cd + 1) % 100;
+    candidates.push((
+        MutationKind::CorruptedCheckDigits,
+        format!("{}{:02}", &original[..18], corrupted),
+    ));
+
+    if let Some((i, c)) = bytes
+        .iter()
+        .enumerate()
+        .find_map(|(i, b)| lookalike(*b).map(|c| (i, c)))
+    {
+        let mut s = String::with_capacity(22);
+        s.push_str(&original[..i]);
+        s.push(c);
+        s.push_str(&original[i + 1..]);
+        candidates.push((MutationKind::UnicodeLookalike, s));
+    }
+
+    candidates
+        .into_iter()
+        .filter_map(|(kind, value)| {
+            let error = crate::parse(&value).err()?;
+            Some(Mutation { kind, value, error })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_kind_applies_to_alphanumeric_lei() {
+        let lei = crate::parse("YZ83GD8L7GG84979J516").unwrap();
+        let found = mutations(&lei);
+        let kinds: Vec<MutationKind> = found.iter().map(|m| m.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                MutationKind::TooShort,
+                MutationKind::TooLong,
+                MutationKind::Lowercase,
+                MutationKind::Transposition,
+                MutationKind::Substitution,
+                MutationKind::CorruptedCheckDigits,
+                MutationKind::UnicodeLookalike,
+            ]
+        );
+        assert_eq!(found[0].error, LEIError::InvalidLength { was: 19 });
+        assert_eq!(found[5].value, "YZ83GD8L7GG84979J517");
+        assert_eq!(found[6].value, "УZ83GD8L7GG84979J516");
+    }
+
+    #[test]
+    fn inapplicable_kinds_are_skipped() {
+        let lei = crate::parse("95980020140005346817").unwrap();
+        let kinds: Vec<MutationKind> = mutations(&lei).iter().map(|m| m.kind).collect();
+        assert!(!kinds.contains(&MutationKind::Lowercase));
+        assert!(kinds.contains(&MutationKind::UnicodeLookalike)); // '0' -> Cyrillic 'О'
+    }
+}
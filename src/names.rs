@@ -0,0 +1,295 @@
+#![warn(missing_docs)]
+//! # lei::names
+//!
+//! Legal entity name normalization and similarity scoring, for matching records that carry a
+//! name but no LEI against GLEIF reference data.
+//!
+//! Scores are in the range 0.0 (nothing in common) to 1.0 (identical after normalization).
+//!
+//! ```
+//! use lei::names;
+//!
+//! assert_eq!(names::normalize("Société Générale S.A."), "societe generale");
+//! assert!(names::similarity("Deutsche Bank AG", "DEUTSCHE BANK Aktiengesellschaft") > 0.99);
+//! ```
+
+/// Legal-form designations stripped from the end of a name by `normalize()`. Multi-word forms
+/// are listed as space-separated tokens, already casefolded and stripped of punctuation, and come
+/// first so they win over their own suffixes.
+const LEGAL_FORMS: &[&str] = &[
+    "gmbh and co kg",
+    "public limited company",
+    "a s",
+    "s a",
+    "s p a",
+    "aktiengesellschaft",
+    "ag",
+    "as",
+    "asa",
+    "ab",
+    "bv",
+    "co",
+    "company",
+    "corp",
+    "corporation",
+    "gmbh",
+    "inc",
+    "incorporated",
+    "kg",
+    "kk",
+    "limited",
+    "llc",
+    "llp",
+    "lp",
+    "ltd",
+    "nv",
+    "oy",
+    "oyj",
+    "plc",
+    "sa",
+    "sarl",
+    "sas",
+    "se",
+    "spa",
+    "srl",
+];
+
+/// Fold a lowercase character to its unaccented ASCII equivalent, pushing the result onto `out`.
+/// Characters without a known folding are pushed unchanged.
+fn fold_char(c: char, out: &mut String) {
+    let folded = match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'ď' | 'đ' | 'ð' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'ĥ' | 'ħ' => "h",
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'ĵ' => "j",
+        'ķ' => "k",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'œ' => "oe",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'ś' | 'ŝ' | 'ş' | 'š' => "s",
+        'ß' => "ss",
+        'ţ' | 'ť' | 'ŧ' => "t",
+        'þ' => "th",
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'ŵ' => "w",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        _ => {
+            out.push(c);
+            return;
+        }
+    };
+    out.push_str(folded);
+}
+
+/// Normalize a legal entity name for comparison: casefold, fold common Latin diacritics to
+/// ASCII, treat punctuation as whitespace, collapse whitespace, and strip trailing legal-form
+/// designations such as "Inc.", "GmbH" or "S.A.". Dots and apostrophes inside a word (as in "S.A.")
+/// are removed rather than split on, so abbreviations collapse to a single token.
+pub fn normalize(name: &str) -> String {
+    let mut folded = String::with_capacity(name.len());
+    let mut chars = name.chars().flat_map(char::to_lowercase).peekable();
+    while let Some(c) = chars.next() {
+        if c.is_alphanumeric() {
+            fold_char(c, &mut folded);
+        } else if c == '&' {
+            folded.push_str(" and ");
+        } else if c == '.' || c == '\'' || c == '’' {
+            // Join abbreviations ("s.a." -> "sa") and possessives ("moody's" -> "moodys"), but
+            // keep a break when the dot ends a word before whitespace.
+            if !matches!(chars.peek(), Some(n) if n.is_alphanumeric()) {
+                folded.push(' ');
+            }
+        } else {
+            folded.push(' ');
+        }
+    }
+
+    let mut tokens: Vec<&str> = folded.split_whitespace().collect();
+    'strip: while tokens.len() > 1 {
+        for form in LEGAL_FORMS {
+            let form_tokens: Vec<&str> = form.split(' ').collect();
+            if form_tokens.len() < tokens.len() && tokens.ends_with(&form_tokens) {
+                tokens.truncate(tokens.len() - form_tokens.len());
+                continue 'strip;
+            }
+        }
+        break;
+    }
+    tokens.join(" ")
+}
+
+/// The Jaro-Winkler similarity of two strings, compared character by character without any
+/// normalization.
+pub fn jaro_winkler(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let window = (a.len().max(b.len()) / 2).saturating_sub(1);
+    let mut a_matched = vec![false; a.len()];
+    let mut b_matched = vec![false; b.len()];
+    let mut matches = 0usize;
+
+    for (i, ca) in a.iter().enumerate() {
+        let lo = i.saturating_sub(window);
+        let hi = (i + window + 1).min(b.len());
+        for j in lo..hi {
+            if !b_matched[j] && b[j] == *ca {
+                a_matched[i] = true;
+                b_matched[j] = true;
+                matches += 1;
+                break;
+            }
+        }
+    }
+
+    if matches == 0 {
+        return 0.0;
+    }
+
+    let a_seq = a
+        .iter()
+        .zip(&a_matched)
+        .filter(|(_, m)| **m)
+        .map(|(c, _)| c);
+    let b_seq = b
+        .iter()
+        .zip(&b_matched)
+        .filter(|(_, m)| **m)
+        .map(|(c, _)| c);
+    let transpositions = a_seq.zip(b_seq).filter(|(x, y)| x != y).count() / 2;
+
+    let m = matches as f64;
+    let jaro = (m / a.len() as f64 + m / b.len() as f64 + (m - transpositions as f64) / m) / 3.0;
+
+    let prefix = a.iter().zip(&b).take(4).take_while(|(x, y)| x == y).count();
+    jaro + (prefix as f64) * 0.1 * (1.0 - jaro)
+}
+
+/// Similarity based on Levenshtein edit distance, scaled by the longer string's length.
+fn levenshtein_ratio(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0usize; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    1.0 - (prev[b.len()] as f64) / (longest as f64)
+}
+
+/// Token-set similarity: the words common to both strings are compared against each string's
+/// full word set, so extra words on one side (such as a trailing "Holdings") and differences in
+/// word order are penalized lightly. Both inputs are split on whitespace without further
+/// normalization.
+pub fn token_set_ratio(a: &str, b: &str) -> f64 {
+    let mut a_tokens: Vec<&str> = a.split_whitespace().collect();
+    let mut b_tokens: Vec<&str> = b.split_whitespace().collect();
+    a_tokens.sort_unstable();
+    a_tokens.dedup();
+    b_tokens.sort_unstable();
+    b_tokens.dedup();
+
+    let common: Vec<&str> = a_tokens
+        .iter()
+        .filter(|t| b_tokens.binary_search(t).is_ok())
+        .copied()
+        .collect();
+    let a_only: Vec<&str> = a_tokens
+        .iter()
+        .filter(|t| b_tokens.binary_search(t).is_err())
+        .copied()
+        .collect();
+    let b_only: Vec<&str> = b_tokens
+        .iter()
+        .filter(|t| a_tokens.binary_search(t).is_err())
+        .copied()
+        .collect();
+
+    let t0 = common.join(" ");
+    let t1 = [t0.as_str(), &a_only.join(" ")].join(" ").trim().to_owned();
+    let t2 = [t0.as_str(), &b_only.join(" ")].join(" ").trim().to_owned();
+
+    if common.is_empty() {
+        return levenshtein_ratio(&t1, &t2);
+    }
+
+    levenshtein_ratio(&t0, &t1)
+        .max(levenshtein_ratio(&t0, &t2))
+        .max(levenshtein_ratio(&t1, &t2))
+}
+
+/// Score how likely two legal entity names refer to the same entity. Both names are passed
+/// through `normalize()` and the higher of the Jaro-Winkler and token-set scores is returned.
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let a = normalize(a);
+    let b = normalize(b);
+    jaro_winkler(&a, &b).max(token_set_ratio(&a, &b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_strips_legal_forms_and_diacritics() {
+        assert_eq!(normalize("  ACME, Inc. "), "acme");
+        assert_eq!(
+            normalize("Müller & Söhne GmbH & Co. KG"),
+            "muller and sohne"
+        );
+        assert_eq!(normalize("BANCO SANTANDER, S.A."), "banco santander");
+        assert_eq!(normalize("Moody's Corporation"), "moodys");
+        assert_eq!(normalize("Ørsted A/S"), "orsted");
+        // A name consisting only of a legal form is left alone.
+        assert_eq!(normalize("Limited"), "limited");
+    }
+
+    #[test]
+    fn jaro_winkler_reference_values() {
+        assert!((jaro_winkler("martha", "marhta") - 0.9611).abs() < 0.0001);
+        assert!((jaro_winkler("dixon", "dicksonx") - 0.8133).abs() < 0.0001);
+        assert_eq!(jaro_winkler("", ""), 1.0);
+        assert_eq!(jaro_winkler("abc", ""), 0.0);
+        assert_eq!(jaro_winkler("abc", "xyz"), 0.0);
+    }
+
+    #[test]
+    fn token_set_ignores_order_and_extra_words() {
+        assert_eq!(token_set_ratio("bank of america", "america bank of"), 1.0);
+        assert_eq!(token_set_ratio("acme", "acme holdings"), 1.0);
+        assert!(token_set_ratio("acme", "globex") < 0.5);
+    }
+
+    #[test]
+    fn similarity_matches_equivalent_names() {
+        assert_eq!(similarity("Apple Inc.", "APPLE INC"), 1.0);
+        assert!(similarity("Credit Suisse AG", "Crédit Suisse") > 0.99);
+        assert!(similarity("Goldman Sachs", "Morgan Stanley") < 0.8);
+    }
+}
@@ -0,0 +1,176 @@
+#![warn(missing_docs)]
+//! # lei::near_duplicates
+//!
+//! Detection of values in a dataset that are almost, but not exactly, the same LEI. Two codes
+//! that share a _Payload_ but not _Check Digits_, or that differ in a single _Payload_
+//! character, are rarely distinct entities; far more often one is a corrupted copy of the other.
+//!
+//! ```
+//! use lei::near_duplicates::{find, NearDuplicateKind};
+//!
+//! let found = find(["YZ83GD8L7GG84979J516", "YZ83GD8L7GG84979J561", "549300IYKILIU506KA05"]);
+//! assert_eq!(found.len(), 1);
+//! assert_eq!(found[0].kind, NearDuplicateKind::CheckDigitsOnly);
+//! assert_eq!(found[0].suggested.unwrap().to_string(), "YZ83GD8L7GG84979J516");
+//! ```
+
+use std::collections::{BTreeSet, HashMap};
+
+use crate::LEI;
+
+/// How the two values of a near-duplicate pair differ.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NearDuplicateKind {
+    /// The _Payloads_ are the same and only the _Check Digits_ differ.
+    CheckDigitsOnly,
+    /// Exactly one _Payload_ character differs (and the _Check Digits_ are the same).
+    SingleCharacter {
+        /// The 0-based position of the differing character.
+        position: usize,
+    },
+}
+
+/// A pair of near-duplicate values.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NearDuplicate {
+    /// The lesser of the two values, after trimming and uppercasing.
+    pub a: String,
+    /// The greater of the two values, after trimming and uppercasing.
+    pub b: String,
+    /// How they differ.
+    pub kind: NearDuplicateKind,
+    /// The likely canonical value: the one of the pair that is a valid LEI, or for
+    /// `CheckDigitsOnly` pairs where neither is valid, the _Payload_ with correct _Check Digits_.
+    /// `None` when both or neither values are valid and no correction is possible.
+    pub suggested: Option<LEI>,
+}
+
+/// Find every near-duplicate pair among the given values. Values are trimmed and uppercased
+/// first; values that are not 20 ASCII characters long and exact repeats are ignored. The result
+/// is sorted by `a`, then `b`.
+pub fn find<I, S>(values: I) -> Vec<NearDuplicate>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let distinct: BTreeSet<String> = values
+        .into_iter()
+        .map(|v| v.as_ref().trim().to_ascii_uppercase())
+        .filter(|v| v.len() == 20 && v.is_ascii())
+        .collect();
+    let distinct: Vec<String> = distinct.into_iter().collect();
+
+    // Values sharing a key differ at most in the masked part of the key: the check digits for
+    // payload keys, or one payload position for masked keys.
+    let mut groups: HashMap<(Option<usize>, String), Vec<usize>> = HashMap::new();
+    for (i, v) in distinct.iter().enumerate() {
+        groups
+            .entry((None, v[..18].to_owned()))
+            .or_default()
+            .push(i);
+        for position in 0..18 {
+            let mut key = String::with_capacity(20);
+            key.push_str(&v[..position]);
+            key.push('*');
+            key.push_str(&v[position + 1..]);
+            groups.entry((Some(position), key)).or_default().push(i);
+        }
+    }
+
+    let mut found = Vec::new();
+    for ((position, _), members) in groups {
+        for (n, &i) in members.iter().enumerate() {
+            for &j in &members[n + 1..] {
+                let (a, b) = if distinct[i] < distinct[j] {
+                    (&distinct[i], &distinct[j])
+                } else {
+                    (&distinct[j], &distinct[i])
+                };
+                let kind = match position {
+                    None => NearDuplicateKind::CheckDigitsOnly,
+                    Some(position) => NearDuplicateKind::SingleCharacter { position },
+                };
+                found.push(NearDuplicate {
+                    a: a.clone(),
+                    b: b.clone(),
+                    kind,
+                    suggested: suggest(a, b, kind),
+                });
+            }
+        }
+    }
+
+    found.sort_by(|x, y| (&x.a, &x.b).cmp(&(&y.a, &y.b)));
+    found
+}
+
+fn suggest(a: &str, b: &str, kind: NearDuplicateKind) -> Option<LEI> {
+    match (crate::parse(a), crate::parse(b)) {
+        (Ok(lei), Err(_)) | (Err(_), Ok(lei)) => Some(lei),
+        (Err(_), Err(_)) if kind == NearDuplicateKind::CheckDigitsOnly => {
+            crate::build_from_payload(&a[..18]).ok()
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_check_digit_and_single_character_pairs() {
+        let found = find([
+            "YZ83GD8L7GG84979J516",
+            " yz83gd8l7gg84979j516 ", // Exact repeat after normalization
+            "YZ83GD8L7GG84979J599",
+            "YZ83GD8L7GG8497OJ516", // Letter O in place of a 9
+            "549300IYKILIU506KA05",
+            "549300IYKILIU506KA0",  // Wrong length, ignored
+            "JJKC32MCHWDI71265Z00", // Two invalid values sharing a payload
+            "JJKC32MCHWDI71265Z01",
+        ]);
+
+        let summary: Vec<(&str, &str, NearDuplicateKind, Option<String>)> = found
+            .iter()
+            .map(|d| {
+                (
+                    d.a.as_str(),
+                    d.b.as_str(),
+                    d.kind,
+                    d.suggested.map(|l| l.to_string()),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "JJKC32MCHWDI71265Z00",
+                    "JJKC32MCHWDI71265Z01",
+                    NearDuplicateKind::CheckDigitsOnly,
+                    Some("JJKC32MCHWDI71265Z06".to_owned())
+                ),
+                (
+                    "YZ83GD8L7GG84979J516",
+                    "YZ83GD8L7GG84979J599",
+                    NearDuplicateKind::CheckDigitsOnly,
+                    Some("YZ83GD8L7GG84979J516".to_owned())
+                ),
+                (
+                    "YZ83GD8L7GG84979J516",
+                    "YZ83GD8L7GG8497OJ516",
+                    NearDuplicateKind::SingleCharacter { position: 15 },
+                    Some("YZ83GD8L7GG84979J516".to_owned())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn distinct_values_are_not_reported() {
+        assert!(find(["YZ83GD8L7GG84979J516", "549300IYKILIU506KA05"]).is_empty());
+        assert!(find(Vec::<String>::new()).is_empty());
+    }
+}
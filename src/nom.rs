@@ -0,0 +1,96 @@
+#![warn(missing_docs)]
+//! # lei::nom
+//!
+//! An LEI parser for use with the [nom](https://crates.io/crates/nom) parser combinator library.
+
+use nom::bytes::complete::take;
+use nom::combinator::map_res;
+use nom::error::{FromExternalError, ParseError};
+use nom::{IResult, Parser};
+
+use crate::{LEIError, LEI};
+
+/// Consume exactly 20 characters and validate them as an LEI with the strict `parse()`. On
+/// failure, the `LEIError` is passed to the error type through `FromExternalError`.
+///
+/// ```
+/// use nom::bytes::complete::tag;
+/// use nom::sequence::preceded;
+/// use nom::Parser;
+///
+/// let mut record = preceded(tag("LEI:"), lei::nom::lei::<nom::error::Error<&str>>);
+/// let (rest, lei) = record.parse("LEI:YZ83GD8L7GG84979J516;EUR").unwrap();
+/// assert_eq!(lei.to_string(), "YZ83GD8L7GG84979J516");
+/// assert_eq!(rest, ";EUR");
+/// ```
+pub fn lei<'a, E>(input: &'a str) -> IResult<&'a str, LEI, E>
+where
+    E: ParseError<&'a str> + FromExternalError<&'a str, LEIError>,
+{
+    map_res(take(20usize), crate::parse).parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CheckDigits;
+    use nom::error::{Error, ErrorKind};
+
+    #[test]
+    fn consumes_exactly_twenty_characters() {
+        let (rest, parsed) = lei::<Error<&str>>("YZ83GD8L7GG84979J516YZ83").unwrap();
+        assert_eq!(parsed.to_string(), "YZ83GD8L7GG84979J516");
+        assert_eq!(rest, "YZ83");
+    }
+
+    #[test]
+    fn rejects_invalid_and_short_input() {
+        assert_eq!(
+            lei::<Error<&str>>("YZ83GD8L7GG84979J517"),
+            Err(nom::Err::Error(Error::new(
+                "YZ83GD8L7GG84979J517",
+                ErrorKind::MapRes
+            )))
+        );
+        assert!(lei::<Error<&str>>("YZ83GD8L7GG84979J5").is_err());
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Custom {
+        Lei(LEIError),
+        Nom(ErrorKind),
+    }
+
+    impl ParseError<&str> for Custom {
+        fn from_error_kind(_: &str, kind: ErrorKind) -> Self {
+            Custom::Nom(kind)
+        }
+
+        fn append(_: &str, _: ErrorKind, other: Self) -> Self {
+            other
+        }
+    }
+
+    impl FromExternalError<&str, LEIError> for Custom {
+        fn from_external_error(_: &str, _: ErrorKind, e: LEIError) -> Self {
+            Custom::Lei(e)
+        }
+    }
+
+    #[test]
+    fn passes_lei_error_to_custom_error_types() {
+        assert_eq!(
+            lei::<Custom>("YZ83GD8L7GG84979J5"),
+            Err(nom::Err::Error(Custom::Nom(ErrorKind::Eof)))
+        );
+        assert_eq!(
+            lei::<Custom>("YZ83GD8L7GG84979J517"),
+            Err(nom::Err::Error(Custom::Lei(
+                LEIError::IncorrectCheckDigits {
+                    was: CheckDigits(*b"17"),
+                    expected: CheckDigits(*b"16")
+                }
+            )))
+        );
+    }
+}
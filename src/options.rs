@@ -0,0 +1,140 @@
+#![warn(missing_docs)]
+//! # lei::options
+//!
+//! Options controlling how `LEI::parse_with()` treats its input, for pipelines that need a
+//! different combination of strictness per source. `UncheckedLei::parse_with()` takes the same
+//! options but checks only the format, for pipelines that verify the _Check Digits_ separately.
+//!
+//! ```
+//! use lei::{ParseOptions, LEI};
+//!
+//! let options = ParseOptions::new()
+//!     .trim_whitespace(true)
+//!     .allow_lowercase(true)
+//!     .reject_reserved_check_digits(true);
+//!
+//! assert!(LEI::parse_with(" 549300iykiliu506ka05", &options).is_ok());
+//! assert!(LEI::parse_with("31570010000000045200", &options).is_err());
+//! ```
+
+use crate::{CheckDigits, LEIError};
+
+/// Options for `LEI::parse_with()`. The default is equivalent to `parse()`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ParseOptions {
+    pub(crate) allow_lowercase: bool,
+    pub(crate) trim_whitespace: bool,
+    pub(crate) reject_reserved_check_digits: bool,
+}
+
+impl ParseOptions {
+    /// Options equivalent to `parse()`.
+    pub fn new() -> ParseOptions {
+        ParseOptions::default()
+    }
+
+    /// Options equivalent to `parse_loose()`.
+    pub fn loose() -> ParseOptions {
+        ParseOptions::new()
+            .allow_lowercase(true)
+            .trim_whitespace(true)
+    }
+
+    /// Options equivalent to `parse_strict_iso()`.
+    pub fn strict_iso() -> ParseOptions {
+        ParseOptions::new().reject_reserved_check_digits(true)
+    }
+
+    /// Set whether lowercase letters are accepted (and converted to uppercase).
+    pub fn allow_lowercase(mut self, allow: bool) -> ParseOptions {
+        self.allow_lowercase = allow;
+        self
+    }
+
+    /// Set whether leading and trailing whitespace is removed before parsing.
+    pub fn trim_whitespace(mut self, trim: bool) -> ParseOptions {
+        self.trim_whitespace = trim;
+        self
+    }
+
+    /// Set whether the _Check Digits_ "00", "01" and "99", which The Standard reserves as never
+    /// valid, are rejected with `LEIError::ReservedCheckDigits`.
+    pub fn reject_reserved_check_digits(mut self, reject: bool) -> ParseOptions {
+        self.reject_reserved_check_digits = reject;
+        self
+    }
+
+    /// Trim and case-fold `value` as configured, returning its bytes if it is the right length.
+    pub(crate) fn normalize(&self, value: &str) -> Result<[u8; 20], LEIError> {
+        let value = if self.trim_whitespace {
+            value.trim()
+        } else {
+            value
+        };
+        let b = value.as_bytes();
+        if b.len() != 20 {
+            return Err(LEIError::InvalidLength { was: b.len() });
+        }
+
+        let mut bb = [0u8; 20];
+        bb.copy_from_slice(b);
+        if self.allow_lowercase {
+            bb.make_ascii_uppercase();
+        }
+        Ok(bb)
+    }
+
+    /// Reject `check_digits` if they are reserved and that is configured.
+    pub(crate) fn check_reserved(&self, check_digits: CheckDigits) -> Result<(), LEIError> {
+        if self.reject_reserved_check_digits && check_digits.is_reserved() {
+            return Err(LEIError::ReservedCheckDigits { was: check_digits });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{UncheckedLei, LEI};
+
+    #[test]
+    fn presets_match_parse_functions() {
+        for value in [
+            "549300IYKILIU506KA05",
+            " 549300iykiliu506ka05 ",
+            "549300IYKILIU506KA06",
+            "31570010000000045200",
+        ] {
+            assert_eq!(
+                LEI::parse_with(value, &ParseOptions::new()),
+                crate::parse(value)
+            );
+            assert_eq!(
+                LEI::parse_with(value, &ParseOptions::loose()),
+                crate::parse_loose(value)
+            );
+            assert_eq!(
+                LEI::parse_with(value, &ParseOptions::strict_iso()),
+                crate::parse_strict_iso(value)
+            );
+        }
+    }
+
+    #[test]
+    fn individual_knobs() {
+        let lowercase = ParseOptions::new().allow_lowercase(true);
+        assert!(LEI::parse_with("549300iykiliu506ka05", &lowercase).is_ok());
+        assert_eq!(
+            LEI::parse_with(" 549300iykiliu506ka05", &lowercase),
+            Err(LEIError::InvalidLength { was: 21 })
+        );
+
+        let unchecked = UncheckedLei::parse_with("549300iykiliu506ka06", &lowercase).unwrap();
+        assert_eq!(unchecked.check_digits(), "06");
+        assert_eq!(
+            UncheckedLei::parse_with("549300IYKILIU506KA0X", &lowercase),
+            Err(LEIError::InvalidCheckDigits { was: *b"0X" })
+        );
+    }
+}
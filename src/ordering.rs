@@ -0,0 +1,113 @@
+#![warn(missing_docs)]
+//! # lei::ordering
+//!
+//! Wrappers that order LEIs by one part before the others, for sorting, grouping and sharding
+//! datasets by _Entity ID_ or by _LOU ID_.
+//!
+//! `LEI`'s own `Ord` compares the full 20-character code, which already puts the _LOU ID_
+//! first; `ByLouId` names that ordering explicitly. `ByEntityId` compares the _Entity ID_ first,
+//! then the _LOU ID_ and _Check Digits_ to break ties, so both wrappers stay consistent with
+//! `Eq` and are safe to use as `BTreeMap` or `BTreeSet` keys.
+//!
+//! ```
+//! use std::collections::BTreeSet;
+//! use lei::ordering::ByEntityId;
+//!
+//! let set: BTreeSet<ByEntityId> = ["YZ83GD8L7GG84979J516", "549300IYKILIU506KA05"]
+//!     .into_iter()
+//!     .map(|s| ByEntityId(lei::parse(s).unwrap()))
+//!     .collect();
+//! let entity_ids: Vec<&str> = set.iter().map(|k| k.0.entity_id()).collect();
+//! assert_eq!(entity_ids, ["00IYKILIU506KA", "GD8L7GG84979J5"]);
+//! ```
+
+use std::cmp::Ordering;
+
+use crate::LEI;
+
+/// An `LEI` ordered by its _Entity ID_, then its _LOU ID_, then its _Check Digits_.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ByEntityId(pub LEI);
+
+impl Ord for ByEntityId {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp_by_entity_id(&other.0)
+    }
+}
+
+impl PartialOrd for ByEntityId {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<LEI> for ByEntityId {
+    fn from(lei: LEI) -> Self {
+        ByEntityId(lei)
+    }
+}
+
+/// An `LEI` ordered by its _LOU ID_, then its _Entity ID_, then its _Check Digits_. This is the
+/// same as `LEI`'s own ordering.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ByLouId(pub LEI);
+
+impl Ord for ByLouId {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl PartialOrd for ByLouId {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<LEI> for ByLouId {
+    fn from(lei: LEI) -> Self {
+        ByLouId(lei)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leis() -> Vec<LEI> {
+        [
+            "YZ83GD8L7GG84979J516",
+            "549300IYKILIU506KA05",
+            "5493002F3N6V3Z14SP04",
+            "315700LK78Z7C0WMIL03",
+        ]
+        .into_iter()
+        .map(|s| crate::parse(s).unwrap())
+        .collect()
+    }
+
+    #[test]
+    fn by_entity_id() {
+        let mut sorted: Vec<ByEntityId> = leis().into_iter().map(ByEntityId).collect();
+        sorted.sort();
+        let entity_ids: Vec<&str> = sorted.iter().map(|k| k.0.entity_id()).collect();
+        assert_eq!(
+            entity_ids,
+            [
+                "002F3N6V3Z14SP",
+                "00IYKILIU506KA",
+                "00LK78Z7C0WMIL",
+                "GD8L7GG84979J5"
+            ]
+        );
+    }
+
+    #[test]
+    fn by_lou_id() {
+        let mut sorted: Vec<ByLouId> = leis().into_iter().map(ByLouId).collect();
+        sorted.sort();
+        let mut expected = leis();
+        expected.sort();
+        assert_eq!(sorted.iter().map(|k| k.0).collect::<Vec<_>>(), expected);
+    }
+}
@@ -0,0 +1,199 @@
+#![warn(missing_docs)]
+//! # lei::parquet
+//!
+//! [Apache Parquet](https://crates.io/crates/parquet) helpers for LEI columns: writing a
+//! `LeiArray` with its Arrow extension type metadata, and validating a column of an existing
+//! file batch by batch, reporting the row of every invalid value.
+//!
+//! ```
+//! use lei::arrow::LeiArray;
+//! use lei::parquet::{validate_column, write_column};
+//!
+//! let leis: LeiArray = [lei::parse("YZ83GD8L7GG84979J516").unwrap()].into_iter().collect();
+//! let file = write_column(Vec::new(), "lei", &leis).unwrap();
+//!
+//! let invalid = validate_column(bytes::Bytes::from(file), "lei").unwrap();
+//! assert!(invalid.is_empty());
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::io::Write;
+use std::sync::Arc;
+
+use ::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use ::parquet::arrow::{ArrowWriter, ProjectionMask};
+use ::parquet::errors::ParquetError;
+use ::parquet::file::reader::ChunkReader;
+use arrow_array::{Array, ArrayRef, RecordBatch};
+use arrow_schema::{ArrowError, Schema};
+
+use crate::arrow::{LeiArray, LeiArrayError};
+use crate::LEIError;
+
+/// All the ways writing or validating a Parquet LEI column could fail, other than invalid
+/// values.
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum ParquetLeiError {
+    /// Reading or writing Parquet failed.
+    Parquet(ParquetError),
+    /// Building or decoding an Arrow record batch failed.
+    Arrow(ArrowError),
+    /// The file has no top-level column with the given name.
+    ColumnNotFound(String),
+    /// The column is not a string or binary column.
+    UnsupportedType(arrow_schema::DataType),
+}
+
+impl Display for ParquetLeiError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ParquetLeiError::Parquet(err) => write!(f, "Parquet error: {err}"),
+            ParquetLeiError::Arrow(err) => write!(f, "Arrow error: {err}"),
+            ParquetLeiError::ColumnNotFound(name) => write!(f, "column {name:?} not found"),
+            ParquetLeiError::UnsupportedType(data_type) => {
+                write!(f, "cannot read LEIs from a column of type {data_type}")
+            }
+        }
+    }
+}
+
+impl Error for ParquetLeiError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParquetLeiError::Parquet(err) => Some(err),
+            ParquetLeiError::Arrow(err) => Some(err),
+            ParquetLeiError::ColumnNotFound(_) | ParquetLeiError::UnsupportedType(_) => None,
+        }
+    }
+}
+
+impl From<ParquetError> for ParquetLeiError {
+    fn from(err: ParquetError) -> Self {
+        ParquetLeiError::Parquet(err)
+    }
+}
+
+impl From<ArrowError> for ParquetLeiError {
+    fn from(err: ArrowError) -> Self {
+        ParquetLeiError::Arrow(err)
+    }
+}
+
+/// Write `leis` to `writer` as a Parquet file with a single column named `name`, tagged with
+/// the LEI extension type so readers get a `FixedSizeBinary(20)` LEI column back. Returns the
+/// writer.
+pub fn write_column<W: Write + Send>(
+    writer: W,
+    name: &str,
+    leis: &LeiArray,
+) -> Result<W, ParquetLeiError> {
+    let field = crate::arrow::field(name, leis.as_fixed_size_binary().null_count() > 0);
+    let schema = Arc::new(Schema::new(vec![field]));
+    let column: ArrayRef = Arc::new(leis.as_fixed_size_binary().clone());
+    let batch = RecordBatch::try_new(schema.clone(), vec![column])?;
+
+    let mut writer = ArrowWriter::try_new(writer, schema, None)?;
+    writer.write(&batch)?;
+    Ok(writer.into_inner()?)
+}
+
+/// Read the column named `name` from a Parquet file, one record batch at a time, and return the
+/// 0-based row and error of every value that is not a valid LEI. Nulls are not reported. Only
+/// the one column is decoded; it may be a string, binary or fixed-size binary column.
+pub fn validate_column<R: ChunkReader + 'static>(
+    reader: R,
+    name: &str,
+) -> Result<Vec<(u64, LEIError)>, ParquetLeiError> {
+    let builder = ParquetRecordBatchReaderBuilder::try_new(reader)?;
+    let index = builder
+        .schema()
+        .index_of(name)
+        .map_err(|_| ParquetLeiError::ColumnNotFound(name.to_owned()))?;
+    let mask = ProjectionMask::roots(builder.parquet_schema(), [index]);
+
+    let mut invalid = Vec::new();
+    let mut row = 0u64;
+    for batch in builder.with_projection(mask).build()? {
+        let batch = batch?;
+        let column = batch.column(0);
+        let found = crate::arrow::invalid_values(column.as_ref()).map_err(|err| match err {
+            LeiArrayError::UnsupportedType(data_type) => {
+                ParquetLeiError::UnsupportedType(data_type)
+            }
+            LeiArrayError::Invalid { .. } => unreachable!("invalid_values() reports values"),
+        })?;
+        invalid.extend(found.into_iter().map(|(i, err)| (row + i as u64, err)));
+        row += batch.num_rows() as u64;
+    }
+    Ok(invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::parquet::file::properties::WriterProperties;
+    use arrow_array::StringArray;
+    use arrow_schema::Field;
+
+    fn string_file(values: Vec<Option<&str>>, batch_size: usize) -> ::bytes::Bytes {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("name", arrow_schema::DataType::Utf8, true),
+            Field::new("lei", arrow_schema::DataType::Utf8, true),
+        ]));
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(batch_size)
+            .build();
+        let mut writer = ArrowWriter::try_new(Vec::new(), schema.clone(), Some(props)).unwrap();
+        for chunk in values.chunks(batch_size) {
+            let names: ArrayRef = Arc::new(StringArray::from(vec!["x"; chunk.len()]));
+            let leis: ArrayRef = Arc::new(StringArray::from(chunk.to_vec()));
+            let batch = RecordBatch::try_new(schema.clone(), vec![names, leis]).unwrap();
+            writer.write(&batch).unwrap();
+        }
+        writer.into_inner().unwrap().into()
+    }
+
+    #[test]
+    fn reports_rows_across_batches() {
+        let file = string_file(
+            vec![
+                Some("YZ83GD8L7GG84979J516"),
+                None,
+                Some("bogus"),
+                Some("549300IYKILIU506KA05"),
+                Some("549300IYKILIU506KA06"),
+            ],
+            2,
+        );
+        let invalid = validate_column(file.clone(), "lei").unwrap();
+        let rows: Vec<u64> = invalid.iter().map(|(row, _)| *row).collect();
+        assert_eq!(rows, vec![2, 4]);
+        assert_eq!(invalid[0].1, LEIError::InvalidLength { was: 5 });
+
+        assert!(matches!(
+            validate_column(file, "missing"),
+            Err(ParquetLeiError::ColumnNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn written_columns_keep_the_extension_type() {
+        let leis: LeiArray = [Some(crate::parse("549300IYKILIU506KA05").unwrap()), None]
+            .into_iter()
+            .collect();
+        let file: ::bytes::Bytes = write_column(Vec::new(), "lei", &leis).unwrap().into();
+
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file.clone()).unwrap();
+        assert!(crate::arrow::is_lei_field(builder.schema().field(0)));
+        let batch = builder.build().unwrap().next().unwrap().unwrap();
+        assert_eq!(
+            LeiArray::try_from_array(batch.column(0).as_ref()).unwrap(),
+            leis
+        );
+
+        assert!(validate_column(file, "lei").unwrap().is_empty());
+    }
+}
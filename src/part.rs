@@ -0,0 +1,90 @@
+#![warn(missing_docs)]
+//! # lei::part
+//!
+//! The named parts of an LEI, for code that extracts fields generically rather than calling
+//! `lou_id()`, `entity_id()` and so on by name.
+//!
+//! ```
+//! use lei::Part;
+//!
+//! let lei = lei::parse("549300IYKILIU506KA05").unwrap();
+//! let fields: Vec<String> = Part::ALL
+//!     .iter()
+//!     .map(|&part| format!("{part}={}", lei.get(part)))
+//!     .collect();
+//! assert_eq!(
+//!     fields,
+//!     [
+//!         "LOU ID=5493",
+//!         "Entity ID=00IYKILIU506KA",
+//!         "Payload=549300IYKILIU506KA",
+//!         "Check Digits=05"
+//!     ]
+//! );
+//! ```
+
+use std::fmt;
+use std::ops::Range;
+
+use crate::LEI;
+
+/// One of the named parts of an LEI.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    /// The first four characters, identifying the issuing _LOU_.
+    LouId,
+    /// The 14 characters identifying the entity.
+    EntityId,
+    /// The _LOU ID_ and _Entity ID_ together: everything except the _Check Digits_.
+    Payload,
+    /// The last two characters.
+    CheckDigits,
+}
+
+impl Part {
+    /// Every part, in order of where it starts (and then by length).
+    pub const ALL: [Part; 4] = [
+        Part::LouId,
+        Part::EntityId,
+        Part::Payload,
+        Part::CheckDigits,
+    ];
+
+    /// The byte range of the part within an LEI.
+    pub fn range(&self) -> Range<usize> {
+        match self {
+            Part::LouId => LEI::LOU_ID_RANGE,
+            Part::EntityId => LEI::ENTITY_ID_RANGE,
+            Part::Payload => LEI::PAYLOAD_RANGE,
+            Part::CheckDigits => LEI::CHECK_DIGITS_RANGE,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Part::LouId => "LOU ID",
+            Part::EntityId => "Entity ID",
+            Part::Payload => "Payload",
+            Part::CheckDigits => "Check Digits",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_matches_named_accessors() {
+        let lei = crate::parse("YZ83GD8L7GG84979J516").unwrap();
+        assert_eq!(lei.get(Part::LouId), lei.lou_id());
+        assert_eq!(lei.get(Part::EntityId), lei.entity_id());
+        assert_eq!(lei.get(Part::Payload), lei.payload());
+        assert_eq!(lei.get(Part::CheckDigits), lei.check_digits());
+        for part in Part::ALL {
+            assert_eq!(lei.as_lei_str().get(part), lei.get(part));
+        }
+    }
+}
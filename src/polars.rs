@@ -0,0 +1,166 @@
+#![warn(missing_docs)]
+//! # lei::polars
+//!
+//! A [Polars](https://crates.io/crates/polars) expression namespace for String columns of LEIs.
+//! Import `LeiExprExt` and call `.lei()` on an expression to validate or decompose each value in
+//! a single pass over the column. Nulls stay null.
+//!
+//! ```
+//! use lei::polars::LeiExprExt;
+//! use polars::prelude::*;
+//!
+//! let df = df!("lei" => ["YZ83GD8L7GG84979J516", "549300IYKILIU506KA06", "bogus"]).unwrap();
+//! let out = df
+//!     .lazy()
+//!     .select([
+//!         col("lei").lei().is_valid().alias("valid"),
+//!         col("lei").lei().check_digits_ok().alias("check_digits_ok"),
+//!         col("lei").lei().lou_id().alias("lou_id"),
+//!     ])
+//!     .collect()
+//!     .unwrap();
+//!
+//! let valid: Vec<Option<bool>> = out.column("valid").unwrap().bool().unwrap().into_iter().collect();
+//! assert_eq!(valid, [Some(true), Some(false), Some(false)]);
+//! let check_digits_ok: Vec<Option<bool>> =
+//!     out.column("check_digits_ok").unwrap().bool().unwrap().into_iter().collect();
+//! assert_eq!(check_digits_ok, [Some(true), Some(false), None]);
+//! let lou_id: Vec<Option<&str>> = out.column("lou_id").unwrap().str().unwrap().into_iter().collect();
+//! assert_eq!(lou_id, [Some("YZ83"), None, None]);
+//! ```
+
+use ::polars::prelude::*;
+
+use crate::{LEIError, Part};
+
+/// Adds the `lei()` namespace to Polars expressions.
+pub trait LeiExprExt {
+    /// Treat the values of this String expression as LEIs.
+    fn lei(self) -> LeiNameSpace;
+}
+
+impl LeiExprExt for Expr {
+    fn lei(self) -> LeiNameSpace {
+        LeiNameSpace(self)
+    }
+}
+
+/// LEI operations on a String expression, returned by `LeiExprExt::lei()`. Values are checked
+/// with the same rules as `parse()`.
+#[derive(Clone, Debug)]
+pub struct LeiNameSpace(Expr);
+
+impl LeiNameSpace {
+    /// Whether each value is a valid LEI.
+    pub fn is_valid(self) -> Expr {
+        map_bool(self.0, |v| {
+            crate::parse_bytes_unrecorded(v.as_bytes()).is_ok()
+        })
+    }
+
+    /// Whether each value has the correct _Check Digits_ for its _Payload_. Null for values that
+    /// are not well-formed enough to compute them (wrong length or invalid characters).
+    pub fn check_digits_ok(self) -> Expr {
+        self.0.map(
+            |c| {
+                let ca = c.str()?;
+                let out: BooleanChunked = ca
+                    .iter()
+                    .map(|v| match crate::parse_bytes_unrecorded(v?.as_bytes()) {
+                        Ok(_) => Some(true),
+                        Err(LEIError::IncorrectCheckDigits { .. }) => Some(false),
+                        Err(_) => None,
+                    })
+                    .collect();
+                Ok(Some(out.with_name(ca.name().clone()).into_column()))
+            },
+            GetOutput::from_type(DataType::Boolean),
+        )
+    }
+
+    /// The _LOU ID_ of each value, or null if it is not a valid LEI.
+    pub fn lou_id(self) -> Expr {
+        map_part(self.0, Part::LouId)
+    }
+
+    /// The _Entity ID_ of each value, or null if it is not a valid LEI.
+    pub fn entity_id(self) -> Expr {
+        map_part(self.0, Part::EntityId)
+    }
+
+    /// The _Check Digits_ of each value, or null if it is not a valid LEI.
+    pub fn check_digits(self) -> Expr {
+        map_part(self.0, Part::CheckDigits)
+    }
+}
+
+/// Map each non-null value of a String expression to a boolean.
+fn map_bool(expr: Expr, f: fn(&str) -> bool) -> Expr {
+    expr.map(
+        move |c| {
+            let ca = c.str()?;
+            let out: BooleanChunked = ca.iter().map(|v| v.map(f)).collect();
+            Ok(Some(out.with_name(ca.name().clone()).into_column()))
+        },
+        GetOutput::from_type(DataType::Boolean),
+    )
+}
+
+/// Map each value of a String expression to one part of it, or null if it is not a valid LEI.
+fn map_part(expr: Expr, part: Part) -> Expr {
+    expr.map(
+        move |c| {
+            let ca = c.str()?;
+            let out: StringChunked = ca
+                .iter()
+                .map(|v| {
+                    let v = v?;
+                    crate::parse_bytes_unrecorded(v.as_bytes()).ok()?;
+                    Some(&v[part.range()])
+                })
+                .collect();
+            Ok(Some(out.with_name(ca.name().clone()).into_column()))
+        },
+        GetOutput::from_type(DataType::String),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn select(values: &[Option<&str>], expr: Expr) -> Column {
+        let df = DataFrame::new(vec![Column::new("lei".into(), values)]).unwrap();
+        let out = df.lazy().select([expr]).collect().unwrap();
+        out.get_columns()[0].clone()
+    }
+
+    #[test]
+    fn nulls_and_names_are_kept() {
+        let values = [
+            Some("YZ83GD8L7GG84979J516"),
+            None,
+            Some("yz83GD8L7GG84979J516"),
+        ];
+
+        let valid = select(&values, col("lei").lei().is_valid());
+        assert_eq!(valid.name().as_str(), "lei");
+        let valid: Vec<Option<bool>> = valid.bool().unwrap().into_iter().collect();
+        assert_eq!(valid, [Some(true), None, Some(false)]);
+
+        let entity_id = select(&values, col("lei").lei().entity_id());
+        let entity_id: Vec<Option<&str>> = entity_id.str().unwrap().into_iter().collect();
+        assert_eq!(entity_id, [Some("GD8L7GG84979J5"), None, None]);
+
+        let check_digits = select(&values, col("lei").lei().check_digits());
+        let check_digits: Vec<Option<&str>> = check_digits.str().unwrap().into_iter().collect();
+        assert_eq!(check_digits, [Some("16"), None, None]);
+    }
+
+    #[test]
+    fn non_string_columns_are_an_error() {
+        let df = DataFrame::new(vec![Column::new("lei".into(), [1i64, 2])]).unwrap();
+        let result = df.lazy().select([col("lei").lei().is_valid()]).collect();
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,95 @@
+#![warn(missing_docs)]
+//! # lei::postgres
+//!
+//! [postgres-types](https://crates.io/crates/postgres-types) `ToSql` and `FromSql`
+//! implementations for `LEI`, so LEIs can be bound as parameters and read from rows with
+//! `tokio-postgres`, `postgres` and pools built on them such as `deadpool-postgres`.
+//!
+//! An LEI is sent as text, and accepted for `TEXT`, `VARCHAR` and `CHAR(n)` (`bpchar`) columns.
+//! Reading validates the value with the strict `parse()`, after removing the trailing spaces
+//! Postgres pads `CHAR(n)` values with when `n` is more than 20.
+//!
+//! ```
+//! use bytes::BytesMut;
+//! use postgres_types::{FromSql, ToSql, Type};
+//!
+//! let lei = lei::parse("YZ83GD8L7GG84979J516").unwrap();
+//! let mut buf = BytesMut::new();
+//! lei.to_sql_checked(&Type::BPCHAR, &mut buf).unwrap();
+//! assert_eq!(&buf[..], b"YZ83GD8L7GG84979J516");
+//! assert_eq!(lei::LEI::from_sql(&Type::BPCHAR, &buf).unwrap(), lei);
+//! ```
+
+use std::error::Error;
+
+use ::bytes::BytesMut;
+use ::postgres_types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
+
+use crate::LEI;
+
+/// Whether an LEI can be read from or written to a column of type `ty`.
+fn accepts(ty: &Type) -> bool {
+    matches!(*ty, Type::TEXT | Type::VARCHAR | Type::BPCHAR)
+}
+
+impl ToSql for LEI {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        <&str as ToSql>::to_sql(&self.as_str(), ty, out)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        accepts(ty)
+    }
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for LEI {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<LEI, Box<dyn Error + Sync + Send>> {
+        let value = <&str as FromSql>::from_sql(ty, raw)?;
+        let value = match *ty {
+            Type::BPCHAR => value.trim_end_matches(' '),
+            _ => value,
+        };
+        Ok(crate::parse(value)?)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        accepts(ty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LEIError;
+
+    #[test]
+    fn accepts_text_columns_only() {
+        for ty in [Type::TEXT, Type::VARCHAR, Type::BPCHAR] {
+            assert!(<LEI as ToSql>::accepts(&ty));
+            assert!(<LEI as FromSql>::accepts(&ty));
+        }
+        let lei = crate::parse("YZ83GD8L7GG84979J516").unwrap();
+        let mut buf = BytesMut::new();
+        assert!(lei.to_sql_checked(&Type::INT4, &mut buf).is_err());
+        assert!(!<LEI as FromSql>::accepts(&Type::BYTEA));
+    }
+
+    #[test]
+    fn from_sql_validates() {
+        let padded = b"549300IYKILIU506KA05     ";
+        assert!(LEI::from_sql(&Type::BPCHAR, padded).is_ok());
+        assert!(LEI::from_sql(&Type::TEXT, padded).is_err());
+
+        let err = LEI::from_sql(&Type::TEXT, b"549300IYKILIU506KA06").unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<LEIError>(),
+            Some(LEIError::IncorrectCheckDigits { .. })
+        ));
+    }
+}
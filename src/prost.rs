@@ -0,0 +1,223 @@
+#![warn(missing_docs)]
+//! # lei::prost
+//!
+//! Helpers for carrying LEIs in protobuf messages with [prost](https://crates.io/crates/prost),
+//! for example in gRPC services.
+//!
+//! Protobuf has no LEI type, so LEIs are usually declared as `string` fields. The recommended
+//! pattern is to convert each generated message to a domain type in one `TryFrom` impl, using
+//! `field()`, `optional_field()` and `repeated_field()` to validate the LEI fields. Their
+//! `FieldError` names the offending field, ready to be returned as an `INVALID_ARGUMENT` status:
+//!
+//! ```
+//! use lei::prost::{field, optional_field, FieldError};
+//! use lei::LEI;
+//!
+//! // Generated by prost from `message Entity { string lei = 1; string parent_lei = 2; }`.
+//! #[derive(Clone, PartialEq, prost::Message)]
+//! struct EntityMessage {
+//!     #[prost(string, tag = "1")]
+//!     lei: String,
+//!     #[prost(string, tag = "2")]
+//!     parent_lei: String,
+//! }
+//!
+//! struct Entity {
+//!     lei: LEI,
+//!     parent_lei: Option<LEI>,
+//! }
+//!
+//! impl TryFrom<EntityMessage> for Entity {
+//!     type Error = FieldError;
+//!
+//!     fn try_from(message: EntityMessage) -> Result<Entity, FieldError> {
+//!         Ok(Entity {
+//!             lei: field("lei", &message.lei)?,
+//!             parent_lei: optional_field("parent_lei", &message.parent_lei)?,
+//!         })
+//!     }
+//! }
+//!
+//! let message = EntityMessage {
+//!     lei: "YZ83GD8L7GG84979J516".to_owned(),
+//!     parent_lei: "549300IYKILIU506KA06".to_owned(),
+//! };
+//! let err = Entity::try_from(message).err().unwrap();
+//! assert_eq!(err.field(), "parent_lei");
+//! ```
+//!
+//! Alternatively, declare the field with the `LeiValue` wrapper message, whose definition is
+//! `PROTO`, and convert it with `LEI::try_from()`.
+
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::{LEIError, LEI};
+
+/// The protobuf definition of `LeiValue`, for inclusion in a `.proto` file or build script.
+pub const PROTO: &str = r#"syntax = "proto3";
+
+package lei;
+
+// A Legal Entity Identifier (ISO 17442), as its 20 characters.
+message LeiValue {
+  string value = 1;
+}
+"#;
+
+/// A wrapper message for an LEI, like the well-known `google.protobuf.StringValue`. See
+/// `PROTO` for its definition. The value is not validated until it is converted to an `LEI`.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct LeiValue {
+    /// The 20 characters of the LEI.
+    #[prost(string, tag = "1")]
+    pub value: String,
+}
+
+impl From<LEI> for LeiValue {
+    fn from(lei: LEI) -> LeiValue {
+        LeiValue {
+            value: lei.to_string(),
+        }
+    }
+}
+
+/// Strict conversion, equivalent to `parse()`.
+impl TryFrom<&LeiValue> for LEI {
+    type Error = LEIError;
+
+    fn try_from(value: &LeiValue) -> Result<LEI, LEIError> {
+        LEI::try_from(value.value.as_str())
+    }
+}
+
+/// Strict conversion, equivalent to `parse()`.
+impl TryFrom<LeiValue> for LEI {
+    type Error = LEIError;
+
+    fn try_from(value: LeiValue) -> Result<LEI, LEIError> {
+        LEI::try_from(&value)
+    }
+}
+
+/// An invalid LEI in a field of a protobuf message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldError {
+    field: &'static str,
+    index: Option<usize>,
+    error: LEIError,
+}
+
+impl FieldError {
+    /// The name of the field.
+    pub fn field(&self) -> &'static str {
+        self.field
+    }
+
+    /// The index of the value, for repeated fields.
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    /// What is wrong with the value.
+    pub fn error(&self) -> &LEIError {
+        &self.error
+    }
+}
+
+impl Display for FieldError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.index {
+            Some(index) => write!(f, "invalid LEI in field {}[{index}]: ", self.field)?,
+            None => write!(f, "invalid LEI in field {}: ", self.field)?,
+        }
+        write!(f, "{}", self.error)
+    }
+}
+
+impl Error for FieldError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Validate a required LEI `string` field with `parse()`. An empty string, which is what
+/// protobuf decodes a missing field as, is reported as an invalid length.
+pub fn field(name: &'static str, value: &str) -> Result<LEI, FieldError> {
+    LEI::try_from(value).map_err(|error| FieldError {
+        field: name,
+        index: None,
+        error,
+    })
+}
+
+/// Validate an optional LEI `string` field with `parse()`, treating the empty string as absent.
+pub fn optional_field(name: &'static str, value: &str) -> Result<Option<LEI>, FieldError> {
+    if value.is_empty() {
+        return Ok(None);
+    }
+    field(name, value).map(Some)
+}
+
+/// Validate a repeated LEI `string` field with `parse()`, stopping at the first invalid value.
+pub fn repeated_field<S: AsRef<str>>(
+    name: &'static str,
+    values: &[S],
+) -> Result<Vec<LEI>, FieldError> {
+    values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            LEI::try_from(value.as_ref()).map_err(|error| FieldError {
+                field: name,
+                index: Some(index),
+                error,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::prost::Message;
+
+    #[test]
+    fn wrapper_message_round_trips() {
+        let lei = crate::parse("549300IYKILIU506KA05").unwrap();
+        let bytes = LeiValue::from(lei).encode_to_vec();
+        assert_eq!(&bytes[..2], &[0x0a, 20]);
+        let decoded = LeiValue::decode(bytes.as_slice()).unwrap();
+        assert_eq!(LEI::try_from(decoded), Ok(lei));
+
+        let invalid = LeiValue {
+            value: "549300IYKILIU506KA06".to_owned(),
+        };
+        assert!(matches!(
+            LEI::try_from(&invalid),
+            Err(LEIError::IncorrectCheckDigits { .. })
+        ));
+    }
+
+    #[test]
+    fn field_helpers() {
+        let lei = crate::parse("549300IYKILIU506KA05").unwrap();
+        assert_eq!(field("lei", "549300IYKILIU506KA05"), Ok(lei));
+        assert_eq!(
+            field("lei", "").unwrap_err().error(),
+            &LEIError::InvalidLength { was: 0 }
+        );
+        assert_eq!(optional_field("lei", ""), Ok(None));
+        assert_eq!(optional_field("lei", "549300IYKILIU506KA05"), Ok(Some(lei)));
+
+        let values = vec!["549300IYKILIU506KA05".to_owned(), "bogus".to_owned()];
+        let err = repeated_field("members", &values).unwrap_err();
+        assert_eq!((err.field(), err.index()), ("members", Some(1)));
+        assert_eq!(
+            err.to_string(),
+            "invalid LEI in field members[1]: invalid length 5 bytes when expecting 20"
+        );
+        assert_eq!(repeated_field("members", &values[..1]), Ok(vec![lei]));
+    }
+}
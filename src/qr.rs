@@ -0,0 +1,220 @@
+#![warn(missing_docs)]
+//! # lei::qr
+//!
+//! A small text framing for carrying an LEI, and optionally a vLEI credential reference, in a QR
+//! code or other barcode, for example on onboarding documents and badges. This module produces
+//! and reads the payload text only; render it with any QR code library.
+//!
+//! The format is:
+//!
+//! ```text
+//! LEI:<lei>[;VLEI:<reference>];C:<crc>
+//! ```
+//!
+//! where `<crc>` is four uppercase hexadecimal digits of the CRC-16/CCITT-FALSE of everything
+//! before `;C:`. The checksum catches damage to the framing and the vLEI reference, which the
+//! LEI's own _Check Digits_ do not cover.
+//!
+//! ```
+//! use lei::qr::QrPayload;
+//!
+//! let lei = lei::parse("YZ83GD8L7GG84979J516").unwrap();
+//! let text = QrPayload::new(lei).encode();
+//! assert_eq!(text, "LEI:YZ83GD8L7GG84979J516;C:C3FC");
+//! assert_eq!(QrPayload::decode(&text).unwrap().lei, lei);
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::{LEIError, LEI};
+
+const PREFIX: &str = "LEI:";
+const VLEI: &str = ";VLEI:";
+const CHECKSUM: &str = ";C:";
+
+/// The contents of a QR payload.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct QrPayload {
+    /// The LEI.
+    pub lei: LEI,
+    /// An optional vLEI credential reference, such as a credential SAID or an OOBI URL.
+    pub vlei: Option<String>,
+}
+
+/// All the ways decoding a QR payload could fail.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum QrError {
+    /// The payload does not start with `LEI:`, is too short, or has no checksum.
+    Malformed,
+    /// The checksum does not match the contents.
+    ChecksumMismatch {
+        /// The checksum computed from the contents.
+        expected: u16,
+        /// The checksum found in the payload, if it was readable hexadecimal.
+        found: Option<u16>,
+    },
+    /// The framing is intact but the LEI is not valid.
+    Invalid(LEIError),
+}
+
+impl Display for QrError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            QrError::Malformed => write!(f, "malformed LEI QR payload"),
+            QrError::ChecksumMismatch {
+                expected,
+                found: Some(found),
+            } => write!(
+                f,
+                "checksum {found:04X} does not match expected {expected:04X}"
+            ),
+            QrError::ChecksumMismatch {
+                expected,
+                found: None,
+            } => write!(f, "unreadable checksum, expected {expected:04X}"),
+            QrError::Invalid(err) => write!(f, "invalid LEI in QR payload: {err}"),
+        }
+    }
+}
+
+impl Error for QrError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            QrError::Invalid(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// CRC-16/CCITT-FALSE: polynomial 0x1021, initial value 0xFFFF, no reflection, no final XOR.
+fn crc16(bytes: &[u8]) -> u16 {
+    let mut crc: u16 = 0xFFFF;
+    for &b in bytes {
+        crc ^= (b as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+impl QrPayload {
+    /// A payload carrying just an LEI.
+    pub fn new(lei: LEI) -> QrPayload {
+        QrPayload { lei, vlei: None }
+    }
+
+    /// Add a vLEI credential reference.
+    pub fn with_vlei(mut self, reference: &str) -> QrPayload {
+        self.vlei = Some(reference.to_owned());
+        self
+    }
+
+    /// Produce the payload text.
+    pub fn encode(&self) -> String {
+        let mut s = String::with_capacity(32);
+        s.push_str(PREFIX);
+        s.push_str(self.lei.as_str());
+        if let Some(vlei) = &self.vlei {
+            s.push_str(VLEI);
+            s.push_str(vlei);
+        }
+        let crc = crc16(s.as_bytes());
+        s.push_str(CHECKSUM);
+        s.push_str(&format!("{crc:04X}"));
+        s
+    }
+
+    /// Read payload text produced by `encode()`, verifying the checksum and the LEI. Surrounding
+    /// whitespace is ignored; the payload is otherwise case sensitive.
+    pub fn decode(text: &str) -> Result<QrPayload, QrError> {
+        let text = text.trim();
+        let (body, checksum) = text.rsplit_once(CHECKSUM).ok_or(QrError::Malformed)?;
+        if !body.starts_with(PREFIX) || body.len() < PREFIX.len() + 20 {
+            return Err(QrError::Malformed);
+        }
+
+        let expected = crc16(body.as_bytes());
+        let found = if checksum.len() == 4 {
+            u16::from_str_radix(checksum, 16).ok()
+        } else {
+            None
+        };
+        if found != Some(expected) {
+            return Err(QrError::ChecksumMismatch { expected, found });
+        }
+
+        let rest = &body[PREFIX.len()..];
+        let (lei, rest) = if rest.is_char_boundary(20) {
+            rest.split_at(20)
+        } else {
+            return Err(QrError::Malformed);
+        };
+        let vlei = match rest {
+            "" => None,
+            _ => Some(
+                rest.strip_prefix(VLEI)
+                    .ok_or(QrError::Malformed)?
+                    .to_owned(),
+            ),
+        };
+        let lei = crate::parse(lei).map_err(QrError::Invalid)?;
+        Ok(QrPayload { lei, vlei })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc_check_value() {
+        assert_eq!(crc16(b"123456789"), 0x29B1);
+    }
+
+    #[test]
+    fn round_trip_with_vlei() {
+        let lei = crate::parse("549300IYKILIU506KA05").unwrap();
+        let payload = QrPayload::new(lei).with_vlei("EBfdlu8R27Fbx-ehrqwImnK-8Cm79sqbAQ4MmvEAYqao");
+        let text = payload.encode();
+        assert!(text.starts_with(
+            "LEI:549300IYKILIU506KA05;VLEI:EBfdlu8R27Fbx-ehrqwImnK-8Cm79sqbAQ4MmvEAYqao;C:"
+        ));
+        assert_eq!(QrPayload::decode(&format!(" {text}\n")), Ok(payload));
+    }
+
+    #[test]
+    fn rejects_damage() {
+        let lei = crate::parse("YZ83GD8L7GG84979J516").unwrap();
+        let text = QrPayload::new(lei).with_vlei("abc").encode();
+
+        let damaged = text.replace("abc", "abd");
+        assert!(matches!(
+            QrPayload::decode(&damaged),
+            Err(QrError::ChecksumMismatch { found: Some(_), .. })
+        ));
+        assert_eq!(
+            QrPayload::decode("YZ83GD8L7GG84979J516"),
+            Err(QrError::Malformed)
+        );
+        assert!(matches!(
+            QrPayload::decode(&text[..text.len() - 1]),
+            Err(QrError::ChecksumMismatch { found: None, .. })
+        ));
+
+        // A framing-valid payload whose LEI has bad check digits.
+        let body = "LEI:YZ83GD8L7GG84979J517";
+        let forged = format!("{body};C:{:04X}", crc16(body.as_bytes()));
+        assert!(matches!(
+            QrPayload::decode(&forged),
+            Err(QrError::Invalid(LEIError::IncorrectCheckDigits { .. }))
+        ));
+    }
+}
@@ -0,0 +1,87 @@
+#![warn(missing_docs)]
+//! # lei::redis
+//!
+//! [redis](https://crates.io/crates/redis) `ToRedisArgs` and `FromRedisValue` implementations
+//! for `LEI`, so LEIs can be used directly as keys and values. An LEI is sent as its 20 ASCII
+//! bytes. Reading accepts a bulk or simple string and validates it with the strict
+//! `parse_bytes()`; anything else, or an invalid LEI, is a `TypeError`.
+//!
+//! ```
+//! use redis::{FromRedisValue, ToRedisArgs, Value};
+//!
+//! let lei = lei::parse("YZ83GD8L7GG84979J516").unwrap();
+//! assert_eq!(lei.to_redis_args(), vec![b"YZ83GD8L7GG84979J516".to_vec()]);
+//!
+//! let reply = Value::BulkString(b"YZ83GD8L7GG84979J516".to_vec());
+//! assert_eq!(lei::LEI::from_redis_value(&reply).unwrap(), lei);
+//! ```
+
+use ::redis::{ErrorKind, FromRedisValue, RedisResult, RedisWrite, ToRedisArgs, Value};
+
+use crate::LEI;
+
+impl ToRedisArgs for LEI {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        out.write_arg(self.as_bytes())
+    }
+}
+
+impl FromRedisValue for LEI {
+    fn from_redis_value(v: &Value) -> RedisResult<LEI> {
+        let bytes = match v {
+            Value::BulkString(b) => b.as_slice(),
+            Value::SimpleString(s) => s.as_bytes(),
+            other => {
+                return Err((
+                    ErrorKind::TypeError,
+                    "Response type not LEI compatible.",
+                    format!("{other:?}"),
+                )
+                    .into())
+            }
+        };
+        crate::parse_bytes(bytes).map_err(|err| {
+            (
+                ErrorKind::TypeError,
+                "Response was not a valid LEI.",
+                err.to_string(),
+            )
+                .into()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commands_carry_the_ascii_bytes() {
+        let lei = crate::parse("549300IYKILIU506KA05").unwrap();
+        let packed = ::redis::cmd("GET").arg(lei).get_packed_command();
+        assert_eq!(
+            packed,
+            b"*2\r\n$3\r\nGET\r\n$20\r\n549300IYKILIU506KA05\r\n".to_vec()
+        );
+    }
+
+    #[test]
+    fn replies_are_validated() {
+        let lei = crate::parse("549300IYKILIU506KA05").unwrap();
+        let simple = Value::SimpleString("549300IYKILIU506KA05".to_owned());
+        assert_eq!(LEI::from_redis_value(&simple).unwrap(), lei);
+        let nil: Option<LEI> = FromRedisValue::from_redis_value(&Value::Nil).unwrap();
+        assert_eq!(nil, None);
+
+        let err = LEI::from_redis_value(&Value::BulkString(b"549300IYKILIU506KA06".to_vec()))
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::TypeError);
+        assert!(err.to_string().contains("when expecting \"05\""), "{err}");
+
+        let err = LEI::from_redis_value(&Value::Int(1)).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::TypeError);
+    }
+}
@@ -0,0 +1,80 @@
+#![warn(missing_docs)]
+//! # lei::regex
+//!
+//! Helpers for turning [regex](https://crates.io/crates/regex) matches into checksum-verified
+//! LEIs, for scanners that find candidates with a pattern such as `LEI::PATTERN`.
+//!
+//! ```
+//! use lei::LEI;
+//! use regex::Regex;
+//!
+//! let re = Regex::new(&format!(r"LEI: (?<lei>{})", LEI::PATTERN)).unwrap();
+//! let caps = re.captures("Issuer LEI: YZ83GD8L7GG84979J516").unwrap();
+//! let lei = lei::regex::from_capture(&caps, "lei").unwrap().unwrap();
+//! assert_eq!(lei.to_string(), "YZ83GD8L7GG84979J516");
+//! ```
+
+use regex::{Captures, Match, Regex};
+
+use crate::{LEIError, LEI};
+
+/// Validate a match as an LEI, including its _Check Digits_.
+pub fn from_match(m: Match<'_>) -> Result<LEI, LEIError> {
+    crate::parse(m.as_str())
+}
+
+/// Validate the named capture group as an LEI. Returns `None` if the group did not participate
+/// in the match.
+pub fn from_capture(caps: &Captures<'_>, name: &str) -> Option<Result<LEI, LEIError>> {
+    caps.name(name).map(from_match)
+}
+
+/// Find every match of `re` in `haystack` and keep those that are valid LEIs. When `re` has
+/// capture groups, the first group is validated, and matches in which it did not participate
+/// are skipped; otherwise the whole match is validated.
+pub fn find_valid<'a>(re: &'a Regex, haystack: &'a str) -> impl Iterator<Item = LEI> + 'a {
+    let group = usize::from(re.captures_len() > 1);
+    re.captures_iter(haystack)
+        .filter_map(move |caps| from_match(caps.get(group)?).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pattern_matches_format_only() {
+        let re = Regex::new(&format!("^{}$", LEI::PATTERN)).unwrap();
+        assert!(re.is_match("YZ83GD8L7GG84979J516"));
+        assert!(re.is_match("YZ83GD8L7GG84979J517")); // Wrong check digits, right format
+        assert!(!re.is_match("yz83gd8l7gg84979j516"));
+        assert!(!re.is_match("YZ83GD8L7GG84979J5A6"));
+    }
+
+    #[test]
+    fn upgrades_matches_to_verified_leis() {
+        let text =
+            "parent YZ83GD8L7GG84979J516, typo YZ83GD8L7GG84979J517, child 549300IYKILIU506KA05";
+        let re = Regex::new(&format!(r"\b{}\b", LEI::PATTERN)).unwrap();
+
+        let results: Vec<Result<LEI, LEIError>> = re.find_iter(text).map(from_match).collect();
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_ok());
+        assert!(matches!(
+            results[1],
+            Err(LEIError::IncorrectCheckDigits { .. })
+        ));
+
+        let valid: Vec<String> = find_valid(&re, text).map(|l| l.to_string()).collect();
+        assert_eq!(valid, vec!["YZ83GD8L7GG84979J516", "549300IYKILIU506KA05"]);
+    }
+
+    #[test]
+    fn find_valid_skips_matches_without_the_first_group() {
+        // The second alternative matches a whole valid LEI without capturing it
+        let re = Regex::new(&format!(r"LEI: ({0})|{0}", LEI::PATTERN)).unwrap();
+        let text = "LEI: YZ83GD8L7GG84979J516, 549300IYKILIU506KA05";
+        let valid: Vec<String> = find_valid(&re, text).map(|l| l.to_string()).collect();
+        assert_eq!(valid, vec!["YZ83GD8L7GG84979J516"]);
+    }
+}
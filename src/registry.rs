@@ -0,0 +1,276 @@
+#![warn(missing_docs)]
+//! # lei::registry
+//!
+//! A pluggable source of information about _LOUs_, keyed by _LOU ID_, for checking that an LEI
+//! was issued by a known and active issuer and for enriching LEIs with issuer details.
+//!
+//! The list of accredited LOUs changes over time, so this crate does not compile one in.
+//! Instead, implement `LouRegistry` over whatever source is current for you, or load a
+//! `MemoryRegistry` at run time (from CSV with the `csv` feature).
+//!
+//! ```
+//! use lei::registry::{LouInfo, LouRegistry, LouStatus, MemoryRegistry};
+//!
+//! let registry: MemoryRegistry = [LouInfo::new("5493", "Example LOU", LouStatus::Active)]
+//!     .into_iter()
+//!     .collect();
+//!
+//! let lei = lei::parse("549300IYKILIU506KA05").unwrap();
+//! assert_eq!(registry.issuer(&lei).unwrap().name, "Example LOU");
+//! assert!(registry.is_active_issuer(&lei));
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::LEI;
+
+/// The accreditation status of a _LOU_.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum LouStatus {
+    /// The LOU is accredited and issuing LEIs.
+    Active,
+    /// The LOU no longer issues LEIs. LEIs it issued remain valid identifiers, but are usually
+    /// transferred to another LOU for management.
+    Inactive,
+    /// Any other status reported by the source, verbatim.
+    Other(String),
+}
+
+impl LouStatus {
+    /// Interpret a status string, ignoring case and surrounding whitespace. Anything other than
+    /// "active" or "inactive" is kept as `Other`.
+    pub fn parse(s: &str) -> LouStatus {
+        let t = s.trim();
+        if t.eq_ignore_ascii_case("active") {
+            LouStatus::Active
+        } else if t.eq_ignore_ascii_case("inactive") {
+            LouStatus::Inactive
+        } else {
+            LouStatus::Other(t.to_owned())
+        }
+    }
+}
+
+impl Display for LouStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            LouStatus::Active => f.write_str("Active"),
+            LouStatus::Inactive => f.write_str("Inactive"),
+            LouStatus::Other(s) => f.write_str(s),
+        }
+    }
+}
+
+/// What a registry knows about one _LOU_.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LouInfo {
+    /// The four-character _LOU ID_ that prefixes the LEIs the LOU issues.
+    pub lou_id: String,
+    /// The name of the LOU.
+    pub name: String,
+    /// The accreditation status of the LOU.
+    pub status: LouStatus,
+}
+
+impl LouInfo {
+    /// Create a new entry.
+    pub fn new(lou_id: &str, name: &str, status: LouStatus) -> LouInfo {
+        LouInfo {
+            lou_id: lou_id.to_owned(),
+            name: name.to_owned(),
+            status,
+        }
+    }
+}
+
+/// A source of _LOU_ information, looked up by _LOU ID_.
+pub trait LouRegistry {
+    /// Look up the LOU with the given _LOU ID_.
+    fn lookup(&self, lou_id: &str) -> Option<&LouInfo>;
+
+    /// Look up the LOU that issued `lei`.
+    fn issuer(&self, lei: &LEI) -> Option<&LouInfo> {
+        self.lookup(lei.lou_id())
+    }
+
+    /// Return true if `lei` was issued by a LOU the registry knows and reports as active.
+    fn is_active_issuer(&self, lei: &LEI) -> bool {
+        matches!(self.issuer(lei), Some(info) if info.status == LouStatus::Active)
+    }
+}
+
+impl<R: LouRegistry + ?Sized> LouRegistry for &R {
+    fn lookup(&self, lou_id: &str) -> Option<&LouInfo> {
+        (**self).lookup(lou_id)
+    }
+}
+
+impl<R: LouRegistry + ?Sized> LouRegistry for Box<R> {
+    fn lookup(&self, lou_id: &str) -> Option<&LouInfo> {
+        (**self).lookup(lou_id)
+    }
+}
+
+/// A `LouRegistry` held in memory, built at run time.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MemoryRegistry {
+    entries: HashMap<String, LouInfo>,
+}
+
+impl MemoryRegistry {
+    /// Create an empty registry.
+    pub fn new() -> MemoryRegistry {
+        MemoryRegistry::default()
+    }
+
+    /// Add an entry, returning the one it replaced, if any.
+    pub fn insert(&mut self, info: LouInfo) -> Option<LouInfo> {
+        self.entries.insert(info.lou_id.clone(), info)
+    }
+
+    /// The number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Return true if there are no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Load a registry from CSV with a header row. The columns named `lou_id`, `name` and
+    /// `status` (matched ignoring case and surrounding whitespace) are required and any others
+    /// are ignored. Each `lou_id` must be four alphanumerics (in either case); a row with any
+    /// other value is an error rather than an entry that can never match.
+    #[cfg(feature = "csv")]
+    pub fn from_csv<R: std::io::Read>(reader: R) -> Result<MemoryRegistry, ::csv::Error> {
+        use std::io;
+
+        let mut reader = ::csv::Reader::from_reader(reader);
+        let headers = reader.headers()?.clone();
+        let find = |name: &str| {
+            headers
+                .iter()
+                .position(|h| h.trim().eq_ignore_ascii_case(name))
+        };
+        let invalid = |message: String| {
+            ::csv::Error::from(io::Error::new(io::ErrorKind::InvalidData, message))
+        };
+        let missing = |name: &str| invalid(format!("missing {name} column"));
+        let lou_id = find("lou_id").ok_or_else(|| missing("lou_id"))?;
+        let name = find("name").ok_or_else(|| missing("name"))?;
+        let status = find("status").ok_or_else(|| missing("status"))?;
+
+        let mut registry = MemoryRegistry::new();
+        for record in reader.records() {
+            let record = record?;
+            let value = record.get(lou_id).unwrap_or("").trim().to_ascii_uppercase();
+            let valid = value.len() == 4 && crate::validate_lou_id_format(value.as_bytes()).is_ok();
+            if !valid {
+                let line = record.position().map_or(0, |p| p.line());
+                return Err(invalid(format!("line {line}: invalid LOU ID {value:?}")));
+            }
+            registry.insert(LouInfo {
+                lou_id: value,
+                name: record.get(name).unwrap_or("").trim().to_owned(),
+                status: LouStatus::parse(record.get(status).unwrap_or("")),
+            });
+        }
+        Ok(registry)
+    }
+}
+
+impl LouRegistry for MemoryRegistry {
+    fn lookup(&self, lou_id: &str) -> Option<&LouInfo> {
+        self.entries.get(lou_id)
+    }
+}
+
+impl FromIterator<LouInfo> for MemoryRegistry {
+    fn from_iter<I: IntoIterator<Item = LouInfo>>(iter: I) -> Self {
+        let mut registry = MemoryRegistry::new();
+        registry.extend(iter);
+        registry
+    }
+}
+
+impl Extend<LouInfo> for MemoryRegistry {
+    fn extend<I: IntoIterator<Item = LouInfo>>(&mut self, iter: I) {
+        for info in iter {
+            self.insert(info);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> MemoryRegistry {
+        [
+            LouInfo::new("5493", "Active LOU", LouStatus::Active),
+            LouInfo::new("JJKC", "Retired LOU", LouStatus::Inactive),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn lookup_and_status() {
+        let registry = registry();
+        assert_eq!(registry.len(), 2);
+
+        let active = crate::parse("549300IYKILIU506KA05").unwrap();
+        let inactive = crate::parse("JJKC32MCHWDI71265Z06").unwrap();
+        let unknown = crate::parse("YZ83GD8L7GG84979J516").unwrap();
+
+        assert!(registry.is_active_issuer(&active));
+        assert!(!registry.is_active_issuer(&inactive));
+        assert_eq!(registry.issuer(&inactive).unwrap().name, "Retired LOU");
+        assert_eq!(registry.issuer(&unknown), None);
+
+        let dynamic: Box<dyn LouRegistry> = Box::new(registry);
+        assert!(dynamic.is_active_issuer(&active));
+    }
+
+    #[test]
+    fn status_parsing() {
+        assert_eq!(LouStatus::parse(" ACTIVE "), LouStatus::Active);
+        assert_eq!(LouStatus::parse("Inactive"), LouStatus::Inactive);
+        assert_eq!(
+            LouStatus::parse("Suspended"),
+            LouStatus::Other("Suspended".to_owned())
+        );
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn from_csv() {
+        let data = "Name,LOU_ID,Status,Country\n\
+                    \"Example, Inc.\",5493,ACTIVE,US\n\
+                    Retired,jjkc,Inactive,DE\n";
+        let registry = MemoryRegistry::from_csv(data.as_bytes()).unwrap();
+        assert_eq!(registry.lookup("5493").unwrap().name, "Example, Inc.");
+        assert_eq!(registry.lookup("JJKC").unwrap().status, LouStatus::Inactive);
+
+        assert!(MemoryRegistry::from_csv("name\nx\n".as_bytes()).is_err());
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn from_csv_rejects_missing_status_and_bad_lou_ids() {
+        let err = MemoryRegistry::from_csv("lou_id,name\n5493,Example\n".as_bytes()).unwrap_err();
+        assert_eq!(err.to_string(), "missing status column");
+
+        let data = "lou_id,name,status\n5493,Example,Active\n,Blank,Active\n";
+        let err = MemoryRegistry::from_csv(data.as_bytes()).unwrap_err();
+        assert_eq!(err.to_string(), "line 3: invalid LOU ID \"\"");
+
+        let data = "lou_id,name,status\n549-,Example,Active\n";
+        assert!(MemoryRegistry::from_csv(data.as_bytes()).is_err());
+        let data = "lou_id,name,status\n54930,Example,Active\n";
+        assert!(MemoryRegistry::from_csv(data.as_bytes()).is_err());
+    }
+}
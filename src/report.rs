@@ -0,0 +1,226 @@
+#![warn(missing_docs)]
+//! # lei::report
+//!
+//! A validation report listing every problem with an input, for data-quality tooling that
+//! needs more than the first error `parse()` stops at.
+//!
+//! ```
+//! use lei::{LEIErrorKind, Part, LEI};
+//!
+//! // A lowercase LOU ID *and* incorrect Check Digits.
+//! let report = LEI::validate_report("yz83GD8L7GG84979J517");
+//! let kinds: Vec<LEIErrorKind> = report.violations.iter().map(|v| v.kind).collect();
+//! assert_eq!(
+//!     kinds,
+//!     [
+//!         LEIErrorKind::InvalidLouId,
+//!         LEIErrorKind::InvalidLouId,
+//!         LEIErrorKind::IncorrectCheckDigits
+//!     ]
+//! );
+//! assert_eq!(report.violations[1].part, Some(Part::LouId));
+//! assert_eq!(report.violations[1].span, 1..2);
+//! assert_eq!(report.violations[2].span, 18..20);
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+
+use crate::{CheckDigits, LEIError, LEIErrorKind, Part, LEI};
+
+/// One problem with an input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Violation {
+    /// The kind of problem, as `parse()` would report it.
+    pub kind: LEIErrorKind,
+    /// The part of the LEI the problem is in, or `None` if it concerns the whole input.
+    pub part: Option<Part>,
+    /// The byte range of the offending characters within the input.
+    pub span: Range<usize>,
+    /// A description of the problem.
+    pub message: String,
+}
+
+/// Every problem found with an input, in input order. See the module documentation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// The problems found.
+    pub violations: Vec<Violation>,
+}
+
+impl ValidationReport {
+    /// Return true if no problems were found, which is exactly when `parse()` succeeds.
+    pub fn is_valid(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Lists the messages of all the violations, separated by semicolons.
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_valid() {
+            return f.write_str("no problems found");
+        }
+        for (i, violation) in self.violations.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{violation}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ValidationReport {}
+
+pub(crate) fn validate_report(value: &str) -> ValidationReport {
+    let b = value.as_bytes();
+    let mut violations = Vec::new();
+
+    if b.len() != LEI::LENGTH {
+        let err = LEIError::InvalidLength { was: b.len() };
+        violations.push(Violation {
+            kind: err.kind(),
+            part: None,
+            span: 0..b.len(),
+            message: err.to_string(),
+        });
+        return ValidationReport { violations };
+    }
+
+    // Report every character that is wrong for its position.
+
+    for (i, c) in value.char_indices() {
+        let (part, kind, valid, expected) = if LEI::LOU_ID_RANGE.contains(&i) {
+            let valid = c.is_ascii_digit() || c.is_ascii_uppercase();
+            let expected = "an uppercase ASCII letter or digit";
+            (Part::LouId, LEIErrorKind::InvalidLouId, valid, expected)
+        } else if LEI::ENTITY_ID_RANGE.contains(&i) {
+            let valid = c.is_ascii_digit() || c.is_ascii_uppercase();
+            let expected = "an uppercase ASCII letter or digit";
+            (
+                Part::EntityId,
+                LEIErrorKind::InvalidEntityId,
+                valid,
+                expected,
+            )
+        } else {
+            let valid = c.is_ascii_digit();
+            let expected = "an ASCII digit";
+            (
+                Part::CheckDigits,
+                LEIErrorKind::InvalidCheckDigits,
+                valid,
+                expected,
+            )
+        };
+        if !valid {
+            violations.push(Violation {
+                kind,
+                part: Some(part),
+                span: i..i + c.len_utf8(),
+                message: format!("{c:?} at position {i} in the {part} is not {expected}"),
+            });
+        }
+    }
+
+    // If the only bad characters are lowercase letters, the Check Digits can still be verified
+    // against the uppercase Payload.
+
+    if b[LEI::PAYLOAD_RANGE].iter().all(u8::is_ascii_alphanumeric)
+        && b[LEI::CHECK_DIGITS_RANGE].iter().all(u8::is_ascii_digit)
+    {
+        let mut payload = [0u8; 18];
+        payload.copy_from_slice(&b[LEI::PAYLOAD_RANGE]);
+        payload.make_ascii_uppercase();
+        let expected = crate::compute_check_digits(&payload);
+        if !crate::check_digits_match(&b[LEI::CHECK_DIGITS_RANGE], expected) {
+            let mut was = [0u8; 2];
+            was.copy_from_slice(&b[LEI::CHECK_DIGITS_RANGE]);
+            let err = LEIError::IncorrectCheckDigits {
+                was: CheckDigits(was),
+                expected: CheckDigits(expected),
+            };
+            violations.push(Violation {
+                kind: err.kind(),
+                part: Some(Part::CheckDigits),
+                span: LEI::CHECK_DIGITS_RANGE,
+                message: err.to_string(),
+            });
+        }
+    }
+
+    ValidationReport { violations }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_input_has_no_violations() {
+        assert!(validate_report("549300IYKILIU506KA05").is_valid());
+    }
+
+    #[test]
+    fn wrong_length_is_reported_alone() {
+        let report = validate_report("549300iykiliu506");
+        assert_eq!(report.violations.len(), 1);
+        assert_eq!(report.violations[0].kind, LEIErrorKind::InvalidLength);
+        assert_eq!(report.violations[0].part, None);
+        assert_eq!(report.violations[0].span, 0..16);
+    }
+
+    #[test]
+    fn reports_every_bad_character() {
+        let report = validate_report("5493-0IYKILIU506K*0A");
+        let spans: Vec<Range<usize>> = report.violations.iter().map(|v| v.span.clone()).collect();
+        assert_eq!(spans, vec![4..5, 17..18, 19..20]);
+        assert_eq!(
+            report.violations[0].message,
+            "'-' at position 4 in the Entity ID is not an uppercase ASCII letter or digit"
+        );
+        assert_eq!(report.violations[2].part, Some(Part::CheckDigits));
+    }
+
+    #[test]
+    fn spans_cover_multibyte_characters() {
+        let report = validate_report("5493ÄIYKILIU506KA05");
+        assert_eq!(report.violations.len(), 1);
+        assert_eq!(report.violations[0].span, 4..6);
+    }
+
+    #[test]
+    fn agrees_with_parse() {
+        for value in [
+            "549300IYKILIU506KA05",
+            "549300IYKILIU506KA06",
+            "549300iykiliu506ka05",
+            "31570010000000045200",
+        ] {
+            assert_eq!(
+                validate_report(value).is_valid(),
+                crate::parse(value).is_ok()
+            );
+        }
+    }
+
+    #[test]
+    fn display_lists_messages() {
+        assert_eq!(
+            validate_report("549300IYKILIU506KA0x").to_string(),
+            "'x' at position 19 in the Check Digits is not an ASCII digit"
+        );
+        assert_eq!(
+            validate_report("549300IYKILIU506KA05").to_string(),
+            "no problems found"
+        );
+    }
+}
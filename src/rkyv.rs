@@ -0,0 +1,149 @@
+#![warn(missing_docs)]
+//! # lei::rkyv
+//!
+//! [rkyv](https://crates.io/crates/rkyv) zero-copy archiving for `LEI`.
+//!
+//! An LEI archives as an `ArchivedLEI`: its 20 ASCII bytes, with no padding or alignment
+//! requirement. When an archive is accessed with validation (`rkyv::access()`), each
+//! `ArchivedLEI` is checked with the same rules as `parse()`, so a memory-mapped archive from an
+//! untrusted source can be queried in place without deserializing it.
+//!
+//! ```
+//! use lei::rkyv::ArchivedLEI;
+//! use rkyv::rancor::Error;
+//!
+//! let leis = vec![lei::parse("YZ83GD8L7GG84979J516").unwrap()];
+//! let bytes = rkyv::to_bytes::<Error>(&leis).unwrap();
+//!
+//! let archived = rkyv::access::<rkyv::vec::ArchivedVec<ArchivedLEI>, Error>(&bytes).unwrap();
+//! assert_eq!(archived[0].lou_id(), "YZ83");
+//! assert_eq!(archived[0], leis[0]);
+//! ```
+
+use std::fmt;
+
+use ::rkyv::bytecheck::CheckBytes;
+use ::rkyv::rancor::{fail, Fallible, Source};
+use ::rkyv::traits::NoUndef;
+use ::rkyv::{Archive, Deserialize, Place, Portable, Serialize};
+
+use crate::{LeiStr, LEI};
+
+/// The archived form of an `LEI`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct ArchivedLEI([u8; 20]);
+
+unsafe impl Portable for ArchivedLEI {} // This is safe because ArchivedLEI is a transparent wrapper of a byte array
+
+unsafe impl NoUndef for ArchivedLEI {} // This is safe because a byte array has no padding
+
+unsafe impl<C: Fallible + ?Sized> CheckBytes<C> for ArchivedLEI
+where
+    C::Error: Source,
+{
+    unsafe fn check_bytes(value: *const Self, _: &mut C) -> Result<(), C::Error> {
+        let bytes = unsafe { &(*value).0 }; // This is safe because the caller guarantees value points to 20 initialized bytes
+        if let Err(err) = crate::parse_bytes_unrecorded(bytes) {
+            fail!(err);
+        }
+        Ok(())
+    }
+}
+
+impl ArchivedLEI {
+    /// Return the full 20-character LEI as a string slice.
+    pub fn as_str(&self) -> &str {
+        self.as_lei_str().as_str()
+    }
+
+    /// Return the archived LEI as a `&LeiStr`, for access to its parts.
+    pub fn as_lei_str(&self) -> &LeiStr {
+        let s = unsafe { std::str::from_utf8_unchecked(&self.0) }; // This is safe because archives are only accessed once validated or trusted, so this is a valid LEI
+        LeiStr::from_str_unchecked(s)
+    }
+
+    /// Copy the archived LEI into an owned `LEI`.
+    pub fn to_lei(&self) -> LEI {
+        LEI(self.0)
+    }
+}
+
+impl std::ops::Deref for ArchivedLEI {
+    type Target = LeiStr;
+
+    fn deref(&self) -> &LeiStr {
+        self.as_lei_str()
+    }
+}
+
+impl fmt::Display for ArchivedLEI {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl fmt::Debug for ArchivedLEI {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ArchivedLEI({})", self.as_str())
+    }
+}
+
+impl PartialEq<LEI> for ArchivedLEI {
+    fn eq(&self, other: &LEI) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl PartialEq<ArchivedLEI> for LEI {
+    fn eq(&self, other: &ArchivedLEI) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Archive for LEI {
+    type Archived = ArchivedLEI;
+    type Resolver = ();
+
+    fn resolve(&self, _: (), out: Place<ArchivedLEI>) {
+        out.write(ArchivedLEI(self.0));
+    }
+}
+
+impl<S: Fallible + ?Sized> Serialize<S> for LEI {
+    fn serialize(&self, _: &mut S) -> Result<(), S::Error> {
+        Ok(())
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<LEI, D> for ArchivedLEI {
+    fn deserialize(&self, _: &mut D) -> Result<LEI, D::Error> {
+        Ok(self.to_lei())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::rkyv::rancor::Error;
+
+    #[test]
+    fn round_trip() {
+        let lei = crate::parse("549300IYKILIU506KA05").unwrap();
+        let bytes = ::rkyv::to_bytes::<Error>(&lei).unwrap();
+        assert_eq!(&bytes[..], lei.as_bytes());
+
+        let archived = ::rkyv::access::<ArchivedLEI, Error>(&bytes).unwrap();
+        assert_eq!(archived.entity_id(), "00IYKILIU506KA");
+        assert_eq!(format!("{archived:?}"), "ArchivedLEI(549300IYKILIU506KA05)");
+        assert_eq!(::rkyv::deserialize::<LEI, Error>(archived).unwrap(), lei);
+    }
+
+    #[test]
+    fn validation_rejects_invalid_bytes() {
+        let mut bytes = ::rkyv::util::AlignedVec::<16>::new();
+        bytes.extend_from_slice(b"549300IYKILIU506KA06");
+        let err = ::rkyv::access::<ArchivedLEI, Error>(&bytes).unwrap_err();
+        assert!(err.to_string().contains("incorrect check digits"), "{err}");
+    }
+}
@@ -0,0 +1,251 @@
+#![warn(missing_docs)]
+//! # lei::rules
+//!
+//! A pipeline of business rules evaluated after structural validation, so that policy such as
+//! "the issuing LOU must be on our allowlist" is written once and applied everywhere.
+//!
+//! Each input is first parsed (strictly by default, or loosely); rules run only on inputs that
+//! are valid LEIs, and every rule runs, so the result lists all of the findings at once.
+//!
+//! ```
+//! use lei::rules::{lou_allowlist, RulePipeline};
+//!
+//! let pipeline = RulePipeline::new()
+//!     .with(lou_allowlist(["5493", "YZ83"]))
+//!     .rule("not-test-entity", |lei| {
+//!         if lei.entity_id().starts_with("00TEST") {
+//!             Err("test entities are not accepted".to_owned())
+//!         } else {
+//!             Ok(())
+//!         }
+//!     });
+//!
+//! assert!(pipeline.check("549300IYKILIU506KA05").passed());
+//!
+//! let result = pipeline.check("JJKC32MCHWDI71265Z06");
+//! assert!(!result.passed());
+//! assert_eq!(result.findings[0].rule, "lou-allowlist");
+//! ```
+
+use std::collections::HashSet;
+
+use crate::registry::{LouRegistry, LouStatus};
+use crate::{LEIError, LEI};
+
+/// A business rule about a valid LEI.
+pub trait Rule {
+    /// A short, stable name for the rule, used in findings.
+    fn name(&self) -> &str;
+
+    /// Check `lei`, returning a description of the violation if it breaks the rule.
+    fn check(&self, lei: &LEI) -> Result<(), String>;
+}
+
+/// A rule made from a name and a closure, as created by `RulePipeline::rule()`.
+pub struct FnRule<F> {
+    name: String,
+    f: F,
+}
+
+impl<F: Fn(&LEI) -> Result<(), String>> FnRule<F> {
+    /// Create a rule from a name and a closure.
+    pub fn new(name: &str, f: F) -> FnRule<F> {
+        FnRule {
+            name: name.to_owned(),
+            f,
+        }
+    }
+}
+
+impl<F: Fn(&LEI) -> Result<(), String>> Rule for FnRule<F> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn check(&self, lei: &LEI) -> Result<(), String> {
+        (self.f)(lei)
+    }
+}
+
+/// A rule requiring the _LOU ID_ to be one of an allowed set, named `lou-allowlist`.
+pub fn lou_allowlist<I, S>(lou_ids: I) -> impl Rule
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let allowed: HashSet<String> = lou_ids.into_iter().map(|s| s.as_ref().to_owned()).collect();
+    FnRule::new("lou-allowlist", move |lei: &LEI| {
+        if allowed.contains(lei.lou_id()) {
+            Ok(())
+        } else {
+            Err(format!("LOU {} is not on the allowlist", lei.lou_id()))
+        }
+    })
+}
+
+/// A rule requiring the issuing _LOU_ to be known to `registry` and active, named
+/// `active-issuer`.
+pub fn active_issuer<R: LouRegistry>(registry: R) -> impl Rule {
+    FnRule::new("active-issuer", move |lei: &LEI| {
+        match registry.issuer(lei) {
+            None => Err(format!("LOU {} is not in the registry", lei.lou_id())),
+            Some(info) if info.status == LouStatus::Active => Ok(()),
+            Some(info) => Err(format!("LOU {} is {}", lei.lou_id(), info.status)),
+        }
+    })
+}
+
+/// One rule violation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Finding {
+    /// The name of the rule.
+    pub rule: String,
+    /// What is wrong.
+    pub message: String,
+}
+
+/// The result of running the pipeline on one input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RuleResult {
+    /// The outcome of structural validation.
+    pub lei: Result<LEI, LEIError>,
+    /// The violations found by the rules, in the order the rules were added. Empty if
+    /// structural validation failed, since the rules did not run.
+    pub findings: Vec<Finding>,
+}
+
+impl RuleResult {
+    /// Return true if the input is a valid LEI that passed every rule.
+    pub fn passed(&self) -> bool {
+        self.lei.is_ok() && self.findings.is_empty()
+    }
+}
+
+/// An ordered set of rules. It is `Send` and `Sync`, so one pipeline can be shared across
+/// threads, for example in the state of a web application.
+#[derive(Default)]
+pub struct RulePipeline {
+    rules: Vec<Box<dyn Rule + Send + Sync>>,
+    loose: bool,
+}
+
+impl RulePipeline {
+    /// Create an empty pipeline that parses inputs strictly.
+    pub fn new() -> RulePipeline {
+        RulePipeline::default()
+    }
+
+    /// Add a rule.
+    pub fn with<R: Rule + Send + Sync + 'static>(mut self, rule: R) -> RulePipeline {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    /// Add a rule made from a name and a closure.
+    pub fn rule<F>(self, name: &str, f: F) -> RulePipeline
+    where
+        F: Fn(&LEI) -> Result<(), String> + Send + Sync + 'static,
+    {
+        self.with(FnRule::new(name, f))
+    }
+
+    /// Parse inputs with `parse_loose()` rather than `parse()`.
+    pub fn loose(mut self, loose: bool) -> RulePipeline {
+        self.loose = loose;
+        self
+    }
+
+    /// The number of rules.
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Return true if there are no rules.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Validate `value` and, if it is a valid LEI, run every rule on it.
+    pub fn check(&self, value: &str) -> RuleResult {
+        let lei = if self.loose {
+            crate::parse_loose(value)
+        } else {
+            crate::parse(value)
+        };
+        let findings = match &lei {
+            Ok(lei) => self.check_lei(lei),
+            Err(_) => Vec::new(),
+        };
+        RuleResult { lei, findings }
+    }
+
+    /// Run every rule on an already-validated LEI.
+    pub fn check_lei(&self, lei: &LEI) -> Vec<Finding> {
+        self.rules
+            .iter()
+            .filter_map(|rule| {
+                rule.check(lei).err().map(|message| Finding {
+                    rule: rule.name().to_owned(),
+                    message,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::{LouInfo, MemoryRegistry};
+
+    #[test]
+    fn collects_every_finding() {
+        let registry: MemoryRegistry = [
+            LouInfo::new("5493", "Active", LouStatus::Active),
+            LouInfo::new("JJKC", "Retired", LouStatus::Inactive),
+        ]
+        .into_iter()
+        .collect();
+        let pipeline = RulePipeline::new()
+            .with(lou_allowlist(["5493"]))
+            .with(active_issuer(registry))
+            .rule("never", |_| Err("always fails".to_owned()));
+        assert_eq!(pipeline.len(), 3);
+
+        let result = pipeline.check("JJKC32MCHWDI71265Z06");
+        let rules: Vec<&str> = result.findings.iter().map(|f| f.rule.as_str()).collect();
+        assert_eq!(rules, vec!["lou-allowlist", "active-issuer", "never"]);
+        assert_eq!(result.findings[1].message, "LOU JJKC is Inactive");
+
+        let result = pipeline.check("549300IYKILIU506KA05");
+        assert_eq!(result.findings.len(), 1);
+        assert_eq!(result.findings[0].rule, "never");
+    }
+
+    #[test]
+    fn pipeline_is_shared_across_threads() {
+        let registry: MemoryRegistry = [LouInfo::new("5493", "Active", LouStatus::Active)]
+            .into_iter()
+            .collect();
+        let pipeline = std::sync::Arc::new(RulePipeline::new().with(active_issuer(registry)));
+        let shared = std::sync::Arc::clone(&pipeline);
+        let passed = std::thread::spawn(move || shared.check("549300IYKILIU506KA05").passed())
+            .join()
+            .unwrap();
+        assert!(passed);
+        assert!(!pipeline.check("JJKC32MCHWDI71265Z06").passed());
+    }
+
+    #[test]
+    fn rules_skip_structurally_invalid_input() {
+        let pipeline = RulePipeline::new().rule("never", |_| Err("always fails".to_owned()));
+        let result = pipeline.check("yz83gd8l7gg84979j516");
+        assert!(result.lei.is_err());
+        assert!(result.findings.is_empty());
+        assert!(!result.passed());
+
+        let result = pipeline.loose(true).check("yz83gd8l7gg84979j516");
+        assert!(result.lei.is_ok());
+        assert_eq!(result.findings.len(), 1);
+    }
+}
@@ -0,0 +1,86 @@
+#![warn(missing_docs)]
+//! # lei::rusqlite
+//!
+//! [rusqlite](https://crates.io/crates/rusqlite) `ToSql` and `FromSql` implementations for
+//! `LEI`. An LEI is written as `TEXT`. Reading accepts `TEXT` or a 20-byte `BLOB` and validates
+//! it with the strict `parse_bytes()`; an invalid value fails with
+//! `rusqlite::Error::FromSqlConversionFailure`, carrying the column index and the `LEIError`.
+//!
+//! ```
+//! use rusqlite::Connection;
+//!
+//! let conn = Connection::open_in_memory().unwrap();
+//! conn.execute("CREATE TABLE entities (lei TEXT NOT NULL)", ()).unwrap();
+//!
+//! let lei = lei::parse("YZ83GD8L7GG84979J516").unwrap();
+//! conn.execute("INSERT INTO entities VALUES (?1)", [lei]).unwrap();
+//! let loaded: lei::LEI = conn
+//!     .query_row("SELECT lei FROM entities", [], |row| row.get(0))
+//!     .unwrap();
+//! assert_eq!(loaded, lei);
+//! ```
+
+use ::rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+
+use crate::LEI;
+
+impl ToSql for LEI {
+    fn to_sql(&self) -> ::rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.as_str()))
+    }
+}
+
+impl FromSql for LEI {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<LEI> {
+        match value {
+            ValueRef::Text(b) | ValueRef::Blob(b) => {
+                crate::parse_bytes(b).map_err(|err| FromSqlError::Other(Box::new(err)))
+            }
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LEIError;
+    use ::rusqlite::types::Type;
+    use ::rusqlite::{Connection, Error};
+
+    fn select(sql: &str) -> ::rusqlite::Result<LEI> {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.query_row(sql, [], |row| row.get(0))
+    }
+
+    #[test]
+    fn reads_text_and_blobs() {
+        let lei = crate::parse("549300IYKILIU506KA05").unwrap();
+        assert_eq!(select("SELECT '549300IYKILIU506KA05'").unwrap(), lei);
+        assert_eq!(
+            select("SELECT CAST('549300IYKILIU506KA05' AS BLOB)").unwrap(),
+            lei
+        );
+
+        let conn = Connection::open_in_memory().unwrap();
+        let back: Option<LEI> = conn
+            .query_row("SELECT ?1", [Some(lei)], |row| row.get(0))
+            .unwrap();
+        assert_eq!(back, Some(lei));
+    }
+
+    #[test]
+    fn conversion_errors() {
+        match select("SELECT '549300IYKILIU506KA06'") {
+            Err(Error::FromSqlConversionFailure(0, Type::Text, err)) => assert!(matches!(
+                err.downcast_ref::<LEIError>(),
+                Some(LEIError::IncorrectCheckDigits { .. })
+            )),
+            other => panic!("expected a conversion failure, got {other:?}"),
+        }
+        assert!(matches!(
+            select("SELECT 42"),
+            Err(Error::InvalidColumnType(0, _, Type::Integer))
+        ));
+    }
+}
@@ -0,0 +1,61 @@
+#![warn(missing_docs)]
+//! # lei::schemars
+//!
+//! [schemars](https://crates.io/crates/schemars) `JsonSchema` implementation for `LEI`, so types
+//! with `LEI` fields generate JSON Schemas that describe the format rather than a plain string.
+//!
+//! The schema is a string of exactly 20 characters matching `LEI::PATTERN`. Like the pattern, it
+//! cannot check the _Check Digits_; `Deserialize` does that.
+//!
+//! ```
+//! use schemars::schema_for;
+//!
+//! let schema = schema_for!(lei::LEI);
+//! assert_eq!(schema.get("pattern").unwrap(), "^[0-9A-Z]{18}[0-9]{2}$");
+//! ```
+
+use std::borrow::Cow;
+
+use ::schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+
+use crate::LEI;
+
+impl JsonSchema for LEI {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("LEI")
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        Cow::Borrowed("lei::LEI")
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "minLength": LEI::LENGTH,
+            "maxLength": LEI::LENGTH,
+            "pattern": format!("^{}$", LEI::PATTERN),
+            "description": "A Legal Entity Identifier (ISO 17442): 18 uppercase alphanumeric characters followed by two check digits.",
+            "examples": ["549300IYKILIU506KA05"],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schema_describes_format() {
+        let schema = ::schemars::schema_for!(LEI);
+        assert_eq!(schema.get("type").unwrap(), "string");
+        assert_eq!(schema.get("minLength").unwrap(), 20);
+        assert_eq!(schema.get("maxLength").unwrap(), 20);
+        assert!(schema
+            .get("description")
+            .unwrap()
+            .as_str()
+            .unwrap()
+            .contains("ISO 17442"));
+    }
+}
@@ -0,0 +1,158 @@
+#![warn(missing_docs)]
+//! # lei::sea_orm
+//!
+//! [SeaORM](https://crates.io/crates/sea-orm) support for `LEI` columns. `LEI` converts to and
+//! from a string `Value`, is read from query results with `TryGetable` (validating with the
+//! strict `parse()`), has column type `CHAR(20)`, and implements `TryFromU64` so it can be the
+//! primary key of an entity.
+//!
+//! ```
+//! use sea_orm::entity::prelude::*;
+//! use sea_orm::{DbBackend, QueryTrait};
+//!
+//! #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+//! #[sea_orm(table_name = "entities")]
+//! pub struct Model {
+//!     #[sea_orm(primary_key, auto_increment = false)]
+//!     pub lei: lei::LEI,
+//!     pub name: String,
+//! }
+//!
+//! #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+//! pub enum Relation {}
+//!
+//! impl ActiveModelBehavior for ActiveModel {}
+//!
+//! let lei = lei::parse("YZ83GD8L7GG84979J516").unwrap();
+//! let query = Entity::find_by_id(lei).build(DbBackend::Sqlite).to_string();
+//! assert!(query.ends_with(r#"WHERE "entities"."lei" = 'YZ83GD8L7GG84979J516'"#));
+//! ```
+
+use ::sea_orm::sea_query::{ArrayType, ColumnType, Nullable, Value, ValueType, ValueTypeErr};
+use ::sea_orm::{ColIdx, DbErr, QueryResult, TryFromU64, TryGetError, TryGetable};
+
+use crate::LEI;
+
+/// Writes the LEI as a string value.
+impl From<LEI> for Value {
+    fn from(lei: LEI) -> Value {
+        Value::String(Some(Box::new(lei.to_string())))
+    }
+}
+
+impl Nullable for LEI {
+    fn null() -> Value {
+        Value::String(None)
+    }
+}
+
+impl ValueType for LEI {
+    fn try_from(v: Value) -> Result<LEI, ValueTypeErr> {
+        match v {
+            Value::String(Some(s)) => crate::parse(&s).map_err(|_| ValueTypeErr),
+            _ => Err(ValueTypeErr),
+        }
+    }
+
+    fn type_name() -> String {
+        "LEI".to_owned()
+    }
+
+    fn array_type() -> ArrayType {
+        ArrayType::String
+    }
+
+    fn column_type() -> ColumnType {
+        ColumnType::Char(Some(LEI::LENGTH as u32))
+    }
+}
+
+impl TryGetable for LEI {
+    fn try_get_by<I: ColIdx>(res: &QueryResult, index: I) -> Result<LEI, TryGetError> {
+        let value = String::try_get_by(res, index)?;
+        crate::parse(&value).map_err(|err| {
+            TryGetError::DbErr(DbErr::TryIntoErr {
+                from: "String",
+                into: "LEI",
+                source: Box::new(err),
+            })
+        })
+    }
+}
+
+/// LEIs cannot be generated by the database, so this always fails.
+impl TryFromU64 for LEI {
+    fn try_from_u64(_: u64) -> Result<LEI, DbErr> {
+        Err(DbErr::ConvertFromU64("LEI"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LEIError;
+    use ::sea_orm::{DbBackend, EntityTrait, MockDatabase};
+    use std::collections::BTreeMap;
+
+    mod entity {
+        use ::sea_orm::entity::prelude::*;
+
+        #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+        #[sea_orm(table_name = "entities")]
+        pub struct Model {
+            #[sea_orm(primary_key)]
+            pub id: i32,
+            pub lei: Option<crate::LEI>,
+        }
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+        pub enum Relation {}
+
+        impl ActiveModelBehavior for ActiveModel {}
+    }
+
+    fn row(id: i32, lei: Value) -> BTreeMap<&'static str, Value> {
+        BTreeMap::from([("id", Value::from(id)), ("lei", lei)])
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn models_load_lei_columns() {
+        let lei = crate::parse("549300IYKILIU506KA05").unwrap();
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results([vec![row(1, Value::from(lei)), row(2, LEI::null())]])
+            .into_connection();
+
+        let models = entity::Entity::find().all(&db).await.unwrap();
+        assert_eq!(
+            models,
+            vec![
+                entity::Model {
+                    id: 1,
+                    lei: Some(lei)
+                },
+                entity::Model { id: 2, lei: None },
+            ]
+        );
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn invalid_values_fail_to_load() {
+        let invalid = Value::String(Some(Box::new("549300IYKILIU506KA06".to_owned())));
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results([vec![row(1, invalid.clone())]])
+            .into_connection();
+
+        match entity::Entity::find().one(&db).await {
+            Err(DbErr::TryIntoErr { into, source, .. }) => {
+                assert_eq!(into, "LEI");
+                assert!(matches!(
+                    source.downcast_ref::<LEIError>(),
+                    Some(LEIError::IncorrectCheckDigits { .. })
+                ));
+            }
+            other => panic!("expected a conversion error, got {other:?}"),
+        }
+        assert!(<LEI as ValueType>::try_from(invalid).is_err());
+        assert!(LEI::try_from_u64(1).is_err());
+    }
+}
@@ -0,0 +1,595 @@
+#![warn(missing_docs)]
+//! # lei::serde
+//!
+//! [serde](https://crates.io/crates/serde) support for `LEI` and `LEIError`, and serialization
+//! of `ValidationReport` for downstream reporting.
+//!
+//! An LEI serializes as its 20-character string. Deserialization validates with the strict
+//! `parse()` and accepts either that string or the LEI's 20 ASCII bytes, given as a byte string
+//! or a sequence of integers, as other languages often write them to CBOR or MessagePack.
+//!
+//! In human-readable formats such as JSON, both the string and the array forms are accepted.
+//! Non-human-readable formats are asked for a string, which binary formats like bincode and
+//! postcard provide; self-describing ones like MessagePack pass along whatever they find. For a
+//! more compact encoding in binary formats, see the `bytes` module.
+//!
+//! ```
+//! use lei::LEI;
+//!
+//! let lei: LEI = serde_json::from_str("\"YZ83GD8L7GG84979J516\"").unwrap();
+//! assert_eq!(serde_json::to_string(&lei).unwrap(), "\"YZ83GD8L7GG84979J516\"");
+//!
+//! let bytes: Vec<u8> = b"YZ83GD8L7GG84979J516".to_vec();
+//! let from_array: LEI = serde_json::from_str(&serde_json::to_string(&bytes).unwrap()).unwrap();
+//! assert_eq!(from_array, lei);
+//! ```
+//!
+//! `LEI` fields of derived types reject malformed codes at the boundary, with an error naming
+//! the rejected value and the problem found:
+//!
+//! ```
+//! use serde::Deserialize;
+//!
+//! #[derive(Debug, Deserialize)]
+//! struct Counterparty {
+//!     lei: lei::LEI,
+//! }
+//!
+//! let json = r#"{"lei": "YZ83GD8L7GG84979J517"}"#;
+//! let err = serde_json::from_str::<Counterparty>(json).unwrap_err();
+//! assert_eq!(
+//!     err.to_string(),
+//!     "invalid LEI \"YZ83GD8L7GG84979J517\": incorrect check digits \"17\" when expecting \"16\" at line 1 column 30"
+//! );
+//! ```
+
+use std::fmt;
+
+use ::serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use ::serde::ser::{Serialize, SerializeStruct, Serializer};
+
+use crate::report::{ValidationReport, Violation};
+use crate::{CheckDigits, LEIError, LEIErrorKind, Part, LEI};
+
+impl Serialize for LEI {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Visits any of the accepted forms, validating the string with `parse_loose()` if `LOOSE` is set
+/// and `parse()` otherwise.
+struct LeiVisitor<const LOOSE: bool>;
+
+impl<const LOOSE: bool> LeiVisitor<LOOSE> {
+    fn parse(v: &str) -> Result<LEI, LEIError> {
+        if LOOSE {
+            crate::parse_loose(v)
+        } else {
+            crate::parse(v)
+        }
+    }
+}
+
+impl<'de, const LOOSE: bool> Visitor<'de> for LeiVisitor<LOOSE> {
+    type Value = LEI;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a 20-character LEI string or 20 ASCII bytes")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<LEI, E> {
+        Self::parse(v).map_err(|err| E::custom(format_args!("invalid LEI {v:?}: {err}")))
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<LEI, E> {
+        match std::str::from_utf8(v) {
+            Ok(s) => self.visit_str(s),
+            Err(_) => Err(E::invalid_value(de::Unexpected::Bytes(v), &self)),
+        }
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<LEI, A::Error> {
+        let mut buf = [0u8; 20];
+        for (i, b) in buf.iter_mut().enumerate() {
+            *b = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        if seq.next_element::<u8>()?.is_some() {
+            return Err(de::Error::invalid_length(21, &self));
+        }
+        self.visit_bytes(&buf)
+    }
+}
+
+fn deserialize_lei<'de, D: Deserializer<'de>, const LOOSE: bool>(
+    deserializer: D,
+    visitor: LeiVisitor<LOOSE>,
+) -> Result<LEI, D::Error> {
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_any(visitor)
+    } else {
+        deserializer.deserialize_str(visitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for LEI {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<LEI, D::Error> {
+        deserialize_lei(deserializer, LeiVisitor::<false>)
+    }
+}
+
+/// Deserialization with `parse_loose()` instead of `parse()`, so leading and trailing whitespace
+/// and lowercase letters are accepted, for ingesting feeds that have not been cleaned. Use it
+/// with `#[serde(with = "lei::serde::loose")]` on an `LEI` field. Serialization is unchanged: the
+/// canonical 20-character string.
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, Deserialize, Serialize)]
+/// struct Row {
+///     #[serde(with = "lei::serde::loose")]
+///     lei: lei::LEI,
+/// }
+///
+/// let row: Row = serde_json::from_str(r#"{"lei": " yz83gd8l7gg84979j516 "}"#).unwrap();
+/// assert_eq!(
+///     serde_json::to_string(&row).unwrap(),
+///     r#"{"lei":"YZ83GD8L7GG84979J516"}"#
+/// );
+/// ```
+pub mod loose {
+    use ::serde::{Deserializer, Serialize, Serializer};
+
+    use super::{deserialize_lei, LeiVisitor};
+    use crate::LEI;
+
+    /// Serialize as the canonical 20-character string, the same as `LEI`'s `Serialize`.
+    pub fn serialize<S: Serializer>(lei: &LEI, serializer: S) -> Result<S::Ok, S::Error> {
+        lei.serialize(serializer)
+    }
+
+    /// Deserialize any of the forms `LEI`'s `Deserialize` accepts, validating with
+    /// `parse_loose()`.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<LEI, D::Error> {
+        deserialize_lei(deserializer, LeiVisitor::<true>)
+    }
+}
+
+/// A compact representation for binary formats, for use with `#[serde(with = "lei::serde::bytes")]`
+/// on an `LEI` field. In formats that are not human-readable, such as bincode, postcard and
+/// MessagePack, the LEI is written as a tuple of its 20 ASCII bytes, which those formats encode
+/// in 20 or 21 bytes with no length prefix. Human-readable formats such as JSON still get the
+/// 20-character string. Deserialization validates with `parse()` either way.
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, PartialEq, Deserialize, Serialize)]
+/// struct Row {
+///     #[serde(with = "lei::serde::bytes")]
+///     lei: lei::LEI,
+/// }
+///
+/// let row = Row { lei: lei::parse("YZ83GD8L7GG84979J516").unwrap() };
+/// let encoded = bincode::serialize(&row).unwrap();
+/// assert_eq!(encoded, b"YZ83GD8L7GG84979J516");
+/// assert_eq!(bincode::deserialize::<Row>(&encoded).unwrap(), row);
+/// ```
+pub mod bytes {
+    use ::serde::ser::SerializeTuple;
+    use ::serde::{Deserializer, Serialize, Serializer};
+
+    use super::{deserialize_lei, LeiVisitor};
+    use crate::LEI;
+
+    /// Serialize as a tuple of the 20 ASCII bytes, or as the string in human-readable formats.
+    pub fn serialize<S: Serializer>(lei: &LEI, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            return lei.serialize(serializer);
+        }
+        let mut tuple = serializer.serialize_tuple(LEI::LENGTH)?;
+        for b in lei.as_bytes() {
+            tuple.serialize_element(b)?;
+        }
+        tuple.end()
+    }
+
+    /// Deserialize the form written by `serialize()`, validating with `parse()`.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<LEI, D::Error> {
+        if deserializer.is_human_readable() {
+            deserialize_lei(deserializer, LeiVisitor::<false>)
+        } else {
+            deserializer.deserialize_tuple(LEI::LENGTH, LeiVisitor::<false>)
+        }
+    }
+}
+
+/// Captured input bytes, written as a string when they are valid UTF-8 and as an array of bytes
+/// otherwise.
+struct Captured<'a>(&'a [u8]);
+
+impl Serialize for Captured<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match std::str::from_utf8(self.0) {
+            Ok(s) => serializer.serialize_str(s),
+            Err(_) => self.0.serialize(serializer),
+        }
+    }
+}
+
+/// Serializes as a struct with fields `kind` (the variant name), `code`, `was`, `expected` (only
+/// for `IncorrectCheckDigits`) and `message` (the `Display` text). Captured bytes are written as
+/// strings when they are valid UTF-8.
+///
+/// ```
+/// let err = lei::parse("549300IYKILIU506KA06").unwrap_err();
+/// let json = serde_json::to_string(&err).unwrap();
+/// assert_eq!(
+///     json,
+///     r#"{"kind":"IncorrectCheckDigits","code":"LEI-E009","was":"06","expected":"05","message":"incorrect check digits \"06\" when expecting \"05\""}"#
+/// );
+/// assert_eq!(serde_json::from_str::<lei::LEIError>(&json).unwrap(), err);
+/// ```
+impl Serialize for LEIError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let expected = match self {
+            LEIError::IncorrectCheckDigits { expected, .. } => Some(expected),
+            _ => None,
+        };
+        let len = if expected.is_some() { 5 } else { 4 };
+        let mut state = serializer.serialize_struct("LEIError", len)?;
+        state.serialize_field("kind", self.kind().name())?;
+        state.serialize_field("code", self.code())?;
+        match self {
+            LEIError::InvalidLength { was }
+            | LEIError::InvalidPayloadLength { was }
+            | LEIError::InvalidLouIdLength { was }
+            | LEIError::InvalidEntityIdLength { was }
+            | LEIError::InvalidCheckDigitsLength { was } => state.serialize_field("was", was)?,
+            LEIError::InvalidLouId { was } => state.serialize_field("was", &Captured(was))?,
+            LEIError::InvalidEntityId { was } => state.serialize_field("was", &Captured(was))?,
+            LEIError::InvalidCheckDigits { was } => state.serialize_field("was", &Captured(was))?,
+            LEIError::IncorrectCheckDigits { was, .. } | LEIError::ReservedCheckDigits { was } => {
+                state.serialize_field("was", was.as_str())?
+            }
+        }
+        if let Some(expected) = expected {
+            state.serialize_field("expected", expected.as_str())?;
+        }
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
+
+/// A `was` or `expected` value: a length, or captured bytes as a string or an array.
+enum Field {
+    Length(u64),
+    Bytes(Vec<u8>),
+}
+
+struct FieldVisitor;
+
+impl<'de> Visitor<'de> for FieldVisitor {
+    type Value = Field;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a length, a string or an array of bytes")
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Field, E> {
+        Ok(Field::Length(v))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Field, E> {
+        Ok(Field::Bytes(v.as_bytes().to_vec()))
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Field, E> {
+        Ok(Field::Bytes(v.to_vec()))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Field, A::Error> {
+        let mut bytes = Vec::new();
+        while let Some(b) = seq.next_element()? {
+            bytes.push(b);
+        }
+        Ok(Field::Bytes(bytes))
+    }
+}
+
+impl<'de> Deserialize<'de> for Field {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Field, D::Error> {
+        deserializer.deserialize_any(FieldVisitor)
+    }
+}
+
+struct LeiErrorVisitor;
+
+impl<'de> Visitor<'de> for LeiErrorVisitor {
+    type Value = LEIError;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an LEIError")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<LEIError, A::Error> {
+        let mut kind: Option<String> = None;
+        let mut was: Option<Field> = None;
+        let mut expected: Option<Field> = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "kind" => kind = Some(map.next_value()?),
+                "was" => was = Some(map.next_value()?),
+                "expected" => expected = Some(map.next_value()?),
+                _ => {
+                    map.next_value::<de::IgnoredAny>()?;
+                }
+            }
+        }
+
+        let kind = kind.ok_or_else(|| de::Error::missing_field("kind"))?;
+        let kind = LEIErrorKind::from_name(&kind)
+            .ok_or_else(|| de::Error::custom(format!("unknown LEIError kind {kind:?}")))?;
+        let was = was.ok_or_else(|| de::Error::missing_field("was"))?;
+
+        fn length<E: de::Error>(field: Field) -> Result<usize, E> {
+            match field {
+                Field::Length(n) => usize::try_from(n).map_err(E::custom),
+                Field::Bytes(_) => Err(E::custom("expected a length")),
+            }
+        }
+        fn bytes<E: de::Error, const N: usize>(field: Field) -> Result<[u8; N], E> {
+            match field {
+                Field::Bytes(b) => b
+                    .try_into()
+                    .map_err(|_| E::custom(format!("expected {N} bytes"))),
+                Field::Length(_) => Err(E::custom("expected a string or bytes")),
+            }
+        }
+        fn check_digits<E: de::Error>(field: Field) -> Result<CheckDigits, E> {
+            let b: [u8; 2] = bytes(field)?;
+            if b.iter().all(u8::is_ascii_digit) {
+                Ok(CheckDigits(b))
+            } else {
+                Err(E::custom("expected two ASCII digits"))
+            }
+        }
+
+        Ok(match kind {
+            LEIErrorKind::InvalidLength => LEIError::InvalidLength { was: length(was)? },
+            LEIErrorKind::InvalidPayloadLength => {
+                LEIError::InvalidPayloadLength { was: length(was)? }
+            }
+            LEIErrorKind::InvalidLouIdLength => LEIError::InvalidLouIdLength { was: length(was)? },
+            LEIErrorKind::InvalidEntityIdLength => {
+                LEIError::InvalidEntityIdLength { was: length(was)? }
+            }
+            LEIErrorKind::InvalidCheckDigitsLength => {
+                LEIError::InvalidCheckDigitsLength { was: length(was)? }
+            }
+            LEIErrorKind::InvalidLouId => LEIError::InvalidLouId { was: bytes(was)? },
+            LEIErrorKind::InvalidEntityId => LEIError::InvalidEntityId { was: bytes(was)? },
+            LEIErrorKind::InvalidCheckDigits => LEIError::InvalidCheckDigits { was: bytes(was)? },
+            LEIErrorKind::IncorrectCheckDigits => LEIError::IncorrectCheckDigits {
+                was: check_digits(was)?,
+                expected: check_digits(
+                    expected.ok_or_else(|| de::Error::missing_field("expected"))?,
+                )?,
+            },
+            LEIErrorKind::ReservedCheckDigits => LEIError::ReservedCheckDigits {
+                was: check_digits(was)?,
+            },
+        })
+    }
+}
+
+/// Accepts the form written by `Serialize`. Only `kind`, `was` and `expected` are read; `code`
+/// and `message` are ignored.
+impl<'de> Deserialize<'de> for LEIError {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<LEIError, D::Error> {
+        deserializer.deserialize_map(LeiErrorVisitor)
+    }
+}
+
+/// Serializes as the variant name, such as `"InvalidLouId"`, the same as the `kind` field of
+/// `LEIError`. The stable code is available from `code()`.
+impl Serialize for LEIErrorKind {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+/// Serializes as `"lou_id"`, `"entity_id"`, `"payload"` or `"check_digits"`.
+impl Serialize for Part {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(match self {
+            Part::LouId => "lou_id",
+            Part::EntityId => "entity_id",
+            Part::Payload => "payload",
+            Part::CheckDigits => "check_digits",
+        })
+    }
+}
+
+/// Serializes as a struct with fields `kind` (the variant name), `code`, `part`, `start`, `end`
+/// and `message`, with `kind` and `code` as in `LEIError`.
+impl Serialize for Violation {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Violation", 6)?;
+        state.serialize_field("kind", &self.kind)?;
+        state.serialize_field("code", self.kind.code())?;
+        state.serialize_field("part", &self.part)?;
+        state.serialize_field("start", &self.span.start)?;
+        state.serialize_field("end", &self.span.end)?;
+        state.serialize_field("message", &self.message)?;
+        state.end()
+    }
+}
+
+/// Serializes as a struct with fields `valid` and `violations`.
+impl Serialize for ValidationReport {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("ValidationReport", 2)?;
+        state.serialize_field("valid", &self.is_valid())?;
+        state.serialize_field("violations", &self.violations)?;
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::serde::de::value::{BytesDeserializer, Error as ValueError};
+
+    #[test]
+    fn string_round_trip() {
+        let lei = crate::parse("YZ83GD8L7GG84979J516").unwrap();
+        let json = serde_json::to_string(&lei).unwrap();
+        assert_eq!(json, "\"YZ83GD8L7GG84979J516\"");
+        assert_eq!(serde_json::from_str::<LEI>(&json).unwrap(), lei);
+    }
+
+    #[test]
+    fn accepts_byte_forms() {
+        let lei = crate::parse("YZ83GD8L7GG84979J516").unwrap();
+
+        let array = serde_json::to_string(&b"YZ83GD8L7GG84979J516".to_vec()).unwrap();
+        assert_eq!(serde_json::from_str::<LEI>(&array).unwrap(), lei);
+
+        let bytes = BytesDeserializer::<ValueError>::new(b"YZ83GD8L7GG84979J516");
+        assert_eq!(LEI::deserialize(bytes).unwrap(), lei);
+    }
+
+    #[test]
+    fn loose_trims_and_folds_case() {
+        let lei = crate::parse("YZ83GD8L7GG84979J516").unwrap();
+        let mut de = serde_json::Deserializer::from_str("\"\\tyz83gd8l7gg84979j516 \"");
+        assert_eq!(loose::deserialize(&mut de).unwrap(), lei);
+
+        let mut de = serde_json::Deserializer::from_str("\"YZ83GD8L7GG84979J517\"");
+        assert!(loose::deserialize(&mut de).is_err());
+
+        let mut de = serde_json::Deserializer::from_str("\"yz83gd8l7gg84979j516\"");
+        assert!(LEI::deserialize(&mut de).is_err());
+    }
+
+    #[test]
+    fn bytes_is_compact_in_binary_formats() {
+        let lei = crate::parse("YZ83GD8L7GG84979J516").unwrap();
+
+        let mut encoded = Vec::new();
+        bytes::serialize(
+            &lei,
+            &mut bincode::Serializer::new(&mut encoded, bincode::options()),
+        )
+        .unwrap();
+        assert_eq!(encoded, lei.as_bytes());
+
+        let mut de = bincode::Deserializer::from_slice(&encoded, bincode::options());
+        assert_eq!(bytes::deserialize(&mut de).unwrap(), lei);
+
+        let mut de = bincode::Deserializer::from_slice(b"YZ83GD8L7GG84979J517", bincode::options());
+        assert!(bytes::deserialize(&mut de).is_err());
+
+        let mut json = Vec::new();
+        bytes::serialize(&lei, &mut serde_json::Serializer::new(&mut json)).unwrap();
+        assert_eq!(json, b"\"YZ83GD8L7GG84979J516\"");
+    }
+
+    #[test]
+    fn rejects_invalid() {
+        let err = serde_json::from_str::<LEI>("\"YZ83GD8L7GG84979J517\"").unwrap_err();
+        assert!(err.to_string().contains("incorrect"), "{err}");
+
+        let err = serde_json::from_str::<LEI>("\"yz83gd8l7gg84979j516\"").unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("invalid LEI \"yz83gd8l7gg84979j516\": "),
+            "{err}"
+        );
+
+        let short = serde_json::to_string(&b"YZ83GD8L7GG84979J51".to_vec()).unwrap();
+        assert!(serde_json::from_str::<LEI>(&short).is_err());
+
+        let long = serde_json::to_string(&b"YZ83GD8L7GG84979J5160".to_vec()).unwrap();
+        assert!(serde_json::from_str::<LEI>(&long).is_err());
+
+        assert!(serde_json::from_str::<LEI>("12").is_err());
+    }
+
+    #[test]
+    fn serializes_validation_report() {
+        let report = crate::validate_report("549300IYKILIU506Ka05");
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            serde_json::json!({
+                "valid": false,
+                "violations": [{
+                    "kind": "InvalidEntityId",
+                    "code": "LEI-E007",
+                    "part": "entity_id",
+                    "start": 17,
+                    "end": 18,
+                    "message": "'a' at position 17 in the Entity ID is not an uppercase ASCII letter or digit"
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn error_round_trip() {
+        let errors = [
+            crate::parse("549300IYKILIU506KA0").unwrap_err(),
+            crate::parse("54a300IYKILIU506KA05").unwrap_err(),
+            crate::parse("549300IYKILIU506Ka05").unwrap_err(),
+            crate::parse("549300IYKILIU506KA0x").unwrap_err(),
+            crate::parse("549300IYKILIU506KA06").unwrap_err(),
+            crate::parse_strict_iso("31570010000000045200").unwrap_err(),
+            crate::CheckDigits::validate("123").unwrap_err(),
+            LEIError::InvalidLouId {
+                was: [b'5', 0xC3, b'4', b'9'],
+            },
+        ];
+        for err in errors {
+            let json = serde_json::to_string(&err).unwrap();
+            assert_eq!(
+                serde_json::from_str::<LEIError>(&json).unwrap(),
+                err,
+                "{json}"
+            );
+        }
+    }
+
+    #[test]
+    fn error_fields() {
+        let err = crate::parse("54a300IYKILIU506KA05").unwrap_err();
+        let value = serde_json::to_value(&err).unwrap();
+        assert_eq!(value["kind"], "InvalidLouId");
+        assert_eq!(value["code"], "LEI-E006");
+        assert_eq!(value["was"], "54a3");
+
+        let err = LEIError::InvalidLouId {
+            was: [b'5', 0xC3, b'4', b'9'],
+        };
+        assert_eq!(
+            serde_json::to_value(&err).unwrap()["was"],
+            serde_json::json!([53, 195, 52, 57])
+        );
+    }
+
+    #[test]
+    fn error_rejects_inconsistent_fields() {
+        for json in [
+            r#"{"kind":"Bogus","was":1}"#,
+            r#"{"kind":"InvalidLength","was":"x"}"#,
+            r#"{"kind":"InvalidLouId","was":"54a"}"#,
+            r#"{"kind":"ReservedCheckDigits","was":"0x"}"#,
+            r#"{"kind":"IncorrectCheckDigits","was":"06"}"#,
+        ] {
+            assert!(serde_json::from_str::<LEIError>(json).is_err(), "{json}");
+        }
+    }
+}
@@ -0,0 +1,110 @@
+#![warn(missing_docs)]
+//! # lei::sqlx
+//!
+//! [sqlx](https://crates.io/crates/sqlx) `Type`, `Encode` and `Decode` implementations for
+//! `LEI`, for every database whose string type has them (Postgres, MySQL and SQLite). An LEI is
+//! stored as text, so it fits `TEXT`, `VARCHAR` and `CHAR(20)` columns, and decoding validates
+//! the value with the strict `parse()`.
+//!
+//! ```
+//! use sqlx::Connection;
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), sqlx::Error> {
+//! let mut conn = sqlx::SqliteConnection::connect("sqlite::memory:").await?;
+//! let lei = lei::parse("YZ83GD8L7GG84979J516").unwrap();
+//! let back: lei::LEI = sqlx::query_scalar("SELECT ?").bind(lei).fetch_one(&mut conn).await?;
+//! assert_eq!(back, lei);
+//! # Ok(())
+//! # }
+//! ```
+
+use ::sqlx::encode::IsNull;
+use ::sqlx::error::BoxDynError;
+use ::sqlx::{Database, Decode, Encode, Type};
+
+use crate::LEI;
+
+impl<DB: Database> Type<DB> for LEI
+where
+    str: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <str as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <str as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'q, DB: Database> Encode<'q, DB> for LEI
+where
+    String: Encode<'q, DB>,
+{
+    fn encode_by_ref(
+        &self,
+        buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        <String as Encode<'q, DB>>::encode(self.as_str().to_owned(), buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.as_bytes().len()
+    }
+}
+
+impl<'r, DB: Database> Decode<'r, DB> for LEI
+where
+    &'r str: Decode<'r, DB>,
+{
+    fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<LEI, BoxDynError> {
+        let value = <&str as Decode<'r, DB>>::decode(value)?;
+        Ok(crate::parse(value)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LEIError;
+    use ::sqlx::{Connection, SqliteConnection};
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn round_trips_through_a_char_column() {
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+        ::sqlx::query("CREATE TABLE entities (lei CHAR(20))")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+        let lei = crate::parse("549300IYKILIU506KA05").unwrap();
+        ::sqlx::query("INSERT INTO entities VALUES (?), (NULL)")
+            .bind(lei)
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+        let rows: Vec<Option<LEI>> = ::sqlx::query_scalar("SELECT lei FROM entities")
+            .fetch_all(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(rows, vec![Some(lei), None]);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn invalid_values_fail_to_decode() {
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+        let result: Result<LEI, _> = ::sqlx::query_scalar("SELECT '549300IYKILIU506KA06'")
+            .fetch_one(&mut conn)
+            .await;
+        let err = match result {
+            Err(::sqlx::Error::ColumnDecode { source, .. }) => source,
+            other => panic!("expected a decode error, got {other:?}"),
+        };
+        assert!(matches!(
+            err.downcast_ref::<LEIError>(),
+            Some(LEIError::IncorrectCheckDigits { .. })
+        ));
+    }
+}
@@ -0,0 +1,328 @@
+#![warn(missing_docs)]
+//! # lei::stats
+//!
+//! Profiling helpers that summarize a collection of LEIs by issuing _LOU_, naming the issuers
+//! when given a `LouRegistry`.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::registry::LouRegistry;
+use crate::{LouId, LEI};
+
+/// The number of LEIs issued by a single _LOU_, and their share of the total.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LouCount {
+    /// The _LOU ID_ of the issuer.
+    pub lou_id: String,
+    /// The name of the issuer, if the counts came from `top_n_named()` and the registry knows
+    /// the _LOU_.
+    pub name: Option<String>,
+    /// How many LEIs in the dataset were issued by this _LOU_.
+    pub count: usize,
+    /// The share of the dataset issued by this _LOU_, as a percentage in the range 0..=100.
+    pub percentage: f64,
+}
+
+/// A histogram of LEIs by issuing _LOU_.
+///
+/// Build one from any iterator of LEIs with `collect()`, or incrementally with `add()`:
+///
+/// ```
+/// use lei::stats::LouStats;
+///
+/// let stats: LouStats = ["5493002F3N6V3Z14SP04", "549300IYKILIU506KA05", "JJKC32MCHWDI71265Z06"]
+///     .iter()
+///     .map(|s| lei::parse(s).unwrap())
+///     .collect();
+///
+/// assert_eq!(stats.total(), 3);
+/// assert_eq!(stats.count("5493"), 2);
+/// assert_eq!(stats.top_n(1)[0].lou_id, "5493");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LouStats {
+    counts: BTreeMap<LouId, usize>,
+    total: usize,
+}
+
+impl LouStats {
+    /// Create an empty histogram.
+    pub fn new() -> LouStats {
+        LouStats::default()
+    }
+
+    /// Count one more LEI.
+    pub fn add(&mut self, lei: &LEI) {
+        *self.counts.entry(lei.lou_id_typed()).or_insert(0) += 1;
+        self.total += 1;
+    }
+
+    /// The total number of LEIs counted.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// The number of distinct _LOU IDs_ seen.
+    pub fn lou_count(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// The number of LEIs counted for the given _LOU ID_.
+    pub fn count(&self, lou_id: &str) -> usize {
+        LouId::parse(lou_id)
+            .ok()
+            .and_then(|lou_id| self.counts.get(&lou_id).copied())
+            .unwrap_or(0)
+    }
+
+    /// The share of all counted LEIs issued by the given _LOU ID_, as a percentage. Returns 0.0
+    /// when nothing has been counted.
+    pub fn percentage(&self, lou_id: &str) -> f64 {
+        self.percentage_of(self.count(lou_id))
+    }
+
+    fn percentage_of(&self, count: usize) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            (count as f64) * 100.0 / (self.total as f64)
+        }
+    }
+
+    /// The percentage shown in the Total row of a table: 100% of something, or 0% of nothing.
+    fn total_percentage(&self) -> f64 {
+        self.percentage_of(self.total)
+    }
+
+    /// Iterate over `(lou_id, count)` pairs in _LOU ID_ order.
+    pub fn histogram(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.counts.iter().map(|(k, v)| (k.as_str(), *v))
+    }
+
+    /// The `n` _LOUs_ with the most LEIs, largest first. Ties are broken by _LOU ID_ order.
+    pub fn top_n(&self, n: usize) -> Vec<LouCount> {
+        let mut entries: Vec<(&LouId, &usize)> = self.counts.iter().collect();
+        entries.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        entries
+            .into_iter()
+            .take(n)
+            .map(|(lou_id, count)| LouCount {
+                lou_id: lou_id.to_string(),
+                name: None,
+                count: *count,
+                percentage: self.percentage_of(*count),
+            })
+            .collect()
+    }
+
+    /// Like `top_n()`, with each issuer's name looked up in `registry`.
+    ///
+    /// ```
+    /// use lei::registry::{LouInfo, LouStatus, MemoryRegistry};
+    /// use lei::stats::LouStats;
+    ///
+    /// let registry: MemoryRegistry = [LouInfo::new("5493", "GLEIF Test LOU", LouStatus::Active)]
+    ///     .into_iter()
+    ///     .collect();
+    /// let stats: LouStats = [lei::parse("549300IYKILIU506KA05").unwrap()].into_iter().collect();
+    ///
+    /// let top = stats.top_n_named(1, &registry);
+    /// assert_eq!(top[0].name.as_deref(), Some("GLEIF Test LOU"));
+    /// ```
+    pub fn top_n_named<R: LouRegistry + ?Sized>(&self, n: usize, registry: &R) -> Vec<LouCount> {
+        let mut top = self.top_n(n);
+        for row in &mut top {
+            row.name = registry.lookup(&row.lou_id).map(|info| info.name.clone());
+        }
+        top
+    }
+
+    /// A plain-text table like the one `Display` renders, with a column of issuer names looked
+    /// up in `registry`. Issuers the registry does not know have an empty name.
+    pub fn table<'a>(&'a self, registry: &'a dyn LouRegistry) -> LouTable<'a> {
+        LouTable {
+            stats: self,
+            registry,
+        }
+    }
+}
+
+/// The table of `LouStats::table()`, naming each issuer.
+pub struct LouTable<'a> {
+    stats: &'a LouStats,
+    registry: &'a dyn LouRegistry,
+}
+
+impl Display for LouTable<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let rows = self
+            .stats
+            .top_n_named(self.stats.counts.len(), self.registry);
+        writeln!(
+            f,
+            "{:<6} {:<40} {:>10} {:>8}",
+            "LOU", "Name", "Count", "Percent"
+        )?;
+        for row in rows {
+            writeln!(
+                f,
+                "{:<6} {:<40} {:>10} {:>7.2}%",
+                row.lou_id,
+                row.name.as_deref().unwrap_or(""),
+                row.count,
+                row.percentage
+            )?;
+        }
+        write!(
+            f,
+            "{:<6} {:<40} {:>10} {:>7.2}%",
+            "Total",
+            "",
+            self.stats.total,
+            self.stats.total_percentage()
+        )
+    }
+}
+
+impl Extend<LEI> for LouStats {
+    fn extend<T: IntoIterator<Item = LEI>>(&mut self, iter: T) {
+        for lei in iter {
+            self.add(&lei);
+        }
+    }
+}
+
+impl<'a> Extend<&'a LEI> for LouStats {
+    fn extend<T: IntoIterator<Item = &'a LEI>>(&mut self, iter: T) {
+        for lei in iter {
+            self.add(lei);
+        }
+    }
+}
+
+impl FromIterator<LEI> for LouStats {
+    fn from_iter<T: IntoIterator<Item = LEI>>(iter: T) -> Self {
+        let mut stats = LouStats::new();
+        stats.extend(iter);
+        stats
+    }
+}
+
+impl<'a> FromIterator<&'a LEI> for LouStats {
+    fn from_iter<T: IntoIterator<Item = &'a LEI>>(iter: T) -> Self {
+        let mut stats = LouStats::new();
+        stats.extend(iter);
+        stats
+    }
+}
+
+/// Renders a plain-text table of every _LOU_, largest first.
+impl Display for LouStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<6} {:>10} {:>8}", "LOU", "Count", "Percent")?;
+        for row in self.top_n(self.counts.len()) {
+            writeln!(
+                f,
+                "{:<6} {:>10} {:>7.2}%",
+                row.lou_id, row.count, row.percentage
+            )?;
+        }
+        write!(
+            f,
+            "{:<6} {:>10} {:>7.2}%",
+            "Total",
+            self.total,
+            self.total_percentage()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> LouStats {
+        [
+            "5493002F3N6V3Z14SP04",
+            "549300IYKILIU506KA05",
+            "549300RIPPWJB5Z0FK07",
+            "JJKC32MCHWDI71265Z06",
+            "Z2VZBHUMB7PWWJ63I008",
+            "Z2VZBHUMB7PWWJ63I008",
+        ]
+        .iter()
+        .map(|s| crate::parse(s).unwrap())
+        .collect()
+    }
+
+    #[test]
+    fn counts_and_percentages() {
+        let stats = sample();
+        assert_eq!(stats.total(), 6);
+        assert_eq!(stats.lou_count(), 3);
+        assert_eq!(stats.count("5493"), 3);
+        assert_eq!(stats.count("Z2VZ"), 2);
+        assert_eq!(stats.count("XXXX"), 0);
+        assert_eq!(stats.percentage("5493"), 50.0);
+        assert_eq!(LouStats::new().percentage("5493"), 0.0);
+    }
+
+    #[test]
+    fn top_n_orders_by_count_then_lou_id() {
+        let stats = sample();
+        let top: Vec<(String, usize)> = stats
+            .top_n(5)
+            .into_iter()
+            .map(|c| (c.lou_id, c.count))
+            .collect();
+        assert_eq!(
+            top,
+            vec![
+                ("5493".to_owned(), 3),
+                ("Z2VZ".to_owned(), 2),
+                ("JJKC".to_owned(), 1)
+            ]
+        );
+        let histogram: Vec<(&str, usize)> = stats.histogram().collect();
+        assert_eq!(histogram, vec![("5493", 3), ("JJKC", 1), ("Z2VZ", 2)]);
+    }
+
+    #[test]
+    fn names_from_registry() {
+        use crate::registry::{LouInfo, LouStatus, MemoryRegistry};
+
+        let registry: MemoryRegistry = [LouInfo::new("5493", "Test LOU", LouStatus::Active)]
+            .into_iter()
+            .collect();
+        let stats = sample();
+        let names: Vec<Option<String>> = stats
+            .top_n_named(2, &registry)
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+        assert_eq!(names, vec![Some("Test LOU".to_owned()), None]);
+
+        let table = stats.table(&registry).to_string();
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[1].starts_with("5493   Test LOU "), "{table}");
+        assert!(lines[1].ends_with("50.00%"), "{table}");
+        assert!(lines[4].ends_with("100.00%"), "{table}");
+    }
+
+    #[test]
+    fn empty_total_is_zero_percent() {
+        use crate::registry::MemoryRegistry;
+
+        let stats = LouStats::new();
+        assert_eq!(
+            stats.to_string().lines().last(),
+            Some("Total           0    0.00%")
+        );
+        let registry = MemoryRegistry::new();
+        let table = stats.table(&registry).to_string();
+        assert!(table.ends_with("   0    0.00%"), "{table}");
+    }
+}
@@ -0,0 +1,129 @@
+#![warn(missing_docs)]
+//! # lei::test_vectors
+//!
+//! Worked examples of the _Check Digit_ computation, with every intermediate value, for verifying
+//! other implementations (FFI bindings, ports to other languages) against the same fixtures this
+//! crate is tested with.
+//!
+//! `STANDARD_EXAMPLE` is the example LEI from Section A.1 of The Standard. `GLEIF_VECTORS` are
+//! LEIs of real entities taken from GLEIF's public data, chosen to cover cases the single example
+//! does not; they are not from The Standard.
+//!
+//! The computation for each vector is:
+//!
+//! 1. Take the 18-character _Payload_.
+//! 2. Replace each letter with its two-digit value (A = 10, B = 11, ..., Z = 35) and append
+//!    "00", giving `digits`.
+//! 3. Compute `digits` modulo 97, giving `remainder`.
+//! 4. Subtract `remainder` from 98, giving the two _Check Digits_, zero-padded.
+//!
+//! A complete LEI is valid when the same letter replacement applied to all 20 characters yields
+//! a number whose remainder modulo 97 is 1.
+//!
+//! ```
+//! use lei::test_vectors::{GLEIF_VECTORS, STANDARD_EXAMPLE};
+//!
+//! for v in std::iter::once(&STANDARD_EXAMPLE).chain(GLEIF_VECTORS) {
+//!     assert_eq!(lei::build_from_payload(v.payload).unwrap().to_string(), v.lei);
+//! }
+//! ```
+
+/// One worked example of the _Check Digit_ computation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TestVector {
+    /// The 18-character _Payload_.
+    pub payload: &'static str,
+    /// The _Payload_ with letters replaced by their numeric values and "00" appended.
+    pub digits: &'static str,
+    /// `digits` modulo 97.
+    pub remainder: u8,
+    /// 98 minus `remainder`, as two decimal digits.
+    pub check_digits: &'static str,
+    /// The complete LEI.
+    pub lei: &'static str,
+}
+
+/// The example LEI from Section A.1 of The Standard.
+pub const STANDARD_EXAMPLE: TestVector = TestVector {
+    payload: "YZ83GD8L7GG84979J5",
+    digits: "3435831613821716168497919500",
+    remainder: 82,
+    check_digits: "16",
+    lei: "YZ83GD8L7GG84979J516",
+};
+
+/// Real-world vectors: LEIs of registered entities from GLEIF's public data. Between them they
+/// cover an all-digit _Payload_ and _Check Digits_ with a leading zero, which
+/// `STANDARD_EXAMPLE` does not.
+pub const GLEIF_VECTORS: &[TestVector] = &[
+    TestVector {
+        payload: "549300IYKILIU506KA",
+        digits: "54930018342018211830506201000",
+        remainder: 93,
+        check_digits: "05",
+        lei: "549300IYKILIU506KA05",
+    },
+    TestVector {
+        payload: "959800201400053468",
+        digits: "95980020140005346800",
+        remainder: 81,
+        check_digits: "17",
+        lei: "95980020140005346817",
+    },
+    TestVector {
+        payload: "5493002F3N6V3Z14SP",
+        digits: "54930021532363133514282500",
+        remainder: 94,
+        check_digits: "04",
+        lei: "5493002F3N6V3Z14SP04",
+    },
+    TestVector {
+        payload: "AJ6VL0Z1WDC42KKJZO",
+        digits: "101963121035132131242202019352400",
+        remainder: 78,
+        check_digits: "20",
+        lei: "AJ6VL0Z1WDC42KKJZO20",
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand(s: &str) -> String {
+        s.chars()
+            .map(|c| c.to_digit(36).unwrap().to_string())
+            .collect()
+    }
+
+    fn mod97(digits: &str) -> u32 {
+        digits
+            .bytes()
+            .fold(0, |r, d| (r * 10 + (d - b'0') as u32) % 97)
+    }
+
+    fn vectors() -> impl Iterator<Item = &'static TestVector> {
+        std::iter::once(&STANDARD_EXAMPLE).chain(GLEIF_VECTORS)
+    }
+
+    #[test]
+    fn intermediates_are_consistent() {
+        for v in vectors() {
+            assert_eq!(format!("{}00", expand(v.payload)), v.digits);
+            assert_eq!(mod97(v.digits), v.remainder as u32);
+            assert_eq!(format!("{:02}", 98 - v.remainder), v.check_digits);
+            assert_eq!(format!("{}{}", v.payload, v.check_digits), v.lei);
+            assert_eq!(mod97(&expand(v.lei)), 1);
+        }
+    }
+
+    #[test]
+    fn vectors_agree_with_crate() {
+        for v in vectors() {
+            let lei = crate::parse(v.lei).unwrap();
+            assert_eq!(lei.payload(), v.payload);
+            assert_eq!(lei.check_digits(), v.check_digits);
+            assert_eq!(crate::build_from_payload(v.payload), Ok(lei));
+        }
+    }
+}
@@ -0,0 +1,403 @@
+#![warn(missing_docs)]
+//! # lei::tower
+//!
+//! A [tower](https://crates.io/crates/tower) layer that validates LEIs carried in HTTP requests
+//! before they reach the wrapped service.
+//!
+//! Each configured `LeiSource` (a header, a path segment, a query parameter or a `RawLei` request
+//! extension) is checked on every request. Path segments and query parameters are
+//! percent-decoded first, as the handler's extractors would see them. Sources absent from a
+//! request are skipped. If every present value is a valid LEI, the parsed values are added to the
+//! request extensions as `ValidatedLeis` and the request is passed on. Otherwise the request is
+//! answered immediately with `422 Unprocessable Entity` and a JSON body describing the first
+//! failure:
+//!
+//! ```json
+//! {"source":"header x-lei","value":"YZ83GD8L7GG84979J517",
+//!  "error":"incorrect check digits \"17\" when expecting \"16\""}
+//! ```
+//!
+//! ```
+//! use lei::tower::{LeiSource, ValidateLeiLayer};
+//!
+//! let layer = ValidateLeiLayer::new()
+//!     .source(LeiSource::header("x-lei"))
+//!     .source(LeiSource::PathSegment(1));
+//! ```
+
+use std::borrow::Cow;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use http::header::{HeaderName, CONTENT_TYPE};
+use http::{HeaderValue, Request, Response, StatusCode};
+use percent_encoding::percent_decode_str;
+use pin_project_lite::pin_project;
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::{LEIError, LEI};
+
+/// Where in a request to look for an LEI.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LeiSource {
+    /// The value of a request header.
+    Header(HeaderName),
+    /// A segment of the URI path, counting from 0 and ignoring the leading `/`. For example,
+    /// segment 1 of `/entities/YZ83GD8L7GG84979J516` is the LEI.
+    PathSegment(usize),
+    /// A query string parameter.
+    Query(String),
+    /// A `RawLei` in the request extensions, placed there by an earlier layer that found an LEI
+    /// elsewhere, such as in a token claim.
+    Extension,
+}
+
+/// An LEI, not yet validated, that an earlier layer or middleware found in a request and added to
+/// its extensions, for `LeiSource::Extension` to validate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RawLei(pub String);
+
+impl LeiSource {
+    /// A header source. Panics if `name` is not a valid header name.
+    pub fn header(name: &'static str) -> LeiSource {
+        LeiSource::Header(HeaderName::from_static(name))
+    }
+
+    /// A query parameter source.
+    pub fn query(name: &str) -> LeiSource {
+        LeiSource::Query(name.to_owned())
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            LeiSource::Header(name) => format!("header {name}"),
+            LeiSource::PathSegment(index) => format!("path segment {index}"),
+            LeiSource::Query(name) => format!("query parameter {name}"),
+            LeiSource::Extension => "extension".to_owned(),
+        }
+    }
+
+    /// Find the value of this source in a request, if present, decoded.
+    fn find<'a, B>(&self, request: &'a Request<B>) -> Option<Cow<'a, str>> {
+        match self {
+            LeiSource::Header(name) => request.headers().get(name).map(|v| {
+                // A header that is not visible ASCII cannot be a valid LEI; validate it as
+                // an empty value so it is rejected rather than skipped.
+                Cow::Borrowed(v.to_str().unwrap_or(""))
+            }),
+            LeiSource::PathSegment(index) => request
+                .uri()
+                .path()
+                .trim_start_matches('/')
+                .split('/')
+                .nth(*index)
+                .filter(|s| !s.is_empty())
+                .map(|s| percent_decode_str(s).decode_utf8_lossy()),
+            LeiSource::Query(name) => request.uri().query().and_then(|q| {
+                form_urlencoded::parse(q.as_bytes()).find_map(|(k, v)| (k == **name).then_some(v))
+            }),
+            LeiSource::Extension => request
+                .extensions()
+                .get::<RawLei>()
+                .map(|raw| Cow::Borrowed(raw.0.as_str())),
+        }
+    }
+}
+
+/// The LEIs validated by `ValidateLei`, in source order, added to the request extensions.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ValidatedLeis(pub Vec<LEI>);
+
+/// A `Layer` producing `ValidateLei` services.
+#[derive(Clone, Debug, Default)]
+pub struct ValidateLeiLayer {
+    sources: Vec<LeiSource>,
+    loose: bool,
+}
+
+impl ValidateLeiLayer {
+    /// Create a layer with no sources. Add sources with `source()`.
+    pub fn new() -> ValidateLeiLayer {
+        ValidateLeiLayer::default()
+    }
+
+    /// Also validate the given source.
+    pub fn source(mut self, source: LeiSource) -> ValidateLeiLayer {
+        self.sources.push(source);
+        self
+    }
+
+    /// Validate with `parse_loose()` instead of the default strict `parse()`.
+    pub fn loose(mut self) -> ValidateLeiLayer {
+        self.loose = true;
+        self
+    }
+}
+
+impl<S> Layer<S> for ValidateLeiLayer {
+    type Service = ValidateLei<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ValidateLei {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// A `Service` that validates LEIs in requests before calling the inner service.
+#[derive(Clone, Debug)]
+pub struct ValidateLei<S> {
+    inner: S,
+    layer: ValidateLeiLayer,
+}
+
+impl<S> ValidateLei<S> {
+    /// Check every source, returning the parsed LEIs or the first failure.
+    fn check<B>(&self, request: &Request<B>) -> Result<Vec<LEI>, (&LeiSource, String, LEIError)> {
+        let mut leis = Vec::new();
+        for source in &self.layer.sources {
+            if let Some(value) = source.find(request) {
+                let parsed = if self.layer.loose {
+                    crate::parse_loose(&value)
+                } else {
+                    crate::parse(&value)
+                };
+                match parsed {
+                    Ok(lei) => leis.push(lei),
+                    Err(err) => return Err((source, value.into_owned(), err)),
+                }
+            }
+        }
+        Ok(leis)
+    }
+}
+
+fn rejection<B: From<String>>(source: &LeiSource, value: &str, err: &LEIError) -> Response<B> {
+    let mut body = String::from("{\"source\":");
+    crate::json::push_string(&mut body, &source.describe());
+    body.push_str(",\"value\":");
+    crate::json::push_string(&mut body, value);
+    body.push_str(",\"error\":");
+    crate::json::push_string(&mut body, &err.to_string());
+    body.push('}');
+
+    let mut response = Response::new(B::from(body));
+    *response.status_mut() = StatusCode::UNPROCESSABLE_ENTITY;
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    response
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for ValidateLei<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    ResBody: From<String>,
+{
+    type Response = Response<ResBody>;
+    type Error = S::Error;
+    type Future = ResponseFuture<S::Future, ResBody>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<ReqBody>) -> Self::Future {
+        match self.check(&request) {
+            Ok(leis) => {
+                request.extensions_mut().insert(ValidatedLeis(leis));
+                ResponseFuture {
+                    state: State::Inner {
+                        future: self.inner.call(request),
+                    },
+                }
+            }
+            Err((source, value, err)) => ResponseFuture {
+                state: State::Rejected {
+                    response: Some(rejection(source, &value, &err)),
+                },
+            },
+        }
+    }
+}
+
+pin_project! {
+    /// The response future of `ValidateLei`.
+    pub struct ResponseFuture<F, B> {
+        #[pin]
+        state: State<F, B>,
+    }
+}
+
+pin_project! {
+    #[project = StateProj]
+    enum State<F, B> {
+        Inner {
+            #[pin]
+            future: F,
+        },
+        Rejected {
+            response: Option<Response<B>>,
+        },
+    }
+}
+
+impl<F, B, E> Future for ResponseFuture<F, B>
+where
+    F: Future<Output = Result<Response<B>, E>>,
+{
+    type Output = Result<Response<B>, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project().state.project() {
+            StateProj::Inner { future } => future.poll(cx),
+            StateProj::Rejected { response } => Poll::Ready(Ok(response
+                .take()
+                .expect("ResponseFuture polled after completion"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::Infallible;
+    use std::future::{ready, Ready};
+
+    /// Echoes the validated LEIs back in the response body.
+    struct Echo;
+
+    impl Service<Request<()>> for Echo {
+        type Response = Response<String>;
+        type Error = Infallible;
+        type Future = Ready<Result<Response<String>, Infallible>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, request: Request<()>) -> Self::Future {
+            let leis = request.extensions().get::<ValidatedLeis>().unwrap();
+            let body: Vec<String> = leis.0.iter().map(LEI::to_string).collect();
+            ready(Ok(Response::new(body.join(","))))
+        }
+    }
+
+    fn call(layer: &ValidateLeiLayer, request: Request<()>) -> Response<String> {
+        let mut service = layer.layer(Echo);
+        let mut future = std::pin::pin!(service.call(request));
+        let mut cx = Context::from_waker(std::task::Waker::noop());
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(Ok(response)) => response,
+            _ => panic!("expected a ready response"),
+        }
+    }
+
+    fn layer() -> ValidateLeiLayer {
+        ValidateLeiLayer::new()
+            .source(LeiSource::header("x-lei"))
+            .source(LeiSource::PathSegment(1))
+            .source(LeiSource::query("parent"))
+    }
+
+    #[test]
+    fn passes_valid_leis_to_inner_service() {
+        let request = Request::get("/entities/YZ83GD8L7GG84979J516?parent=549300IYKILIU506KA05")
+            .header("x-lei", "JJKC32MCHWDI71265Z06")
+            .body(())
+            .unwrap();
+        let response = call(&layer(), request);
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.body(),
+            "JJKC32MCHWDI71265Z06,YZ83GD8L7GG84979J516,549300IYKILIU506KA05"
+        );
+
+        let response = call(&layer(), Request::get("/").body(()).unwrap());
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.body(), "");
+    }
+
+    #[test]
+    fn rejects_invalid_lei_with_json_error() {
+        let request = Request::get("/entities/YZ83GD8L7GG84979J517")
+            .body(())
+            .unwrap();
+        let response = call(&layer(), request);
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+        assert_eq!(
+            response.body(),
+            r#"{"source":"path segment 1","value":"YZ83GD8L7GG84979J517","error":"incorrect check digits \"17\" when expecting \"16\""}"#
+        );
+    }
+
+    #[test]
+    fn decodes_query_and_path() {
+        let request =
+            Request::get("/entities/YZ83GD8L7GG84979J51%36?parent=549300IYKILIU506KA0%35")
+                .body(())
+                .unwrap();
+        let response = call(&layer(), request);
+        assert_eq!(response.body(), "YZ83GD8L7GG84979J516,549300IYKILIU506KA05");
+
+        let request = Request::get("/?parent=549300IYKILIU506KA05%20")
+            .body(())
+            .unwrap();
+        let response = call(&layer(), request);
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(
+            response
+                .body()
+                .contains(r#""value":"549300IYKILIU506KA05 ""#),
+            "{}",
+            response.body()
+        );
+
+        let request = Request::get("/?parent=549300IYKILIU506KA05%20")
+            .body(())
+            .unwrap();
+        let response = call(&layer().loose(), request);
+        assert_eq!(response.body(), "549300IYKILIU506KA05");
+    }
+
+    #[test]
+    fn validates_extension() {
+        let layer = ValidateLeiLayer::new().source(LeiSource::Extension);
+        let mut request = Request::get("/").body(()).unwrap();
+        request
+            .extensions_mut()
+            .insert(RawLei("YZ83GD8L7GG84979J516".to_owned()));
+        assert_eq!(call(&layer, request).body(), "YZ83GD8L7GG84979J516");
+
+        let mut request = Request::get("/").body(()).unwrap();
+        request
+            .extensions_mut()
+            .insert(RawLei("YZ83GD8L7GG84979J517".to_owned()));
+        let response = call(&layer, request);
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(response.body().starts_with(r#"{"source":"extension","#));
+    }
+
+    #[test]
+    fn loose_mode_accepts_lowercase() {
+        let request = Request::get("/")
+            .header("x-lei", "yz83gd8l7gg84979j516")
+            .body(())
+            .unwrap();
+        assert_eq!(
+            call(&layer(), request).status(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+
+        let request = Request::get("/")
+            .header("x-lei", "yz83gd8l7gg84979j516")
+            .body(())
+            .unwrap();
+        let response = call(&layer().loose(), request);
+        assert_eq!(response.body(), "YZ83GD8L7GG84979J516");
+    }
+}
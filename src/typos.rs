@@ -0,0 +1,120 @@
+//! Candidate corrections for mistyped LEIs, returned by `LEI::suggest()`.
+
+use crate::LEI;
+
+/// Characters commonly confused with each other when LEIs are keyed in by hand.
+const CONFUSABLE: [(u8, u8); 2] = [(b'O', b'0'), (b'I', b'1')];
+
+const ALPHANUMERIC: &[u8; 36] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+pub(crate) fn suggest(value: &str) -> Vec<LEI> {
+    let value = value.trim();
+    if value.len() != LEI::LENGTH {
+        return Vec::new();
+    }
+    let mut bb = [0u8; 20];
+    bb.copy_from_slice(value.as_bytes());
+    bb.make_ascii_uppercase();
+
+    if let Ok(lei) = crate::parse_bytes_unrecorded(&bb) {
+        return vec![lei];
+    }
+
+    // Try the error classes from most to least likely, so the best candidates come first.
+
+    let mut found = Vec::new();
+
+    for i in 0..LEI::LENGTH {
+        for (a, b) in CONFUSABLE {
+            let replacement = match bb[i] {
+                c if c == a => b,
+                c if c == b => a,
+                _ => continue,
+            };
+            consider(&mut found, bb, |candidate| candidate[i] = replacement);
+        }
+    }
+
+    for i in 0..LEI::LENGTH - 1 {
+        if bb[i] != bb[i + 1] {
+            consider(&mut found, bb, |candidate| candidate.swap(i, i + 1));
+        }
+    }
+
+    for i in 0..LEI::LENGTH {
+        let alphabet = if LEI::CHECK_DIGITS_RANGE.contains(&i) {
+            &ALPHANUMERIC[..10]
+        } else {
+            &ALPHANUMERIC[..]
+        };
+        for &c in alphabet {
+            if c != bb[i] {
+                consider(&mut found, bb, |candidate| candidate[i] = c);
+            }
+        }
+    }
+
+    found
+}
+
+/// Apply `edit` to a copy of `bb` and add the result to `found` if it is a valid LEI not already
+/// found.
+fn consider(found: &mut Vec<LEI>, mut bb: [u8; 20], edit: impl FnOnce(&mut [u8; 20])) {
+    edit(&mut bb);
+    if let Ok(lei) = crate::parse_bytes_unrecorded(&bb) {
+        if !found.contains(&lei) {
+            found.push(lei);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn confusions_come_first() {
+        let found = suggest("549300IYKILIU5O6KA05");
+        assert_eq!(found[0].as_str(), "549300IYKILIU506KA05");
+    }
+
+    #[test]
+    fn transpositions() {
+        let found = suggest("YZ83GD8L7GG84979J561");
+        assert!(found
+            .iter()
+            .any(|lei| lei.as_str() == "YZ83GD8L7GG84979J516"));
+        let found = suggest("YZ83DG8L7GG84979J516");
+        assert!(found
+            .iter()
+            .any(|lei| lei.as_str() == "YZ83GD8L7GG84979J516"));
+    }
+
+    #[test]
+    fn substitutions() {
+        let found = suggest("YZ83GD8L7GG84979J5-6");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].as_str(), "YZ83GD8L7GG84979J516");
+        assert!(suggest("YZ83GD8L7GG84979J566")
+            .iter()
+            .any(|lei| lei.as_str() == "YZ83GD8L7GG84979J516"));
+    }
+
+    #[test]
+    fn valid_and_hopeless_inputs() {
+        assert_eq!(suggest(" yz83gd8l7gg84979j516 ").len(), 1);
+        assert!(suggest("YZ83GD8L7GG84979J5").is_empty());
+        assert!(suggest("YZ83-D8L7GG84979J5-6").is_empty());
+        assert!(suggest("YZ83GD8L7GG84979J5\u{e9}").is_empty());
+    }
+
+    #[test]
+    fn candidates_are_distinct_and_valid() {
+        let found = suggest("YZ83GD8L7GG84979J517");
+        assert!(!found.is_empty());
+        for (i, lei) in found.iter().enumerate() {
+            assert!(crate::validate(lei.as_str()));
+            assert!(!found[..i].contains(lei));
+        }
+    }
+}
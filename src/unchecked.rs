@@ -0,0 +1,156 @@
+#![warn(missing_docs)]
+//! # lei::unchecked
+//!
+//! An LEI whose format has been checked but whose _Check Digits_ have not, for data-cleaning
+//! pipelines that keep values with incorrect _Check Digits_ apart from malformed ones. Unlike an
+//! `LEI`, an `UncheckedLei` is not known to be valid; `verify()` or `repair()` turns it into one.
+//!
+//! ```
+//! use lei::{LEIError, ParseOptions, UncheckedLei};
+//!
+//! let options = ParseOptions::loose();
+//! let unchecked = UncheckedLei::parse_with(" 549300iykiliu506ka06", &options).unwrap();
+//! assert_eq!(unchecked.as_str(), "549300IYKILIU506KA06");
+//! assert!(matches!(
+//!     unchecked.verify(),
+//!     Err(LEIError::IncorrectCheckDigits { .. })
+//! ));
+//! assert_eq!(unchecked.repair().as_str(), "549300IYKILIU506KA05");
+//! ```
+
+use std::fmt;
+use std::str::from_utf8_unchecked;
+
+use crate::{CheckDigits, LEIError, ParseOptions, LEI};
+
+/// An LEI in valid format whose _Check Digits_ may be incorrect. See the module documentation.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct UncheckedLei([u8; 20]);
+
+impl UncheckedLei {
+    /// Check that `value` is in LEI format, with the same rules as `parse()` except that the
+    /// _Check Digits_ only need to be two decimal digits.
+    pub fn parse(value: &str) -> Result<UncheckedLei, LEIError> {
+        UncheckedLei::parse_with(value, &ParseOptions::new())
+    }
+
+    /// Check that `value` is in LEI format, with the rules chosen by `options` except that the
+    /// _Check Digits_ only need to be two decimal digits.
+    pub fn parse_with(value: &str, options: &ParseOptions) -> Result<UncheckedLei, LEIError> {
+        let bb = options.normalize(value)?;
+        crate::validate_lou_id_format(&bb[LEI::LOU_ID_RANGE])?;
+        crate::validate_entity_id_format(&bb[LEI::ENTITY_ID_RANGE])?;
+        crate::validate_check_digits_format(&bb[LEI::CHECK_DIGITS_RANGE])?;
+        options.check_reserved(CheckDigits([bb[18], bb[19]]))?;
+        Ok(UncheckedLei(bb))
+    }
+
+    /// Return the LEI if its _Check Digits_ are correct, or `LEIError::IncorrectCheckDigits`.
+    pub fn verify(&self) -> Result<LEI, LEIError> {
+        crate::parse_bytes_unrecorded(&self.0)
+    }
+
+    /// Return the LEI with its _Check Digits_ recomputed from the _Payload_.
+    pub fn repair(&self) -> LEI {
+        let mut bb = self.0;
+        let check_digits = crate::compute_check_digits(&bb[LEI::PAYLOAD_RANGE]);
+        bb[LEI::CHECK_DIGITS_RANGE].copy_from_slice(&check_digits);
+        LEI(bb)
+    }
+
+    /// Return the full 20-character value as a string slice.
+    pub fn as_str(&self) -> &str {
+        unsafe { from_utf8_unchecked(&self.0[..]) } // This is safe because we know it is ASCII
+    }
+
+    /// Return just the _LOU ID_ portion of the value.
+    pub fn lou_id(&self) -> &str {
+        &self.as_str()[LEI::LOU_ID_RANGE]
+    }
+
+    /// Return just the _Entity ID_ portion of the value.
+    pub fn entity_id(&self) -> &str {
+        &self.as_str()[LEI::ENTITY_ID_RANGE]
+    }
+
+    /// Return just the _Payload_ portion of the value (everything except the _Check Digits_).
+    pub fn payload(&self) -> &str {
+        &self.as_str()[LEI::PAYLOAD_RANGE]
+    }
+
+    /// Return just the unverified _Check Digits_ portion of the value.
+    pub fn check_digits(&self) -> &str {
+        &self.as_str()[LEI::CHECK_DIGITS_RANGE]
+    }
+}
+
+impl fmt::Display for UncheckedLei {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl fmt::Debug for UncheckedLei {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "UncheckedLei({})", self.as_str())
+    }
+}
+
+impl From<LEI> for UncheckedLei {
+    fn from(lei: LEI) -> UncheckedLei {
+        UncheckedLei(lei.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_format_only() {
+        let unchecked = UncheckedLei::parse("YZ83GD8L7GG84979J517").unwrap();
+        assert_eq!(unchecked.payload(), "YZ83GD8L7GG84979J5");
+        assert_eq!(unchecked.check_digits(), "17");
+        assert_eq!(
+            unchecked.verify(),
+            Err(LEIError::IncorrectCheckDigits {
+                was: CheckDigits(*b"17"),
+                expected: CheckDigits(*b"16"),
+            })
+        );
+        assert_eq!(
+            unchecked.repair(),
+            crate::parse("YZ83GD8L7GG84979J516").unwrap()
+        );
+        assert_eq!(
+            format!("{unchecked:?}"),
+            "UncheckedLei(YZ83GD8L7GG84979J517)"
+        );
+
+        assert_eq!(
+            UncheckedLei::parse("yz83GD8L7GG84979J517"),
+            Err(LEIError::InvalidLouId { was: *b"yz83" })
+        );
+    }
+
+    #[test]
+    fn round_trips_valid_leis() {
+        let lei = crate::parse("549300IYKILIU506KA05").unwrap();
+        let unchecked = UncheckedLei::from(lei);
+        assert_eq!(unchecked.to_string(), lei.to_string());
+        assert_eq!(unchecked.verify(), Ok(lei));
+        assert_eq!(unchecked.repair(), lei);
+    }
+
+    #[test]
+    fn applies_reserved_rule() {
+        let strict = ParseOptions::strict_iso();
+        assert_eq!(
+            UncheckedLei::parse_with("549300IYKILIU506KA99", &strict),
+            Err(LEIError::ReservedCheckDigits {
+                was: CheckDigits(*b"99")
+            })
+        );
+        assert!(UncheckedLei::parse("549300IYKILIU506KA99").is_ok());
+    }
+}
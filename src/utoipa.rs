@@ -0,0 +1,63 @@
+#![warn(missing_docs)]
+//! # lei::utoipa
+//!
+//! [utoipa](https://crates.io/crates/utoipa) `ToSchema` implementation for `LEI`, so `LEI` fields
+//! of API types are documented in the OpenAPI specification as LEIs rather than plain strings.
+//!
+//! The schema is a string with format `lei`, exactly 20 characters long and matching
+//! `LEI::PATTERN`, with an example value. Like the pattern, it cannot check the _Check Digits_.
+//!
+//! ```
+//! use utoipa::PartialSchema;
+//!
+//! let schema = serde_json::to_value(lei::LEI::schema()).unwrap();
+//! assert_eq!(schema["format"], "lei");
+//! assert_eq!(schema["pattern"], "^[0-9A-Z]{18}[0-9]{2}$");
+//! ```
+
+use std::borrow::Cow;
+
+use ::utoipa::openapi::schema::{ObjectBuilder, Schema, SchemaFormat, Type};
+use ::utoipa::openapi::RefOr;
+use ::utoipa::{PartialSchema, ToSchema};
+
+use crate::LEI;
+
+impl PartialSchema for LEI {
+    fn schema() -> RefOr<Schema> {
+        ObjectBuilder::new()
+            .schema_type(Type::String)
+            .format(Some(SchemaFormat::Custom("lei".to_owned())))
+            .min_length(Some(LEI::LENGTH))
+            .max_length(Some(LEI::LENGTH))
+            .pattern(Some(format!("^{}$", LEI::PATTERN)))
+            .description(Some(
+                "A Legal Entity Identifier (ISO 17442): 18 uppercase alphanumeric characters \
+                 followed by two check digits.",
+            ))
+            .examples(["549300IYKILIU506KA05"])
+            .into()
+    }
+}
+
+impl ToSchema for LEI {
+    fn name() -> Cow<'static, str> {
+        Cow::Borrowed("LEI")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schema_describes_format() {
+        let schema = serde_json::to_value(LEI::schema()).unwrap();
+        assert_eq!(schema["type"], "string");
+        assert_eq!(schema["minLength"], 20);
+        assert_eq!(schema["maxLength"], 20);
+        assert_eq!(schema["examples"][0], "549300IYKILIU506KA05");
+        assert!(crate::validate(schema["examples"][0].as_str().unwrap()));
+        assert_eq!(LEI::name(), "LEI");
+    }
+}
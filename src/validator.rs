@@ -0,0 +1,99 @@
+#![warn(missing_docs)]
+//! # lei::validator
+//!
+//! A custom rule for the [validator](https://crates.io/crates/validator) derive, for request
+//! DTOs that keep LEIs as strings. Point `custom` at `validate_lei` on any field whose type
+//! dereferences to `str` (`String`, `&str`, `Cow<str>` and the like); `Option` fields are only
+//! checked when they are `Some`:
+//!
+//! ```
+//! use validator::Validate;
+//!
+//! #[derive(Validate)]
+//! struct Registration {
+//!     #[validate(custom(function = "lei::validator::validate_lei"))]
+//!     lei: String,
+//!     #[validate(custom(function = "lei::validator::validate_lei"))]
+//!     parent_lei: Option<String>,
+//! }
+//!
+//! let ok = Registration {
+//!     lei: "YZ83GD8L7GG84979J516".to_owned(),
+//!     parent_lei: None,
+//! };
+//! assert!(ok.validate().is_ok());
+//!
+//! let typo = Registration {
+//!     lei: "YZ83GD8L7GG84979J516".to_owned(),
+//!     parent_lei: Some("YZ83GD8L7GG84979J517".to_owned()),
+//! };
+//! let errors = typo.validate().unwrap_err();
+//! let error = &errors.field_errors()["parent_lei"][0];
+//! assert_eq!(error.code, "lei");
+//! assert_eq!(error.params["lei_error"], "LEI-E009");
+//! ```
+//!
+//! The `regex` rule with `LEI::PATTERN` is no substitute: it checks the format but not the
+//! _Check Digits_, so it lets typos like the one above through.
+
+use std::borrow::Cow;
+
+use ::validator::ValidationError;
+
+/// Validate `value` as an LEI with `parse()`. The `ValidationError` has code `"lei"`, the
+/// `LEIError` message, and the `LEIError` code (like `"LEI-E009"`) as its `lei_error` parameter.
+/// The derive adds the offending value as the `value` parameter.
+pub fn validate_lei<T: AsRef<str> + ?Sized>(value: &T) -> Result<(), ValidationError> {
+    crate::parse(value.as_ref()).map(|_| ()).map_err(|err| {
+        let mut error = ValidationError::new("lei").with_message(Cow::Owned(err.to_string()));
+        error.add_param(Cow::Borrowed("lei_error"), &err.code());
+        error
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::validator::Validate;
+
+    #[derive(Validate)]
+    struct Counterparty<'a> {
+        #[validate(custom(function = "validate_lei"))]
+        lei: &'a str,
+        #[validate(custom(function = "validate_lei", code = "counterparty_lei"))]
+        parent: Option<Cow<'a, str>>,
+    }
+
+    #[test]
+    fn reports_the_lei_error() {
+        let err = validate_lei("YZ83GD8L7GG84979J517").unwrap_err();
+        assert_eq!(err.code, "lei");
+        assert_eq!(
+            err.message.as_deref(),
+            Some("incorrect check digits \"17\" when expecting \"16\"")
+        );
+        assert_eq!(err.params["lei_error"], "LEI-E009");
+
+        assert!(validate_lei(&String::from("YZ83GD8L7GG84979J516")).is_ok());
+        assert!(validate_lei(" YZ83GD8L7GG84979J516").is_err());
+    }
+
+    #[test]
+    fn works_with_the_derive() {
+        let ok = Counterparty {
+            lei: "549300IYKILIU506KA05",
+            parent: Some(Cow::Borrowed("YZ83GD8L7GG84979J516")),
+        };
+        assert!(ok.validate().is_ok());
+
+        let bad = Counterparty {
+            lei: "549300IYKILIU506KA0",
+            parent: Some(Cow::Borrowed("bogus")),
+        };
+        let errors = bad.validate().unwrap_err();
+        let fields = errors.field_errors();
+        assert_eq!(fields["lei"][0].code, "lei");
+        assert_eq!(fields["lei"][0].params["value"], "549300IYKILIU506KA0");
+        assert_eq!(fields["parent"][0].code, "counterparty_lei");
+    }
+}
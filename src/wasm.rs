@@ -0,0 +1,228 @@
+#![warn(missing_docs)]
+//! # lei::wasm
+//!
+//! [wasm-bindgen](https://crates.io/crates/wasm-bindgen) bindings, so a web front end can
+//! validate LEIs with the same code as the back end. Built for `wasm32-unknown-unknown` (for
+//! example with `wasm-pack`), the module exports `parse()`, `parseLoose()` and `validate()`
+//! functions, a `Lei` class and a `LeiError` class:
+//!
+//! ```js
+//! import { parse, validate } from "lei";
+//!
+//! const lei = parse("YZ83GD8L7GG84979J516");
+//! console.log(lei.louId, lei.entityId, lei.checkDigits); // "YZ83" "GD8L7GG84979J5" "16"
+//!
+//! const error = validate("YZ83GD8L7GG84979J517");
+//! if (error) {
+//!     console.log(error.code, error.message); // "LEI-E009" "incorrect check digits ..."
+//!     input.setSelectionRange(error.spanStart, error.spanEnd);
+//! }
+//! ```
+//!
+//! `parse()` and `parseLoose()` throw a `LeiError` for an invalid LEI. Its `spanStart` and
+//! `spanEnd` are UTF-16 indices into the string that was passed in, as JavaScript strings and
+//! `setSelectionRange()` use, including for `parseLoose()`, which ignores surrounding whitespace.
+//! The same functions are callable from Rust:
+//!
+//! ```
+//! let err = lei::wasm::parse("YZ83GD8L7GG84979J517").unwrap_err();
+//! assert_eq!(err.code(), "LEI-E009");
+//! assert_eq!((err.span_start(), err.span_end()), (Some(18), Some(20)));
+//! ```
+
+use ::wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{LEIError, LEI};
+
+/// A valid LEI, exported to JavaScript as the `Lei` class.
+#[wasm_bindgen(js_name = Lei)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WasmLei(LEI);
+
+/// Why a value is not a valid LEI, exported to JavaScript as the `LeiError` class.
+#[wasm_bindgen(js_name = LeiError)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WasmLeiError {
+    error: LEIError,
+    /// The span of the error as UTF-16 indices into the input.
+    span: Option<(usize, usize)>,
+}
+
+impl WasmLeiError {
+    /// Wrap `error` from parsing `input`, whose first `skipped` bytes were not parsed, converting
+    /// its byte span into UTF-16 indices into `input`.
+    fn new(error: LEIError, input: &str, skipped: usize) -> WasmLeiError {
+        let utf16 = |byte: usize, round_up: bool| {
+            let mut byte = (skipped + byte).min(input.len());
+            while !input.is_char_boundary(byte) {
+                if round_up {
+                    byte += 1;
+                } else {
+                    byte -= 1;
+                }
+            }
+            input[..byte].encode_utf16().count()
+        };
+        let span = error
+            .span()
+            .map(|span| (utf16(span.start, false), utf16(span.end, true)));
+        WasmLeiError { error, span }
+    }
+}
+
+/// Parse `value` strictly, like `lei::parse()`.
+#[wasm_bindgen]
+pub fn parse(value: &str) -> Result<WasmLei, WasmLeiError> {
+    crate::parse(value)
+        .map(WasmLei)
+        .map_err(|err| WasmLeiError::new(err, value, 0))
+}
+
+/// Parse `value` like `lei::parse_loose()`, allowing surrounding whitespace and lowercase
+/// letters, for values typed by people.
+#[wasm_bindgen(js_name = parseLoose)]
+pub fn parse_loose(value: &str) -> Result<WasmLei, WasmLeiError> {
+    let skipped = value.len() - value.trim_start().len();
+    crate::parse_loose(value)
+        .map(WasmLei)
+        .map_err(|err| WasmLeiError::new(err, value, skipped))
+}
+
+/// Validate `value` strictly, returning the error (`undefined` in JavaScript if it is valid).
+#[wasm_bindgen]
+pub fn validate(value: &str) -> Option<WasmLeiError> {
+    crate::parse(value)
+        .err()
+        .map(|err| WasmLeiError::new(err, value, 0))
+}
+
+#[wasm_bindgen(js_class = Lei)]
+impl WasmLei {
+    /// The 20-character LEI.
+    #[wasm_bindgen(getter)]
+    pub fn value(&self) -> String {
+        self.0.as_str().to_owned()
+    }
+
+    /// The _LOU ID_, the first 4 characters.
+    #[wasm_bindgen(getter = louId)]
+    pub fn lou_id(&self) -> String {
+        self.0.lou_id().to_owned()
+    }
+
+    /// The _Entity ID_, the 14 characters after the _LOU ID_.
+    #[wasm_bindgen(getter = entityId)]
+    pub fn entity_id(&self) -> String {
+        self.0.entity_id().to_owned()
+    }
+
+    /// The _Check Digits_, the last 2 characters.
+    #[wasm_bindgen(getter = checkDigits)]
+    pub fn check_digits(&self) -> String {
+        self.0.check_digits().to_owned()
+    }
+
+    /// The 20-character LEI, as `toString()` in JavaScript.
+    #[wasm_bindgen(js_name = toString)]
+    pub fn as_string(&self) -> String {
+        self.value()
+    }
+}
+
+#[wasm_bindgen(js_class = LeiError)]
+impl WasmLeiError {
+    /// The stable error code, like `"LEI-E009"`.
+    #[wasm_bindgen(getter)]
+    pub fn code(&self) -> String {
+        self.error.code().to_owned()
+    }
+
+    /// The name of the error kind, like `"IncorrectCheckDigits"`.
+    #[wasm_bindgen(getter)]
+    pub fn kind(&self) -> String {
+        self.error.kind().name().to_owned()
+    }
+
+    /// The error message.
+    #[wasm_bindgen(getter)]
+    pub fn message(&self) -> String {
+        self.error.to_string()
+    }
+
+    /// The start of the offending characters, as a UTF-16 index into the input that was passed
+    /// in (unlike `LEIError::span()`, which counts bytes of the parsed string), or `undefined` for
+    /// errors about the length of the input.
+    #[wasm_bindgen(getter = spanStart)]
+    pub fn span_start(&self) -> Option<usize> {
+        self.span.map(|(start, _)| start)
+    }
+
+    /// The end (exclusive) of the offending characters, as a UTF-16 index like `spanStart`, or
+    /// `undefined` for errors about the length of the input.
+    #[wasm_bindgen(getter = spanEnd)]
+    pub fn span_end(&self) -> Option<usize> {
+        self.span.map(|(_, end)| end)
+    }
+}
+
+impl From<WasmLei> for LEI {
+    fn from(lei: WasmLei) -> LEI {
+        lei.0
+    }
+}
+
+impl From<WasmLeiError> for LEIError {
+    fn from(err: WasmLeiError) -> LEIError {
+        err.error
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_into_parts() {
+        let lei = parse("YZ83GD8L7GG84979J516").unwrap();
+        assert_eq!(lei.value(), "YZ83GD8L7GG84979J516");
+        assert_eq!(lei.lou_id(), "YZ83");
+        assert_eq!(lei.entity_id(), "GD8L7GG84979J5");
+        assert_eq!(lei.check_digits(), "16");
+        assert_eq!(lei.as_string(), "YZ83GD8L7GG84979J516");
+
+        assert_eq!(parse_loose(" yz83gd8l7gg84979j516 "), Ok(lei));
+        assert!(parse(" yz83gd8l7gg84979j516 ").is_err());
+        assert_eq!(validate("YZ83GD8L7GG84979J516"), None);
+    }
+
+    #[test]
+    fn describes_errors() {
+        let err = validate("5493-0IYKILIU5*6KA05").unwrap();
+        assert_eq!(err.code(), "LEI-E007");
+        assert_eq!(err.kind(), "InvalidEntityId");
+        assert_eq!((err.span_start(), err.span_end()), (Some(4), Some(15)));
+
+        let err = parse("YZ83").unwrap_err();
+        assert_eq!(err.code(), "LEI-E001");
+        assert_eq!(
+            err.message(),
+            LEIError::InvalidLength { was: 4 }.to_string()
+        );
+        assert_eq!((err.span_start(), err.span_end()), (None, None));
+    }
+
+    #[test]
+    fn spans_are_utf16_indices_into_the_input() {
+        let err = parse_loose("  yz83gd8l7gg84979j517").unwrap_err();
+        assert_eq!((err.span_start(), err.span_end()), (Some(20), Some(22)));
+
+        // 'é' is 2 bytes in UTF-8 but 1 unit in UTF-16, and '😀' is 4 bytes but 2 units.
+        let err = validate("YZ8éGD8L7GG84979J51").unwrap();
+        assert_eq!(err.code(), "LEI-E006");
+        assert_eq!((err.span_start(), err.span_end()), (Some(3), Some(4)));
+
+        let err = validate("YZ83😀8L7GG84979J5").unwrap();
+        assert_eq!(err.code(), "LEI-E007");
+        assert_eq!((err.span_start(), err.span_end()), (Some(4), Some(6)));
+    }
+}
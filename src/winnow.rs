@@ -0,0 +1,53 @@
+#![warn(missing_docs)]
+//! # lei::winnow
+//!
+//! An LEI parser for use with the [winnow](https://crates.io/crates/winnow) parser combinator
+//! library.
+
+use winnow::error::{FromExternalError, ParserError};
+use winnow::token::take;
+use winnow::Parser;
+
+use crate::{LEIError, LEI};
+
+/// Consume exactly 20 characters and validate them as an LEI with the strict `parse()`. On
+/// failure, the `LEIError` is passed to the error type through `FromExternalError`.
+///
+/// ```
+/// use winnow::Parser;
+///
+/// let mut record = ("LEI:", lei::winnow::lei::<winnow::error::ContextError>);
+/// let mut input = "LEI:YZ83GD8L7GG84979J516;EUR";
+/// let (_, lei) = record.parse_next(&mut input).unwrap();
+/// assert_eq!(lei.to_string(), "YZ83GD8L7GG84979J516");
+/// assert_eq!(input, ";EUR");
+/// ```
+pub fn lei<E>(input: &mut &str) -> Result<LEI, E>
+where
+    E: for<'i> ParserError<&'i str> + for<'i> FromExternalError<&'i str, LEIError>,
+{
+    take(20usize).try_map(crate::parse).parse_next(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use winnow::error::ContextError;
+
+    #[test]
+    fn consumes_exactly_twenty_characters() {
+        let mut input = "YZ83GD8L7GG84979J516YZ83";
+        let parsed = lei::<ContextError>(&mut input).unwrap();
+        assert_eq!(parsed.to_string(), "YZ83GD8L7GG84979J516");
+        assert_eq!(input, "YZ83");
+    }
+
+    #[test]
+    fn rejects_invalid_and_short_input() {
+        let mut input = "YZ83GD8L7GG84979J517";
+        assert!(lei::<ContextError>(&mut input).is_err());
+        let mut input = "YZ83GD8L7GG84979J5";
+        assert!(lei::<ContextError>(&mut input).is_err());
+        assert!(lei::<ContextError>.parse("YZ83GD8L7GG84979J516").is_ok());
+    }
+}
@@ -0,0 +1,393 @@
+#![warn(missing_docs)]
+//! # lei::xbrl
+//!
+//! Extraction of LEIs from XBRL and Inline XBRL (iXBRL) documents, such as ESEF annual financial
+//! reports.
+//!
+//! XBRL identifies the reporting entity of each _context_ with an `identifier` element whose
+//! `scheme` attribute names the identifier system. ESEF requires the LEI scheme,
+//! `http://standards.iso.org/iso/17442`:
+//!
+//! ```xml
+//! <xbrli:context id="c-1">
+//!   <xbrli:entity>
+//!     <xbrli:identifier scheme="http://standards.iso.org/iso/17442">YZ83GD8L7GG84979J516</xbrli:identifier>
+//!   </xbrli:entity>
+//!   ...
+//! </xbrli:context>
+//! ```
+//!
+//! This is a lightweight scanner rather than a full XML parser: it recognizes tags by their local
+//! name regardless of namespace prefix, reads quoted attribute values (which may contain `>`) and
+//! valueless attributes, decodes entity and character references, unwraps CDATA sections, and
+//! skips comments, processing instructions and declarations. That is sufficient for the
+//! well-formed documents produced by XBRL tooling.
+
+use std::borrow::Cow;
+
+use crate::{LEIError, LEI};
+
+/// The `scheme` attribute value identifying an LEI in XBRL entity identifiers.
+pub const LEI_SCHEME: &str = "http://standards.iso.org/iso/17442";
+
+/// An LEI entity identifier found in an XBRL document.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct XbrlIdentifier {
+    /// The `id` of the enclosing `context` element, if there is one.
+    pub context_id: Option<String>,
+    /// The text of the identifier, with CDATA sections unwrapped and entity and character
+    /// references decoded, but otherwise as it appeared in the document.
+    pub value: String,
+    /// The byte offset of the `identifier` start tag in the document.
+    pub offset: usize,
+    /// The result of validating the trimmed value as an LEI.
+    pub lei: Result<LEI, LEIError>,
+}
+
+/// A start or end tag found by the scanner.
+struct Tag<'a> {
+    offset: usize,
+    local_name: &'a str,
+    attributes: &'a str,
+    is_end: bool,
+    self_closing: bool,
+    end: usize,
+}
+
+/// The length of the tag at the start of `rest` up to and including its `>`, skipping over
+/// quoted attribute values.
+fn tag_len(rest: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in rest.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '>') => return Some(i + 1),
+            (Some(q), c) if c == q => quote = None,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Iterate over the start and end tags in a document, skipping comments, CDATA sections,
+/// processing instructions and declarations.
+fn tags(document: &str) -> impl Iterator<Item = Tag<'_>> {
+    let mut pos = 0;
+    std::iter::from_fn(move || loop {
+        let offset = pos + document[pos..].find('<')?;
+        let rest = &document[offset..];
+
+        if rest.starts_with("<!--") {
+            pos = offset + rest.find("-->").map(|i| i + 3).unwrap_or(rest.len());
+            continue;
+        }
+        if rest.starts_with("<![CDATA[") {
+            pos = offset + rest.find("]]>").map(|i| i + 3).unwrap_or(rest.len());
+            continue;
+        }
+
+        let len = tag_len(rest)?;
+        pos = offset + len;
+        if rest.starts_with("<?") || rest.starts_with("<!") {
+            continue;
+        }
+
+        let (inner, is_end) = match rest[1..len - 1].strip_prefix('/') {
+            Some(inner) => (inner, true),
+            None => (&rest[1..len - 1], false),
+        };
+        let (inner, self_closing) = match inner.strip_suffix('/') {
+            Some(i) => (i, true),
+            None => (inner, false),
+        };
+        let name_end = inner
+            .find(|c: char| c.is_ascii_whitespace())
+            .unwrap_or(inner.len());
+        let name = &inner[..name_end];
+        let local_name = name.rsplit(':').next().unwrap_or(name);
+
+        return Some(Tag {
+            offset,
+            local_name,
+            attributes: &inner[name_end..],
+            is_end,
+            self_closing,
+            end: pos,
+        });
+    })
+}
+
+/// Find the value of the named attribute in the attribute text of a start tag, with references
+/// decoded. Attributes without a value are skipped.
+fn attribute<'a>(attributes: &'a str, name: &str) -> Option<Cow<'a, str>> {
+    let mut rest = attributes;
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            return None;
+        }
+        let key_end = rest
+            .find(|c: char| c == '=' || c.is_ascii_whitespace())
+            .unwrap_or(rest.len());
+        let key = &rest[..key_end];
+        rest = rest[key_end..].trim_start();
+        let Some(after) = rest.strip_prefix('=') else {
+            continue;
+        };
+        let after = after.trim_start();
+        let quote = after.chars().next()?;
+        if quote != '"' && quote != '\'' {
+            return None;
+        }
+        let len = after[1..].find(quote)?;
+        if key == name {
+            return Some(decode(&after[1..1 + len]));
+        }
+        rest = &after[len + 2..];
+    }
+}
+
+/// Decode the predefined entity references and the character references in `text`. Unknown or
+/// malformed references are kept as they are.
+fn decode(text: &str) -> Cow<'_, str> {
+    if !text.contains('&') {
+        return Cow::Borrowed(text);
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest.find(';').and_then(|semi| {
+            let c = match &rest[1..semi] {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                r => {
+                    let code = match r.strip_prefix("#x").or_else(|| r.strip_prefix("#X")) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                        None => r.strip_prefix('#').and_then(|dec| dec.parse().ok()),
+                    };
+                    char::from_u32(code?)?
+                }
+            };
+            Some((c, semi + 1))
+        });
+        match decoded {
+            Some((c, len)) => {
+                out.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    Cow::Owned(out)
+}
+
+/// The text content starting at `start`, up to the next tag: character data with references
+/// decoded and CDATA sections unwrapped, skipping comments.
+fn text_content(document: &str, start: usize) -> String {
+    let mut text = String::new();
+    let mut rest = &document[start..];
+    loop {
+        let lt = rest.find('<').unwrap_or(rest.len());
+        text.push_str(&decode(&rest[..lt]));
+        rest = &rest[lt..];
+        if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+            let end = cdata.find("]]>").unwrap_or(cdata.len());
+            text.push_str(&cdata[..end]);
+            rest = cdata.get(end + 3..).unwrap_or_default();
+        } else if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment
+                .find("-->")
+                .map(|end| &comment[end + 3..])
+                .unwrap_or_default();
+        } else {
+            return text;
+        }
+    }
+}
+
+/// Extract every entity identifier using the LEI scheme from an XBRL or iXBRL document, in
+/// document order, each paired with its enclosing context and validation result.
+///
+/// ```
+/// let doc = r#"<xbrli:context id="c-1"><xbrli:entity>
+///   <xbrli:identifier scheme="http://standards.iso.org/iso/17442">YZ83GD8L7GG84979J516</xbrli:identifier>
+/// </xbrli:entity></xbrli:context>"#;
+///
+/// let found = lei::xbrl::extract(doc);
+/// assert_eq!(found.len(), 1);
+/// assert_eq!(found[0].context_id.as_deref(), Some("c-1"));
+/// assert_eq!(found[0].lei.as_ref().unwrap().to_string(), "YZ83GD8L7GG84979J516");
+/// ```
+pub fn extract(document: &str) -> Vec<XbrlIdentifier> {
+    let mut found = Vec::new();
+    let mut context_id: Option<Cow<'_, str>> = None;
+
+    for tag in tags(document) {
+        match tag.local_name {
+            "context" if tag.is_end => context_id = None,
+            "context" if !tag.self_closing => context_id = attribute(tag.attributes, "id"),
+            "identifier" if !tag.is_end && !tag.self_closing => {
+                let scheme = attribute(tag.attributes, "scheme");
+                if scheme.as_deref().map(str::trim) != Some(LEI_SCHEME) {
+                    continue;
+                }
+                let value = text_content(document, tag.end);
+                let lei = crate::parse(value.trim());
+                found.push(XbrlIdentifier {
+                    context_id: context_id.as_deref().map(str::to_owned),
+                    value,
+                    offset: tag.offset,
+                    lei,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    found
+}
+
+/// The distinct valid LEIs identifying reporting entities in a document, in order of first
+/// appearance. A well-formed ESEF filing yields exactly one.
+pub fn reporting_entities(document: &str) -> Vec<LEI> {
+    let mut leis: Vec<LEI> = Vec::new();
+    for identifier in extract(document) {
+        if let Ok(lei) = identifier.lei {
+            if !leis.contains(&lei) {
+                leis.push(lei);
+            }
+        }
+    }
+    leis
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IXBRL: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<html xmlns:ix="http://www.xbrl.org/2013/inlineXBRL" xmlns:xbrli="http://www.xbrl.org/2003/instance">
+<body>
+<!-- <xbrli:identifier scheme="http://standards.iso.org/iso/17442">IGNOREDINCOMMENT0000</xbrli:identifier> -->
+<ix:header><ix:resources>
+<xbrli:context id="c-1">
+  <xbrli:entity>
+    <xbrli:identifier scheme="http://standards.iso.org/iso/17442">YZ83GD8L7GG84979J516</xbrli:identifier>
+  </xbrli:entity>
+  <xbrli:period><xbrli:instant>2023-12-31</xbrli:instant></xbrli:period>
+</xbrli:context>
+<xbrli:context id='c-2'>
+  <xbrli:entity>
+    <xbrli:identifier scheme='http://standards.iso.org/iso/17442'> YZ83GD8L7GG84979J516 </xbrli:identifier>
+  </xbrli:entity>
+</xbrli:context>
+<context id="c-3">
+  <entity>
+    <identifier scheme="http://www.sec.gov/CIK">0000320193</identifier>
+  </entity>
+</context>
+<context id="c-4">
+  <entity>
+    <identifier scheme="http://standards.iso.org/iso/17442">YZ83GD8L7GG84979J517</identifier>
+  </entity>
+</context>
+</ix:resources></ix:header>
+</body>
+</html>"#;
+
+    #[test]
+    fn extracts_lei_scheme_identifiers_with_contexts() {
+        let found = extract(IXBRL);
+        let summary: Vec<(Option<&str>, bool)> = found
+            .iter()
+            .map(|f| (f.context_id.as_deref(), f.lei.is_ok()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (Some("c-1"), true),
+                (Some("c-2"), true),
+                (Some("c-4"), false)
+            ]
+        );
+        assert_eq!(found[1].value, " YZ83GD8L7GG84979J516 ");
+        assert!(IXBRL[found[0].offset..].starts_with("<xbrli:identifier"));
+        assert!(matches!(
+            found[2].lei,
+            Err(LEIError::IncorrectCheckDigits { .. })
+        ));
+    }
+
+    #[test]
+    fn valueless_attributes() {
+        assert_eq!(attribute(r#" hidden id="c1""#, "id").as_deref(), Some("c1"));
+        assert_eq!(attribute(r#" id hidden"#, "id"), None);
+
+        let doc = r#"<xbrli:context hidden id="c1"><xbrli:entity>
+  <xbrli:identifier scheme="http://standards.iso.org/iso/17442">YZ83GD8L7GG84979J516</xbrli:identifier>
+</xbrli:entity></xbrli:context>"#;
+        assert_eq!(extract(doc)[0].context_id.as_deref(), Some("c1"));
+    }
+
+    #[test]
+    fn cdata_and_references() {
+        let doc = r#"<context id="a&amp;b"><entity>
+  <identifier scheme="http://standards.iso.org/iso/17442"><![CDATA[YZ83GD8L7GG84979J516]]></identifier>
+  <identifier scheme="http://standards.iso.org/iso/17442">YZ83GD8L7GG84979J51&#x36;</identifier>
+  <identifier scheme="http://standards.iso.org/iso/17442">YZ83GD8L7GG84979J51&#54;<!-- x --></identifier>
+  <identifier scheme="http://standards.iso.org/iso/17442">YZ83&bogus;</identifier>
+</entity></context>"#;
+        let found = extract(doc);
+        let values: Vec<&str> = found.iter().map(|f| f.value.as_str()).collect();
+        assert_eq!(
+            values,
+            vec![
+                "YZ83GD8L7GG84979J516",
+                "YZ83GD8L7GG84979J516",
+                "YZ83GD8L7GG84979J516",
+                "YZ83&bogus;"
+            ]
+        );
+        assert!(found[..3].iter().all(|f| f.lei.is_ok()));
+        assert_eq!(found[0].context_id.as_deref(), Some("a&b"));
+    }
+
+    #[test]
+    fn context_ends() {
+        let doc = r#"<context id="c1"><entity>
+  <identifier scheme="http://standards.iso.org/iso/17442">YZ83GD8L7GG84979J516</identifier>
+</entity></context>
+<identifier scheme="http://standards.iso.org/iso/17442">YZ83GD8L7GG84979J516</identifier>"#;
+        let contexts: Vec<Option<String>> =
+            extract(doc).into_iter().map(|f| f.context_id).collect();
+        assert_eq!(contexts, vec![Some("c1".to_owned()), None]);
+    }
+
+    #[test]
+    fn greater_than_in_attribute_values() {
+        let doc = r#"<context id="c>1"><entity>
+  <identifier note='a > b' scheme="http://standards.iso.org/iso/17442">YZ83GD8L7GG84979J516</identifier>
+</entity></context>"#;
+        let found = extract(doc);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].context_id.as_deref(), Some("c>1"));
+        assert_eq!(found[0].value, "YZ83GD8L7GG84979J516");
+    }
+
+    #[test]
+    fn reporting_entities_are_distinct_and_valid() {
+        let leis = reporting_entities(IXBRL);
+        assert_eq!(leis.len(), 1);
+        assert_eq!(leis[0].to_string(), "YZ83GD8L7GG84979J516");
+        assert!(reporting_entities("<html></html>").is_empty());
+    }
+}